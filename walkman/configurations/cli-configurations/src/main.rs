@@ -1,36 +1,94 @@
 pub(crate) mod utils;
 
 use ::infrastructures::boundaries::AggregateView;
+use ::infrastructures::boundaries::SubscriptionListFormat;
+use ::infrastructures::boundaries::SubscriptionListView;
+use ::infrastructures::gateways::discarders::FilesystemDiscarder;
+use ::infrastructures::gateways::downloaders::HttpFeedDownloader;
+use ::infrastructures::gateways::downloaders::RateLimiter;
+use ::infrastructures::gateways::downloaders::RetryPolicy;
 use ::infrastructures::gateways::downloaders::YtdlpDownloader;
+use ::infrastructures::gateways::executors::CommandExecutor;
+use ::infrastructures::gateways::executors::LiveProcessGroups;
+use ::infrastructures::gateways::executors::RecordingCommandExecutor;
+use ::infrastructures::gateways::executors::ReplayCommandExecutor;
+use ::infrastructures::gateways::executors::TokioCommandExecutor;
+use ::infrastructures::gateways::formats::OpmlFormat;
+use ::infrastructures::gateways::formats::PlainTextFormat;
+use ::infrastructures::gateways::importers::FilesystemMediaImporter;
+use ::infrastructures::gateways::importers::TransferPolicy;
 use ::infrastructures::gateways::postprocessors::AlbumNamingPolicy;
 use ::infrastructures::gateways::postprocessors::ArtistsNamingPolicy;
+use ::infrastructures::gateways::postprocessors::ChapterSplitter;
+use ::infrastructures::gateways::postprocessors::ChapterSplittingPolicy;
+use ::infrastructures::gateways::postprocessors::CoverArtPolicy;
+use ::infrastructures::gateways::postprocessors::CoverArtWriter;
+use ::infrastructures::gateways::postprocessors::FolderCoverPolicy;
 use ::infrastructures::gateways::postprocessors::Id3MetadataWriter;
+use ::infrastructures::gateways::postprocessors::LoudnessNormalizationPolicy;
+use ::infrastructures::gateways::postprocessors::LoudnessNormalizer;
+use ::infrastructures::gateways::postprocessors::LyricsSidecarPolicy;
+use ::infrastructures::gateways::postprocessors::LyricsWriter;
+use ::infrastructures::gateways::postprocessors::TitleParser;
+use ::infrastructures::gateways::postprocessors::UploadNumberingPolicy;
+use ::infrastructures::gateways::profiles::DeviceProfile;
 use ::infrastructures::gateways::repositories::BincodeSerializer;
+use ::infrastructures::gateways::repositories::CompressedSerializedFilesystemLibraryRepository;
 use ::infrastructures::gateways::repositories::CompressedSerializedFilesystemResourcesRepository;
 use ::infrastructures::gateways::repositories::Compressor;
 use ::infrastructures::gateways::repositories::Flate2Compressor;
+use ::infrastructures::gateways::repositories::LibraryRecords;
 use ::infrastructures::gateways::repositories::Serializer;
 use ::use_cases::boundaries::Accept;
 use ::use_cases::boundaries::DownloadChannelOutputBoundary;
 use ::use_cases::boundaries::DownloadChannelRequestModel;
+use ::use_cases::boundaries::DownloadFeedOutputBoundary;
+use ::use_cases::boundaries::DownloadFeedRequestModel;
 use ::use_cases::boundaries::DownloadPlaylistOutputBoundary;
 use ::use_cases::boundaries::DownloadPlaylistRequestModel;
 use ::use_cases::boundaries::DownloadVideoOutputBoundary;
 use ::use_cases::boundaries::DownloadVideoRequestModel;
+use ::use_cases::boundaries::ExportSubscriptionsOutputBoundary;
+use ::use_cases::boundaries::ExportSubscriptionsRequestModel;
+use ::use_cases::boundaries::ImportMediaOutputBoundary;
+use ::use_cases::boundaries::ImportMediaRequestModel;
+use ::use_cases::boundaries::ImportSubscriptionsOutputBoundary;
+use ::use_cases::boundaries::ImportSubscriptionsRequestModel;
+use ::use_cases::boundaries::ListSubscriptionsOutputBoundary;
+use ::use_cases::boundaries::ListSubscriptionsRequestModel;
+use ::use_cases::boundaries::RemoveSubscriptionOutputBoundary;
+use ::use_cases::boundaries::RemoveSubscriptionRequestModel;
 use ::use_cases::boundaries::UpdateMediaOutputBoundary;
 use ::use_cases::boundaries::UpdateMediaRequestModel;
 use ::use_cases::gateways::ChannelDownloader;
+use ::use_cases::gateways::ChannelResolver;
+use ::use_cases::gateways::Discarder;
+use ::use_cases::gateways::Exporter;
+use ::use_cases::gateways::FeedDownloader;
+use ::use_cases::gateways::FeedResolver;
+use ::use_cases::gateways::Importer;
+use ::use_cases::gateways::LibraryRepository;
+use ::use_cases::gateways::MediaImporter;
 use ::use_cases::gateways::PlaylistDownloader;
+use ::use_cases::gateways::PlaylistResolver;
 use ::use_cases::gateways::PostProcessor;
 use ::use_cases::gateways::UrlRepository;
 use ::use_cases::gateways::VideoDownloader;
+use ::use_cases::gateways::VideoResolver;
 use ::use_cases::interactors::DownloadChannelInteractor;
+use ::use_cases::interactors::DownloadFeedInteractor;
 use ::use_cases::interactors::DownloadPlaylistInteractor;
 use ::use_cases::interactors::DownloadVideoInteractor;
+use ::use_cases::interactors::ExportSubscriptionsInteractor;
+use ::use_cases::interactors::ImportMediaInteractor;
+use ::use_cases::interactors::ImportSubscriptionsInteractor;
+use ::use_cases::interactors::ListSubscriptionsInteractor;
+use ::use_cases::interactors::RemoveSubscriptionInteractor;
 use ::use_cases::interactors::UpdateMediaInteractor;
 use ::use_cases::models::descriptors::ResolvedChannel;
 use ::use_cases::models::descriptors::ResolvedPlaylist;
 use ::use_cases::models::descriptors::ResolvedVideo;
+use ::use_cases::models::descriptors::SubscriptionUrls;
 
 use crate::utils::aliases::Fallible;
 use crate::utils::aliases::MaybeOwnedPath;
@@ -38,7 +96,7 @@ use crate::utils::aliases::MaybeOwnedString;
 use crate::utils::extensions::OptionExt;
 
 #[tokio::main]
-async fn main() -> Fallible<()> {
+async fn main() -> Fallible<::std::process::ExitCode> {
     let logger = ::tracing_appender::rolling::minutely("logs", "cli.log");
     let (logger, _logger_guard) = ::tracing_appender::non_blocking(logger);
 
@@ -56,15 +114,59 @@ async fn main() -> Fallible<()> {
         .subcommand(::clap::command!("download-video")
             .alias("download")
             .arg(::clap::arg!(-i --url <URL>)
-                .value_parser(::clap::value_parser!(::std::string::String))))
+                .value_parser(::clap::value_parser!(::std::string::String)))
+            .arg(::clap::arg!(--"dry-run")))
         .subcommand(::clap::command!("download-playlist")
             .arg(::clap::arg!(-i --url <URL>)
-                .value_parser(::clap::value_parser!(::std::string::String))))
+                .value_parser(::clap::value_parser!(::std::string::String)))
+            .arg(::clap::arg!(--"dry-run")))
         .subcommand(::clap::command!("download-channel")
             .arg(::clap::arg!(-i --url <URL>)
-                .value_parser(::clap::value_parser!(::std::string::String))))
+                .value_parser(::clap::value_parser!(::std::string::String)))
+            .arg(::clap::arg!(--"dry-run")))
+        .subcommand(::clap::command!("download-feed")
+            .arg(::clap::arg!(-i --url <URL>)
+                .value_parser(::clap::value_parser!(::std::string::String)))
+            .arg(::clap::arg!(--"dry-run")))
         .subcommand(::clap::command!("update-media")
-            .alias("update"))
+            .alias("update")
+            .arg(::clap::arg!(--mirror))
+            .arg(::clap::arg!(--"dry-run"))
+            .arg(::clap::arg!(--"trash-directory" [FOLDER])
+                .requires("mirror")
+                .value_parser(::clap::value_parser!(::std::path::PathBuf))))
+        .subcommand(::clap::command!("remove-subscription")
+            .alias("remove")
+            .arg(::clap::arg!(-i --url <URL>)
+                .value_parser(::clap::value_parser!(::std::string::String)))
+            .arg(::clap::arg!(--purge))
+            .arg(::clap::arg!(--"trash-directory" [FOLDER])
+                .requires("purge")
+                .value_parser(::clap::value_parser!(::std::path::PathBuf))))
+        .subcommand(::clap::command!("list-subscriptions")
+            .aliases(["list", "status"])
+            .arg(::clap::arg!(--format [FORMAT])
+                .default_value("table")
+                .value_parser(["table", "json"])))
+        .subcommand(::clap::command!("export-subscriptions")
+            .alias("export")
+            .arg(::clap::arg!(-f --file <FILE>)
+                .value_parser(::clap::value_parser!(::std::path::PathBuf)))
+            .arg(::clap::arg!(--format [FORMAT])
+                .default_value("opml")
+                .value_parser(["opml", "text"])))
+        .subcommand(::clap::command!("import-subscriptions")
+            .arg(::clap::arg!(-f --file <FILE>)
+                .value_parser(::clap::value_parser!(::std::path::PathBuf)))
+            .arg(::clap::arg!(--format [FORMAT])
+                .default_value("opml")
+                .value_parser(["opml", "text"])))
+        .subcommand(::clap::command!("import-media")
+            .visible_alias("import")
+            .arg(::clap::arg!(<FOLDER>)
+                .value_parser(::clap::value_parser!(::std::path::PathBuf)))
+            .arg(::clap::arg!(--"move"))
+            .arg(::clap::arg!(--postprocess)))
         .arg(::clap::arg!(-o --directory <FOLDER>)
             .value_parser(::clap::value_parser!(::std::path::PathBuf)))
         .arg(::clap::arg!(--"video-urls-path" [FILE])
@@ -73,17 +175,85 @@ async fn main() -> Fallible<()> {
             .value_parser(::clap::value_parser!(::std::path::PathBuf)))
         .arg(::clap::arg!(--"channel-urls-path" [FILE])
             .value_parser(::clap::value_parser!(::std::path::PathBuf)))
+        .arg(::clap::arg!(--"feed-urls-path" [FILE])
+            .value_parser(::clap::value_parser!(::std::path::PathBuf)))
+        .arg(::clap::arg!(--"library-path" [FILE])
+            .value_parser(::clap::value_parser!(::std::path::PathBuf)))
         .arg(::clap::arg!(-N --workers [NUMBER])
             .value_parser(::clap::value_parser!(u64)))
-        .arg(::clap::arg!(--"per-worker-cooldown" [MILLISECONDS])
-            .default_value("0")
+        .arg(::clap::arg!(--resolvers [NUMBER])
+            .value_parser(::clap::value_parser!(u64).range(1..)))
+        .arg(::clap::arg!(--"requests-per-minute" [NUMBER])
+            .value_parser(::clap::value_parser!(u64).range(1..)))
+        .arg(::clap::arg!(--"limit-rate" [RATE])
+            .value_parser(::clap::value_parser!(::bytesize::ByteSize)))
+        .arg(::clap::arg!(--"event-buffer" [NUMBER])
+            .default_value("256")
+            .value_parser(::clap::value_parser!(u64).range(1..)))
+        .arg(::clap::arg!(--"max-attempts" [NUMBER])
+            .default_value("3")
+            .value_parser(::clap::value_parser!(u64).range(1..)))
+        .arg(::clap::arg!(--"retry-base-delay" [MILLISECONDS])
+            .default_value("1000")
             .value_parser(::clap::value_parser!(u64)))
+        .arg(::clap::arg!(--"retry-jitter" [MILLISECONDS])
+            .default_value("500")
+            .value_parser(::clap::value_parser!(u64)))
+        .arg(::clap::arg!(--"retry-on" [MESSAGE])
+            .action(::clap::ArgAction::Append)
+            .value_parser(::clap::value_parser!(::std::string::String)))
+        .arg(::clap::arg!(--"record-commands" [FILE])
+            .conflicts_with("replay-commands")
+            .value_parser(::clap::value_parser!(::std::path::PathBuf)))
+        .arg(::clap::arg!(--"replay-commands" [FILE])
+            .value_parser(::clap::value_parser!(::std::path::PathBuf)))
+        .arg(::clap::arg!(--"device-profile" [NAME])
+            .default_value("nwz-b183f")
+            .value_parser(["nwz-b183f", "modern-player"]))
         .arg(::clap::arg!(--"set-video-album-as" [POLICY])
             .default_value("playlist-title")
             .value_parser(["video-album", "playlist-title"]))
         .arg(::clap::arg!(--"set-video-artists-as" [POLICY])
             .default_value("video-artists-and-channel-title")
-            .value_parser(["video-artists", "channel-title", "video-artists-and-channel-title"]));
+            .value_parser(["video-artists", "channel-title", "video-artists-and-channel-title"]))
+        .arg(::clap::arg!(--"parse-titles"))
+        .arg(::clap::arg!(--"title-rule" [REGEX])
+            .action(::clap::ArgAction::Append)
+            .requires("parse-titles")
+            .value_parser(|rule: &str| {
+                let rule = ::regex::Regex::new(rule)?;
+
+                match rule.capture_names().flatten().any(|name| name == "title") {
+                    true => Ok(rule),
+                    false => ::anyhow::bail!("the rule names no `title` group"),
+                }
+            }))
+        .arg(::clap::arg!(--"number-channel-uploads"))
+        .arg(::clap::arg!(--"tracks-per-disc" [NUMBER])
+            .value_parser(::clap::value_parser!(u64).range(1..)))
+        .arg(::clap::arg!(--"set-cover-art-as" [POLICY])
+            .default_value("video-thumbnail")
+            .value_parser(["video-thumbnail", "playlist-thumbnail"]))
+        .arg(::clap::arg!(--"write-folder-covers"))
+        .arg(::clap::arg!(--"write-lyrics"))
+        .arg(::clap::arg!(--"lyrics-languages" [LANGUAGES])
+            .default_value("en")
+            .value_delimiter(',')
+            .value_parser(::clap::value_parser!(::std::string::String)))
+        .arg(::clap::arg!(--"write-lrc-files")
+            .requires("write-lyrics"))
+        .arg(::clap::arg!(--"split-chapters" [POLICY])
+            .value_parser(["tracks", "chapters"]))
+        .arg(::clap::arg!(--"normalize-loudness" [POLICY])
+            .value_parser(["track", "album"]))
+        .arg(::clap::arg!(--"target-loudness" [LUFS])
+            .default_value("-16")
+            .allow_negative_numbers(true)
+            .value_parser(::clap::value_parser!(f64)))
+        .arg(::clap::arg!(--"true-peak" [DBTP])
+            .default_value("-1.5")
+            .allow_negative_numbers(true)
+            .value_parser(::clap::value_parser!(f64)));
 
     let matches = command.get_matches();
 
@@ -108,17 +278,104 @@ async fn main() -> Fallible<()> {
         .unwrap_or_else(|| directory.join("channel-urls.bin"))
         .to_owned()
         .into();
+    let feed_urls_path: MaybeOwnedPath = matches
+        .get_one::<::std::path::PathBuf>("feed-urls-path")
+        .cloned()
+        .unwrap_or_else(|| directory.join("feed-urls.bin"))
+        .to_owned()
+        .into();
+    let library_path: MaybeOwnedPath = matches
+        .get_one::<::std::path::PathBuf>("library-path")
+        .cloned()
+        .unwrap_or_else(|| directory.join("library.bin"))
+        .to_owned()
+        .into();
 
     let workers = matches
         .get_one::<u64>("workers")
         .ok()
         .copied()
         .unwrap_or_else(|_| ::num_cpus::get() as u64);
-    let per_worker_cooldown = matches
-        .get_one::<u64>("per-worker-cooldown")
-        .map(|cooldown| ::std::time::Duration::from_millis(*cooldown))
+    let resolvers = matches.get_one::<u64>("resolvers").copied().unwrap_or(workers);
+    let requests_per_minute = matches.get_one::<u64>("requests-per-minute").copied();
+    let bytes_per_second = matches
+        .get_one::<::bytesize::ByteSize>("limit-rate")
+        .map(::bytesize::ByteSize::as_u64);
+
+    let event_buffer = *matches.get_one::<u64>("event-buffer").ok()? as usize;
+
+    let max_attempts = *matches.get_one::<u64>("max-attempts").ok()?;
+    let retry_base_delay = matches
+        .get_one::<u64>("retry-base-delay")
+        .map(|delay| ::std::time::Duration::from_millis(*delay))
         .ok()?;
+    let retry_jitter = matches
+        .get_one::<u64>("retry-jitter")
+        .map(|jitter| ::std::time::Duration::from_millis(*jitter))
+        .ok()?;
+    let retryable_messages: Option<Vec<MaybeOwnedString>> = matches
+        .get_many::<::std::string::String>("retry-on")
+        .map(|messages| messages.cloned().map(Into::into).collect());
+
+    let record_commands_path: Option<MaybeOwnedPath> = matches
+        .get_one::<::std::path::PathBuf>("record-commands")
+        .cloned()
+        .map(Into::into);
+    let replay_commands_path: Option<MaybeOwnedPath> = matches
+        .get_one::<::std::path::PathBuf>("replay-commands")
+        .cloned()
+        .map(Into::into);
+
+    let dry_run = matches
+        .subcommand()
+        .and_then(|(_, matches)| matches.try_get_one::<bool>("dry-run").ok().flatten())
+        .copied()
+        .unwrap_or_default();
+    let mirror = matches
+        .subcommand_matches("update-media")
+        .is_some_and(|matches| matches.get_flag("mirror"));
+    let purge = matches
+        .subcommand_matches("remove-subscription")
+        .is_some_and(|matches| matches.get_flag("purge"));
+    let transfer_policy = match matches
+        .subcommand_matches("import-media")
+        .is_some_and(|matches| matches.get_flag("move"))
+    {
+        true => TransferPolicy::MoveFiles,
+        false => TransferPolicy::CopyFiles,
+    };
+    let postprocess = matches
+        .subcommand_matches("import-media")
+        .is_some_and(|matches| matches.get_flag("postprocess"));
+    let trash_directory: Option<MaybeOwnedPath> = matches
+        .subcommand()
+        .and_then(|(_, matches)| matches.try_get_one::<::std::path::PathBuf>("trash-directory").ok().flatten())
+        .cloned()
+        .map(Into::into);
+
+    let subscription_list_format = match matches
+        .subcommand_matches("list-subscriptions")
+        .and_then(|matches| matches.get_one::<::std::string::String>("format"))
+        .map(::std::string::String::as_str)
+    {
+        Some("table") | None => SubscriptionListFormat::Table,
+        Some("json") => SubscriptionListFormat::Json,
+        _ => panic!(),
+    };
 
+    let subscriptions_format = matches
+        .subcommand()
+        .filter(|(name, _)| ["export-subscriptions", "import-subscriptions"].contains(name))
+        .and_then(|(_, matches)| matches.get_one::<::std::string::String>("format"))
+        .map(::std::string::String::as_str)
+        .unwrap_or("opml")
+        .to_owned();
+
+    let device_profile = match matches.get_one::<::std::string::String>("device-profile").ok()? as &str {
+        "nwz-b183f" => DeviceProfile::nwz_b183f(),
+        "modern-player" => DeviceProfile::modern_player(),
+        _ => panic!(),
+    };
     let album_naming_policy = match matches.get_one::<::std::string::String>("set-video-album-as").ok()? as &str {
         "video-album" => AlbumNamingPolicy::UseVideoAlbum,
         "playlist-title" => AlbumNamingPolicy::UsePlaylistTitle,
@@ -130,9 +387,75 @@ async fn main() -> Fallible<()> {
         "video-artists-and-channel-title" => ArtistsNamingPolicy::UseBothVideoArtistsAndChannelTitle,
         _ => panic!(),
     };
+    let title_rules: Vec<::regex::Regex> = matches
+        .get_many::<::regex::Regex>("title-rule")
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect();
+    let parse_titles = matches.get_flag("parse-titles");
+    let upload_numbering_policy = match matches.get_flag("number-channel-uploads") {
+        true => UploadNumberingPolicy::NumberUploadsChronologically,
+        false => UploadNumberingPolicy::LeaveUploadsUnnumbered,
+    };
+    let tracks_per_disc = matches.get_one::<u64>("tracks-per-disc").copied();
+    // Playlist thumbnails only make sense as covers when the playlists make up the
+    // albums, so that asking for them otherwise is refused rather than ignored
+    let cover_art_policy = match (
+        matches.get_one::<::std::string::String>("set-video-album-as").ok()? as &str,
+        matches.get_one::<::std::string::String>("set-cover-art-as").ok()? as &str,
+    ) {
+        ("playlist-title", "playlist-thumbnail") => CoverArtPolicy::UsePlaylistThumbnail,
+        (album, "playlist-thumbnail") => ::anyhow::bail!(
+            "`--set-cover-art-as playlist-thumbnail` needs `--set-video-album-as playlist-title`, not `{}`",
+            album
+        ),
+        (_, "video-thumbnail") => CoverArtPolicy::UseVideoThumbnail,
+        _ => panic!(),
+    };
+    let folder_cover_policy = match matches.get_flag("write-folder-covers") {
+        true => FolderCoverPolicy::WriteFolderCovers,
+        false => FolderCoverPolicy::SkipFolderCovers,
+    };
+    let write_lyrics = matches.get_flag("write-lyrics");
+    let lyrics_languages: Vec<MaybeOwnedString> = matches
+        .get_many::<::std::string::String>("lyrics-languages")
+        .ok()?
+        .cloned()
+        .map(Into::into)
+        .collect();
+    let lyrics_sidecar_policy = match matches.get_flag("write-lrc-files") {
+        true => LyricsSidecarPolicy::WriteLrcFiles,
+        false => LyricsSidecarPolicy::SkipLrcFiles,
+    };
+    let chapter_splitting_policy = match matches
+        .get_one::<::std::string::String>("split-chapters")
+        .map(::std::string::String::as_str)
+    {
+        Some("tracks") => Some(ChapterSplittingPolicy::SplitIntoTracks),
+        Some("chapters") => Some(ChapterSplittingPolicy::MarkChapters),
+        None => None,
+        _ => panic!(),
+    };
+    let loudness_normalization_policy = match matches
+        .get_one::<::std::string::String>("normalize-loudness")
+        .map(::std::string::String::as_str)
+    {
+        Some("track") => Some(LoudnessNormalizationPolicy::NormalizeEachTrack),
+        Some("album") => Some(LoudnessNormalizationPolicy::KeepAlbumLevels),
+        None => None,
+        _ => panic!(),
+    };
+    let target_loudness = *matches.get_one::<f64>("target-loudness").ok()?;
+    let true_peak = *matches.get_one::<f64>("true-peak").ok()?;
+
+    // Cancellation
+    let cancellation = ::tokio_util::sync::CancellationToken::new();
 
     // Boundaries
-    let view = ::std::sync::Arc::new(AggregateView::builder().build());
+    let view = ::std::sync::Arc::new(AggregateView::builder().cancellation(cancellation.clone()).build());
+    let subscription_list_view =
+        ::std::sync::Arc::new(SubscriptionListView::builder().format(subscription_list_format).build());
 
     // Gateways
     let serializer = ::std::sync::Arc::new(
@@ -151,67 +474,335 @@ async fn main() -> Fallible<()> {
             .video_urls_path(video_urls_path)
             .playlist_urls_path(playlist_urls_path)
             .channel_urls_path(channel_urls_path)
+            .feed_urls_path(feed_urls_path)
             .build()
             .await?,
     );
+    let library = ::std::sync::Arc::new(
+        CompressedSerializedFilesystemLibraryRepository::builder()
+            .serializer(::std::sync::Arc::clone(&serializer) as ::std::sync::Arc<dyn Serializer<LibraryRecords>>)
+            .compressor(::std::sync::Arc::clone(&compressor) as ::std::sync::Arc<dyn Compressor>)
+            .library_path(library_path)
+            .build()
+            .await?,
+    );
+
+    let process_groups = ::std::sync::Arc::new(LiveProcessGroups::default());
+
+    ::tokio::spawn({
+        let cancellation = cancellation.clone();
+        let process_groups = ::std::sync::Arc::clone(&process_groups);
+        let urls = ::std::sync::Arc::clone(&urls);
+        let library = ::std::sync::Arc::clone(&library);
+
+        async move {
+            while ShutdownSignal::received().await.is_ok() {
+                // A second signal skips the graceful shutdown altogether, but still
+                // waits for the repositories, so that no file is left half written.
+                // Children sit in process groups of their own, which the signal never
+                // reaches, so that they are killed here rather than left orphaned
+                if cancellation.is_cancelled() {
+                    process_groups.terminate();
+
+                    urls.flush().await.ok();
+                    library.flush().await.ok();
+
+                    ::std::process::exit(130);
+                }
+
+                cancellation.cancel();
+            }
+        }
+    });
+
+    let rate_limiter = ::std::sync::Arc::new(
+        RateLimiter::builder()
+            .requests_per_minute(requests_per_minute)
+            .bytes_per_second(bytes_per_second)
+            .build(),
+    );
+
+    let retry_policy = RetryPolicy::builder()
+        .max_attempts(max_attempts)
+        .base_delay(retry_base_delay)
+        .jitter(retry_jitter)
+        .maybe_retryable_messages(retryable_messages)
+        .build();
+
+    let executor: ::std::sync::Arc<dyn CommandExecutor> = match (record_commands_path, replay_commands_path) {
+        (_, Some(path)) => ::std::sync::Arc::new(
+            ReplayCommandExecutor::builder()
+                .path(path)
+                .cancellation(cancellation.clone())
+                .build()
+                .await?,
+        ),
+        (Some(path), None) => ::std::sync::Arc::new(
+            RecordingCommandExecutor::builder()
+                .executor(::std::sync::Arc::new(
+                    TokioCommandExecutor::builder()
+                        .cancellation(cancellation.clone())
+                        .process_groups(::std::sync::Arc::clone(&process_groups))
+                        .build(),
+                ) as ::std::sync::Arc<dyn CommandExecutor>)
+                .path(path)
+                .build(),
+        ),
+        (None, None) => ::std::sync::Arc::new(
+            TokioCommandExecutor::builder()
+                .cancellation(cancellation.clone())
+                .process_groups(::std::sync::Arc::clone(&process_groups))
+                .build(),
+        ),
+    };
 
     let downloader = ::std::sync::Arc::new(
         YtdlpDownloader::builder()
-            .directory(directory)
+            .directory(directory.clone())
+            .profile(device_profile.clone())
+            .workers(workers)
+            .resolvers(resolvers)
+            .rate_limiter(::std::sync::Arc::clone(&rate_limiter))
+            .retry_policy(retry_policy)
+            .cancellation(cancellation.clone())
+            .executor(::std::sync::Arc::clone(&executor))
+            .event_buffer(event_buffer)
+            .build(),
+    );
+    let feed_downloader = ::std::sync::Arc::new(
+        HttpFeedDownloader::builder()
+            .directory(directory.clone())
+            .profile(device_profile.clone())
             .workers(workers)
-            .per_worker_cooldown(per_worker_cooldown)
+            .rate_limiter(::std::sync::Arc::clone(&rate_limiter))
+            .cancellation(cancellation.clone())
+            .executor(::std::sync::Arc::clone(&executor))
+            .event_buffer(event_buffer)
             .build(),
     );
 
+    let media_importer = ::std::sync::Arc::new(
+        FilesystemMediaImporter::builder()
+            .directory(directory)
+            .profile(device_profile.clone())
+            .transfer_policy(transfer_policy)
+            .cancellation(cancellation.clone())
+            .event_buffer(event_buffer)
+            .build(),
+    );
+
+    let title_parser = parse_titles.then(|| ::std::sync::Arc::new(TitleParser::builder().rules(title_rules).build()));
     let metadata_writer = ::std::sync::Arc::new(
         Id3MetadataWriter::builder()
             .album_naming_policy(album_naming_policy)
             .artists_naming_policy(artists_naming_policy)
+            .upload_numbering_policy(upload_numbering_policy)
+            .maybe_tracks_per_disc(tracks_per_disc)
+            .profile(device_profile.clone())
             .build(),
     );
+    let cover_art_writer = ::std::sync::Arc::new(
+        CoverArtWriter::builder()
+            .cover_art_policy(cover_art_policy)
+            .folder_cover_policy(folder_cover_policy)
+            .workers(workers)
+            .executor(::std::sync::Arc::clone(&executor))
+            .profile(device_profile.clone())
+            .build(),
+    );
+    let lyrics_writer = write_lyrics.then(|| {
+        ::std::sync::Arc::new(
+            LyricsWriter::builder()
+                .languages(lyrics_languages)
+                .sidecar_policy(lyrics_sidecar_policy)
+                .workers(workers)
+                .rate_limiter(::std::sync::Arc::clone(&rate_limiter))
+                .executor(::std::sync::Arc::clone(&executor))
+                .profile(device_profile.clone())
+                .build(),
+        )
+    });
+    let chapter_splitter = chapter_splitting_policy.map(|policy| {
+        ::std::sync::Arc::new(
+            ChapterSplitter::builder()
+                .splitting_policy(policy)
+                .rate_limiter(rate_limiter)
+                .executor(::std::sync::Arc::clone(&executor))
+                .profile(device_profile.clone())
+                .build(),
+        )
+    });
+    let loudness_normalizer = loudness_normalization_policy.map(|policy| {
+        ::std::sync::Arc::new(
+            LoudnessNormalizer::builder()
+                .normalization_policy(policy)
+                .target_loudness(target_loudness)
+                .true_peak(true_peak)
+                .workers(workers)
+                .executor(executor)
+                .profile(device_profile)
+                .build(),
+        )
+    });
+
+    let discarder =
+        ::std::sync::Arc::new(FilesystemDiscarder::builder().maybe_trash_directory(trash_directory).build());
 
-    let video_postprocessors: Vec<::std::sync::Arc<dyn PostProcessor<ResolvedVideo>>> =
-        vec![::std::sync::Arc::clone(&metadata_writer) as ::std::sync::Arc<dyn PostProcessor<ResolvedVideo>>];
-    let playlist_postprocessors: Vec<::std::sync::Arc<dyn PostProcessor<ResolvedPlaylist>>> =
-        vec![::std::sync::Arc::clone(&metadata_writer) as ::std::sync::Arc<dyn PostProcessor<ResolvedPlaylist>>];
-    let channel_postprocessors: Vec<::std::sync::Arc<dyn PostProcessor<ResolvedChannel>>> =
-        vec![::std::sync::Arc::clone(&metadata_writer) as ::std::sync::Arc<dyn PostProcessor<ResolvedChannel>>];
+    let (subscriptions_exporter, subscriptions_importer): (
+        ::std::sync::Arc<dyn Exporter<SubscriptionUrls>>,
+        ::std::sync::Arc<dyn Importer<SubscriptionUrls>>,
+    ) = match subscriptions_format.as_str() {
+        "opml" => {
+            let format = ::std::sync::Arc::new(OpmlFormat::builder().build());
+            (::std::sync::Arc::clone(&format) as _, format as _)
+        },
+        "text" => {
+            let format = ::std::sync::Arc::new(PlainTextFormat);
+            (::std::sync::Arc::clone(&format) as _, format as _)
+        },
+        format => ::anyhow::bail!("unexpected subscriptions format `{}`", format),
+    };
+
+    // The title parser goes first, since everything after it reads the titles, and
+    // the splitter goes last, since the tracks it cuts take the place of the file
+    let video_postprocessors: Vec<::std::sync::Arc<dyn PostProcessor<ResolvedVideo>>> = title_parser
+        .clone()
+        .map(|parser| parser as _)
+        .into_iter()
+        .chain([::std::sync::Arc::clone(&metadata_writer) as _, ::std::sync::Arc::clone(&cover_art_writer) as _])
+        .chain(lyrics_writer.clone().map(|writer| writer as _))
+        .chain(loudness_normalizer.clone().map(|normalizer| normalizer as _))
+        .chain(chapter_splitter.clone().map(|splitter| splitter as _))
+        .collect();
+    let playlist_postprocessors: Vec<::std::sync::Arc<dyn PostProcessor<ResolvedPlaylist>>> = title_parser
+        .clone()
+        .map(|parser| parser as _)
+        .into_iter()
+        .chain([::std::sync::Arc::clone(&metadata_writer) as _, ::std::sync::Arc::clone(&cover_art_writer) as _])
+        .chain(lyrics_writer.clone().map(|writer| writer as _))
+        .chain(loudness_normalizer.clone().map(|normalizer| normalizer as _))
+        .chain(chapter_splitter.clone().map(|splitter| splitter as _))
+        .collect();
+    let channel_postprocessors: Vec<::std::sync::Arc<dyn PostProcessor<ResolvedChannel>>> = title_parser
+        .map(|parser| parser as _)
+        .into_iter()
+        .chain([::std::sync::Arc::clone(&metadata_writer) as _, ::std::sync::Arc::clone(&cover_art_writer) as _])
+        .chain(lyrics_writer.map(|writer| writer as _))
+        .chain(loudness_normalizer.map(|normalizer| normalizer as _))
+        .chain(chapter_splitter.map(|splitter| splitter as _))
+        .collect();
+    // Existing tags are kept as they are unless asked otherwise, so that nothing is
+    // lost to the naming policies
+    let import_postprocessors = match postprocess {
+        true => video_postprocessors.clone(),
+        false => Vec::new(),
+    };
 
     // Interactors
     let download_video_interactor: std::sync::Arc<DownloadVideoInteractor> = ::std::sync::Arc::new(
         DownloadVideoInteractor::builder()
             .view(::std::sync::Arc::clone(&view) as ::std::sync::Arc<dyn DownloadVideoOutputBoundary>)
             .urls(::std::sync::Arc::clone(&urls) as ::std::sync::Arc<dyn UrlRepository>)
+            .library(::std::sync::Arc::clone(&library) as ::std::sync::Arc<dyn LibraryRepository>)
             .downloader(::std::sync::Arc::clone(&downloader) as ::std::sync::Arc<dyn VideoDownloader>)
+            .resolver(::std::sync::Arc::clone(&downloader) as ::std::sync::Arc<dyn VideoResolver>)
             .postprocessors(video_postprocessors.clone())
+            .dry_run(dry_run)
             .build(),
     );
     let download_playlist_interactor = ::std::sync::Arc::new(
         DownloadPlaylistInteractor::builder()
             .view(::std::sync::Arc::clone(&view) as ::std::sync::Arc<dyn DownloadPlaylistOutputBoundary>)
             .urls(::std::sync::Arc::clone(&urls) as ::std::sync::Arc<dyn UrlRepository>)
+            .library(::std::sync::Arc::clone(&library) as ::std::sync::Arc<dyn LibraryRepository>)
             .downloader(::std::sync::Arc::clone(&downloader) as ::std::sync::Arc<dyn PlaylistDownloader>)
+            .resolver(::std::sync::Arc::clone(&downloader) as ::std::sync::Arc<dyn PlaylistResolver>)
             .postprocessors(playlist_postprocessors.clone())
+            .dry_run(dry_run)
             .build(),
     );
     let download_channel_interactor = ::std::sync::Arc::new(
         DownloadChannelInteractor::builder()
             .view(::std::sync::Arc::clone(&view) as ::std::sync::Arc<dyn DownloadChannelOutputBoundary>)
             .urls(::std::sync::Arc::clone(&urls) as ::std::sync::Arc<dyn UrlRepository>)
+            .library(::std::sync::Arc::clone(&library) as ::std::sync::Arc<dyn LibraryRepository>)
             .downloader(::std::sync::Arc::clone(&downloader) as ::std::sync::Arc<dyn ChannelDownloader>)
+            .resolver(::std::sync::Arc::clone(&downloader) as ::std::sync::Arc<dyn ChannelResolver>)
             .postprocessors(channel_postprocessors.clone())
+            .dry_run(dry_run)
+            .build(),
+    );
+    let download_feed_interactor = ::std::sync::Arc::new(
+        DownloadFeedInteractor::builder()
+            .view(::std::sync::Arc::clone(&view) as ::std::sync::Arc<dyn DownloadFeedOutputBoundary>)
+            .urls(::std::sync::Arc::clone(&urls) as ::std::sync::Arc<dyn UrlRepository>)
+            .library(::std::sync::Arc::clone(&library) as ::std::sync::Arc<dyn LibraryRepository>)
+            .downloader(::std::sync::Arc::clone(&feed_downloader) as ::std::sync::Arc<dyn FeedDownloader>)
+            .resolver(::std::sync::Arc::clone(&feed_downloader) as ::std::sync::Arc<dyn FeedResolver>)
+            .postprocessors(playlist_postprocessors.clone())
+            .dry_run(dry_run)
             .build(),
     );
     let update_media_interactor = ::std::sync::Arc::new(
         UpdateMediaInteractor::builder()
             .view(::std::sync::Arc::clone(&view) as ::std::sync::Arc<dyn UpdateMediaOutputBoundary>)
             .urls(::std::sync::Arc::clone(&urls) as ::std::sync::Arc<dyn UrlRepository>)
+            .library(::std::sync::Arc::clone(&library) as ::std::sync::Arc<dyn LibraryRepository>)
             .video_downloader(::std::sync::Arc::clone(&downloader) as ::std::sync::Arc<dyn VideoDownloader>)
             .playlist_downloader(::std::sync::Arc::clone(&downloader) as ::std::sync::Arc<dyn PlaylistDownloader>)
             .channel_downloader(::std::sync::Arc::clone(&downloader) as ::std::sync::Arc<dyn ChannelDownloader>)
+            .feed_downloader(::std::sync::Arc::clone(&feed_downloader) as ::std::sync::Arc<dyn FeedDownloader>)
+            .video_resolver(::std::sync::Arc::clone(&downloader) as ::std::sync::Arc<dyn VideoResolver>)
+            .playlist_resolver(::std::sync::Arc::clone(&downloader) as ::std::sync::Arc<dyn PlaylistResolver>)
+            .channel_resolver(::std::sync::Arc::clone(&downloader) as ::std::sync::Arc<dyn ChannelResolver>)
+            .feed_resolver(::std::sync::Arc::clone(&feed_downloader) as ::std::sync::Arc<dyn FeedResolver>)
             .video_postprocessors(video_postprocessors.clone())
             .playlist_postprocessors(playlist_postprocessors.clone())
             .channel_postprocessors(channel_postprocessors.clone())
+            .maybe_discarder(
+                mirror.then(|| ::std::sync::Arc::clone(&discarder) as ::std::sync::Arc<dyn Discarder<ResolvedVideo>>),
+            )
+            .dry_run(dry_run)
+            .build(),
+    );
+    let remove_subscription_interactor = ::std::sync::Arc::new(
+        RemoveSubscriptionInteractor::builder()
+            .view(::std::sync::Arc::clone(&view) as ::std::sync::Arc<dyn RemoveSubscriptionOutputBoundary>)
+            .urls(::std::sync::Arc::clone(&urls) as ::std::sync::Arc<dyn UrlRepository>)
+            .library(::std::sync::Arc::clone(&library) as ::std::sync::Arc<dyn LibraryRepository>)
+            .maybe_discarder(
+                purge.then(|| ::std::sync::Arc::clone(&discarder) as ::std::sync::Arc<dyn Discarder<ResolvedVideo>>),
+            )
+            .build(),
+    );
+    let list_subscriptions_interactor = ::std::sync::Arc::new(
+        ListSubscriptionsInteractor::builder()
+            .view(::std::sync::Arc::clone(&subscription_list_view)
+                as ::std::sync::Arc<dyn ListSubscriptionsOutputBoundary>)
+            .urls(::std::sync::Arc::clone(&urls) as ::std::sync::Arc<dyn UrlRepository>)
+            .library(::std::sync::Arc::clone(&library) as ::std::sync::Arc<dyn LibraryRepository>)
+            .build(),
+    );
+    let export_subscriptions_interactor = ::std::sync::Arc::new(
+        ExportSubscriptionsInteractor::builder()
+            .view(::std::sync::Arc::clone(&view) as ::std::sync::Arc<dyn ExportSubscriptionsOutputBoundary>)
+            .urls(::std::sync::Arc::clone(&urls) as ::std::sync::Arc<dyn UrlRepository>)
+            .exporter(subscriptions_exporter)
+            .build(),
+    );
+    let import_subscriptions_interactor = ::std::sync::Arc::new(
+        ImportSubscriptionsInteractor::builder()
+            .view(::std::sync::Arc::clone(&view) as ::std::sync::Arc<dyn ImportSubscriptionsOutputBoundary>)
+            .urls(::std::sync::Arc::clone(&urls) as ::std::sync::Arc<dyn UrlRepository>)
+            .importer(subscriptions_importer)
+            .build(),
+    );
+    let import_media_interactor = ::std::sync::Arc::new(
+        ImportMediaInteractor::builder()
+            .view(::std::sync::Arc::clone(&view) as ::std::sync::Arc<dyn ImportMediaOutputBoundary>)
+            .library(::std::sync::Arc::clone(&library) as ::std::sync::Arc<dyn LibraryRepository>)
+            .importer(::std::sync::Arc::clone(&media_importer) as ::std::sync::Arc<dyn MediaImporter>)
+            .postprocessors(import_postprocessors)
             .build(),
     );
 
@@ -232,13 +823,63 @@ async fn main() -> Fallible<()> {
             let request = DownloadChannelRequestModel::builder().url(url).build();
             download_channel_interactor.accept(request).await?;
         },
+        Some(("download-feed", matches)) => {
+            let url = matches.get_one::<::std::string::String>("url").ok()?.to_owned();
+            let request = DownloadFeedRequestModel::builder().url(url).build();
+            download_feed_interactor.accept(request).await?;
+        },
         Some(("update-media", _)) => {
             let request = UpdateMediaRequestModel;
             update_media_interactor.accept(request).await?;
         },
+        Some(("remove-subscription", matches)) => {
+            let url = matches.get_one::<::std::string::String>("url").ok()?.to_owned();
+            let request = RemoveSubscriptionRequestModel::builder().url(url).build();
+            remove_subscription_interactor.accept(request).await?;
+        },
+        Some(("list-subscriptions", _)) => {
+            let request = ListSubscriptionsRequestModel;
+            list_subscriptions_interactor.accept(request).await?;
+        },
+        Some(("export-subscriptions", matches)) => {
+            let path = matches.get_one::<::std::path::PathBuf>("file").ok()?.to_owned();
+            let request = ExportSubscriptionsRequestModel::builder().path(path).build();
+            export_subscriptions_interactor.accept(request).await?;
+        },
+        Some(("import-subscriptions", matches)) => {
+            let path = matches.get_one::<::std::path::PathBuf>("file").ok()?.to_owned();
+            let request = ImportSubscriptionsRequestModel::builder().path(path).build();
+            import_subscriptions_interactor.accept(request).await?;
+        },
+        Some(("import-media", matches)) => {
+            let path = matches.get_one::<::std::path::PathBuf>("FOLDER").ok()?.to_owned();
+            let request = ImportMediaRequestModel::builder().path(path).build();
+            import_media_interactor.accept(request).await?;
+        },
 
         _ => unreachable!(),
     }
 
-    Ok(())
+    Ok(view.exit_code())
+}
+
+struct ShutdownSignal;
+
+impl ShutdownSignal {
+    async fn received() -> Fallible<()> {
+        #[cfg(unix)]
+        {
+            let mut terminate = ::tokio::signal::unix::signal(::tokio::signal::unix::SignalKind::terminate())?;
+
+            ::tokio::select! {
+                result = ::tokio::signal::ctrl_c() => result?,
+                _ = terminate.recv() => {},
+            }
+        }
+
+        #[cfg(not(unix))]
+        ::tokio::signal::ctrl_c().await?;
+
+        Ok(())
+    }
 }
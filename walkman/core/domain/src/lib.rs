@@ -12,6 +12,8 @@ pub struct Video {
     pub metadata: VideoMetadata,
 
     pub path: VideoFilePath,
+
+    pub updated_at: Option<::std::time::SystemTime>,
 }
 
 #[derive(Debug, Clone)]
@@ -99,6 +101,8 @@ pub struct Playlist {
     pub metadata: PlaylistMetadata,
 
     pub videos: Option<MaybeOwnedVec<Video>>,
+
+    pub updated_at: Option<::std::time::SystemTime>,
 }
 
 #[derive(Debug, Clone)]
@@ -161,6 +165,8 @@ pub struct Channel {
 
     pub videos: Option<MaybeOwnedVec<Video>>,
     pub playlists: Option<MaybeOwnedVec<Playlist>>,
+
+    pub updated_at: Option<::std::time::SystemTime>,
 }
 
 #[derive(Debug, Clone)]
@@ -213,3 +219,26 @@ impl From<MaybeOwnedString> for ChannelUrl {
 pub struct ChannelMetadata {
     pub title: Option<MaybeOwnedString>,
 }
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct FeedUrl(MaybeOwnedString);
+
+impl ::std::ops::Deref for FeedUrl {
+    type Target = MaybeOwnedString;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<FeedUrl> for MaybeOwnedString {
+    fn from(outer: FeedUrl) -> Self {
+        outer.0
+    }
+}
+
+impl From<MaybeOwnedString> for FeedUrl {
+    fn from(inner: MaybeOwnedString) -> Self {
+        Self(inner)
+    }
+}
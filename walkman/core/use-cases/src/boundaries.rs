@@ -2,9 +2,16 @@ use ::async_trait::async_trait;
 
 use crate::models::events::ChannelDownloadEvent;
 use crate::models::events::DiagnosticEvent;
+use crate::models::events::DownloadPlanEvent;
 use crate::models::events::PlaylistDownloadEvent;
+use crate::models::events::RunSummaryEvent;
+use crate::models::events::SubscriptionExportEvent;
+use crate::models::events::SubscriptionImportEvent;
+use crate::models::events::SubscriptionListingEvent;
+use crate::models::events::SubscriptionRemovalEvent;
 use crate::models::events::VideoDownloadEvent;
 use crate::utils::aliases::Fallible;
+use crate::utils::aliases::MaybeOwnedPath;
 use crate::utils::aliases::MaybeOwnedString;
 
 pub trait DownloadVideoInputBoundary:
@@ -58,6 +65,23 @@ pub struct DownloadChannelRequestModel {
     pub url: MaybeOwnedString,
 }
 
+pub trait DownloadFeedInputBoundary:
+    Accept<DownloadFeedRequestModel> + ::core::marker::Send + ::core::marker::Sync
+{
+}
+
+impl<InputBoundary> DownloadFeedInputBoundary for InputBoundary where
+    InputBoundary: Accept<DownloadFeedRequestModel> + ::core::marker::Send + ::core::marker::Sync
+{
+}
+
+#[derive(Debug, Clone)]
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct DownloadFeedRequestModel {
+    pub url: MaybeOwnedString,
+}
+
 pub trait UpdateMediaInputBoundary:
     Accept<UpdateMediaRequestModel> + ::core::marker::Send + ::core::marker::Sync
 {
@@ -71,14 +95,106 @@ impl<InputBoundary> UpdateMediaInputBoundary for InputBoundary where
 #[derive(Debug, Clone)]
 pub struct UpdateMediaRequestModel;
 
+pub trait RemoveSubscriptionInputBoundary:
+    Accept<RemoveSubscriptionRequestModel> + ::core::marker::Send + ::core::marker::Sync
+{
+}
+
+impl<InputBoundary> RemoveSubscriptionInputBoundary for InputBoundary where
+    InputBoundary: Accept<RemoveSubscriptionRequestModel> + ::core::marker::Send + ::core::marker::Sync
+{
+}
+
+#[derive(Debug, Clone)]
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct RemoveSubscriptionRequestModel {
+    pub url: MaybeOwnedString,
+}
+
+pub trait ListSubscriptionsInputBoundary:
+    Accept<ListSubscriptionsRequestModel> + ::core::marker::Send + ::core::marker::Sync
+{
+}
+
+impl<InputBoundary> ListSubscriptionsInputBoundary for InputBoundary where
+    InputBoundary: Accept<ListSubscriptionsRequestModel> + ::core::marker::Send + ::core::marker::Sync
+{
+}
+
+#[derive(Debug, Clone)]
+pub struct ListSubscriptionsRequestModel;
+
+pub trait ExportSubscriptionsInputBoundary:
+    Accept<ExportSubscriptionsRequestModel> + ::core::marker::Send + ::core::marker::Sync
+{
+}
+
+impl<InputBoundary> ExportSubscriptionsInputBoundary for InputBoundary where
+    InputBoundary: Accept<ExportSubscriptionsRequestModel> + ::core::marker::Send + ::core::marker::Sync
+{
+}
+
+#[derive(Debug, Clone)]
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct ExportSubscriptionsRequestModel {
+    pub path: MaybeOwnedPath,
+}
+
+pub trait ImportSubscriptionsInputBoundary:
+    Accept<ImportSubscriptionsRequestModel> + ::core::marker::Send + ::core::marker::Sync
+{
+}
+
+impl<InputBoundary> ImportSubscriptionsInputBoundary for InputBoundary where
+    InputBoundary: Accept<ImportSubscriptionsRequestModel> + ::core::marker::Send + ::core::marker::Sync
+{
+}
+
+#[derive(Debug, Clone)]
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct ImportSubscriptionsRequestModel {
+    pub path: MaybeOwnedPath,
+}
+
+pub trait ImportMediaInputBoundary:
+    Accept<ImportMediaRequestModel> + ::core::marker::Send + ::core::marker::Sync
+{
+}
+
+impl<InputBoundary> ImportMediaInputBoundary for InputBoundary where
+    InputBoundary: Accept<ImportMediaRequestModel> + ::core::marker::Send + ::core::marker::Sync
+{
+}
+
+#[derive(Debug, Clone)]
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct ImportMediaRequestModel {
+    pub path: MaybeOwnedPath,
+}
+
 pub trait DownloadVideoOutputBoundary:
-    Activate + Update<VideoDownloadEvent> + Update<DiagnosticEvent> + ::core::marker::Send + ::core::marker::Sync
+    Activate
+    + Update<VideoDownloadEvent>
+    + Update<DownloadPlanEvent>
+    + Update<RunSummaryEvent>
+    + Update<DiagnosticEvent>
+    + ::core::marker::Send
+    + ::core::marker::Sync
 {
 }
 
 impl<OutputBoundary> DownloadVideoOutputBoundary for OutputBoundary where
-    OutputBoundary:
-        Activate + Update<VideoDownloadEvent> + Update<DiagnosticEvent> + ::core::marker::Send + ::core::marker::Sync
+    OutputBoundary: Activate
+        + Update<VideoDownloadEvent>
+        + Update<DownloadPlanEvent>
+        + Update<RunSummaryEvent>
+        + Update<DiagnosticEvent>
+        + ::core::marker::Send
+        + ::core::marker::Sync
 {
 }
 
@@ -86,6 +202,8 @@ pub trait DownloadPlaylistOutputBoundary:
     Activate
     + Update<VideoDownloadEvent>
     + Update<PlaylistDownloadEvent>
+    + Update<DownloadPlanEvent>
+    + Update<RunSummaryEvent>
     + Update<DiagnosticEvent>
     + ::core::marker::Send
     + ::core::marker::Sync
@@ -96,6 +214,8 @@ impl<OutputBoundary> DownloadPlaylistOutputBoundary for OutputBoundary where
     OutputBoundary: Activate
         + Update<VideoDownloadEvent>
         + Update<PlaylistDownloadEvent>
+        + Update<DownloadPlanEvent>
+        + Update<RunSummaryEvent>
         + Update<DiagnosticEvent>
         + ::core::marker::Send
         + ::core::marker::Sync
@@ -107,6 +227,8 @@ pub trait DownloadChannelOutputBoundary:
     + Update<VideoDownloadEvent>
     + Update<PlaylistDownloadEvent>
     + Update<ChannelDownloadEvent>
+    + Update<DownloadPlanEvent>
+    + Update<RunSummaryEvent>
     + Update<DiagnosticEvent>
     + ::core::marker::Send
     + ::core::marker::Sync
@@ -118,6 +240,32 @@ impl<OutputBoundary> DownloadChannelOutputBoundary for OutputBoundary where
         + Update<VideoDownloadEvent>
         + Update<PlaylistDownloadEvent>
         + Update<ChannelDownloadEvent>
+        + Update<DownloadPlanEvent>
+        + Update<RunSummaryEvent>
+        + Update<DiagnosticEvent>
+        + ::core::marker::Send
+        + ::core::marker::Sync
+{
+}
+
+pub trait DownloadFeedOutputBoundary:
+    Activate
+    + Update<VideoDownloadEvent>
+    + Update<PlaylistDownloadEvent>
+    + Update<DownloadPlanEvent>
+    + Update<RunSummaryEvent>
+    + Update<DiagnosticEvent>
+    + ::core::marker::Send
+    + ::core::marker::Sync
+{
+}
+
+impl<OutputBoundary> DownloadFeedOutputBoundary for OutputBoundary where
+    OutputBoundary: Activate
+        + Update<VideoDownloadEvent>
+        + Update<PlaylistDownloadEvent>
+        + Update<DownloadPlanEvent>
+        + Update<RunSummaryEvent>
         + Update<DiagnosticEvent>
         + ::core::marker::Send
         + ::core::marker::Sync
@@ -129,6 +277,8 @@ pub trait UpdateMediaOutputBoundary:
     + Update<VideoDownloadEvent>
     + Update<PlaylistDownloadEvent>
     + Update<ChannelDownloadEvent>
+    + Update<DownloadPlanEvent>
+    + Update<RunSummaryEvent>
     + Update<DiagnosticEvent>
     + ::core::marker::Send
     + ::core::marker::Sync
@@ -140,6 +290,86 @@ impl<OutputBoundary> UpdateMediaOutputBoundary for OutputBoundary where
         + Update<VideoDownloadEvent>
         + Update<PlaylistDownloadEvent>
         + Update<ChannelDownloadEvent>
+        + Update<DownloadPlanEvent>
+        + Update<RunSummaryEvent>
+        + Update<DiagnosticEvent>
+        + ::core::marker::Send
+        + ::core::marker::Sync
+{
+}
+
+pub trait RemoveSubscriptionOutputBoundary:
+    Activate + Update<SubscriptionRemovalEvent> + Update<DiagnosticEvent> + ::core::marker::Send + ::core::marker::Sync
+{
+}
+
+impl<OutputBoundary> RemoveSubscriptionOutputBoundary for OutputBoundary where
+    OutputBoundary: Activate
+        + Update<SubscriptionRemovalEvent>
+        + Update<DiagnosticEvent>
+        + ::core::marker::Send
+        + ::core::marker::Sync
+{
+}
+
+pub trait ListSubscriptionsOutputBoundary:
+    Activate + Update<SubscriptionListingEvent> + Update<DiagnosticEvent> + ::core::marker::Send + ::core::marker::Sync
+{
+}
+
+impl<OutputBoundary> ListSubscriptionsOutputBoundary for OutputBoundary where
+    OutputBoundary: Activate
+        + Update<SubscriptionListingEvent>
+        + Update<DiagnosticEvent>
+        + ::core::marker::Send
+        + ::core::marker::Sync
+{
+}
+
+pub trait ExportSubscriptionsOutputBoundary:
+    Activate + Update<SubscriptionExportEvent> + Update<DiagnosticEvent> + ::core::marker::Send + ::core::marker::Sync
+{
+}
+
+impl<OutputBoundary> ExportSubscriptionsOutputBoundary for OutputBoundary where
+    OutputBoundary: Activate
+        + Update<SubscriptionExportEvent>
+        + Update<DiagnosticEvent>
+        + ::core::marker::Send
+        + ::core::marker::Sync
+{
+}
+
+pub trait ImportSubscriptionsOutputBoundary:
+    Activate + Update<SubscriptionImportEvent> + Update<DiagnosticEvent> + ::core::marker::Send + ::core::marker::Sync
+{
+}
+
+impl<OutputBoundary> ImportSubscriptionsOutputBoundary for OutputBoundary where
+    OutputBoundary: Activate
+        + Update<SubscriptionImportEvent>
+        + Update<DiagnosticEvent>
+        + ::core::marker::Send
+        + ::core::marker::Sync
+{
+}
+
+pub trait ImportMediaOutputBoundary:
+    Activate
+    + Update<VideoDownloadEvent>
+    + Update<PlaylistDownloadEvent>
+    + Update<RunSummaryEvent>
+    + Update<DiagnosticEvent>
+    + ::core::marker::Send
+    + ::core::marker::Sync
+{
+}
+
+impl<OutputBoundary> ImportMediaOutputBoundary for OutputBoundary where
+    OutputBoundary: Activate
+        + Update<VideoDownloadEvent>
+        + Update<PlaylistDownloadEvent>
+        + Update<RunSummaryEvent>
         + Update<DiagnosticEvent>
         + ::core::marker::Send
         + ::core::marker::Sync
@@ -1,14 +1,24 @@
 use ::async_trait::async_trait;
+use ::domain::Channel;
 use ::domain::ChannelUrl;
+use ::domain::FeedUrl;
+use ::domain::Playlist;
 use ::domain::PlaylistUrl;
+use ::domain::Video;
 use ::domain::VideoUrl;
 
+use crate::models::descriptors::PlannedChannel;
+use crate::models::descriptors::PlannedPlaylist;
+use crate::models::descriptors::PlannedVideo;
+use crate::models::descriptors::ResolvedVideo;
 use crate::models::events::ChannelDownloadEvent;
 use crate::models::events::DiagnosticEvent;
 use crate::models::events::PlaylistDownloadEvent;
 use crate::models::events::VideoDownloadEvent;
 use crate::utils::aliases::BoxedStream;
 use crate::utils::aliases::Fallible;
+use crate::utils::aliases::MaybeOwnedPath;
+use crate::utils::aliases::MaybeOwnedVec;
 
 #[async_trait]
 pub trait VideoDownloader: ::core::marker::Send + ::core::marker::Sync {
@@ -20,14 +30,14 @@ pub trait VideoDownloader: ::core::marker::Send + ::core::marker::Sync {
 #[async_trait]
 pub trait PlaylistDownloader: ::core::marker::Send + ::core::marker::Sync {
     async fn download(
-        self: ::std::sync::Arc<Self>, url: PlaylistUrl,
+        self: ::std::sync::Arc<Self>, url: PlaylistUrl, downloaded_videos: MaybeOwnedVec<ResolvedVideo>,
     ) -> Fallible<(BoxedStream<VideoDownloadEvent>, BoxedStream<PlaylistDownloadEvent>, BoxedStream<DiagnosticEvent>)>;
 }
 
 #[async_trait]
 pub trait ChannelDownloader: ::core::marker::Send + ::core::marker::Sync {
     async fn download(
-        self: ::std::sync::Arc<Self>, url: ChannelUrl,
+        self: ::std::sync::Arc<Self>, url: ChannelUrl, downloaded_videos: MaybeOwnedVec<ResolvedVideo>,
     ) -> Fallible<(
         BoxedStream<VideoDownloadEvent>,
         BoxedStream<PlaylistDownloadEvent>,
@@ -37,20 +47,109 @@ pub trait ChannelDownloader: ::core::marker::Send + ::core::marker::Sync {
 }
 
 #[async_trait]
+pub trait FeedDownloader: ::core::marker::Send + ::core::marker::Sync {
+    async fn download(
+        self: ::std::sync::Arc<Self>, url: FeedUrl, downloaded_videos: MaybeOwnedVec<ResolvedVideo>,
+    ) -> Fallible<(BoxedStream<VideoDownloadEvent>, BoxedStream<PlaylistDownloadEvent>, BoxedStream<DiagnosticEvent>)>;
+}
+
+#[async_trait]
+pub trait VideoResolver: ::core::marker::Send + ::core::marker::Sync {
+    async fn resolve(
+        self: ::std::sync::Arc<Self>, url: VideoUrl,
+    ) -> Fallible<(PlannedVideo, BoxedStream<DiagnosticEvent>)>;
+}
+
+#[async_trait]
+pub trait PlaylistResolver: ::core::marker::Send + ::core::marker::Sync {
+    async fn resolve(
+        self: ::std::sync::Arc<Self>, url: PlaylistUrl,
+    ) -> Fallible<(PlannedPlaylist, BoxedStream<DiagnosticEvent>)>;
+}
+
+#[async_trait]
+pub trait ChannelResolver: ::core::marker::Send + ::core::marker::Sync {
+    async fn resolve(
+        self: ::std::sync::Arc<Self>, url: ChannelUrl,
+    ) -> Fallible<(PlannedChannel, BoxedStream<DiagnosticEvent>)>;
+}
+
+#[async_trait]
+pub trait FeedResolver: ::core::marker::Send + ::core::marker::Sync {
+    async fn resolve(
+        self: ::std::sync::Arc<Self>, url: FeedUrl,
+    ) -> Fallible<(PlannedPlaylist, BoxedStream<DiagnosticEvent>)>;
+}
+
+#[async_trait]
+pub trait MediaImporter: ::core::marker::Send + ::core::marker::Sync {
+    async fn import(
+        self: ::std::sync::Arc<Self>, path: MaybeOwnedPath, imported_videos: MaybeOwnedVec<ResolvedVideo>,
+    ) -> Fallible<(BoxedStream<VideoDownloadEvent>, BoxedStream<PlaylistDownloadEvent>, BoxedStream<DiagnosticEvent>)>;
+}
+
+#[async_trait]
+// The artifact is handed over mutably, so that whatever a post-processor
+// changes about it is what the library records
 pub trait PostProcessor<Artifact>: ::core::marker::Send + ::core::marker::Sync {
-    async fn process(self: ::std::sync::Arc<Self>, artifact: &Artifact) -> Fallible<()>;
+    async fn process(self: ::std::sync::Arc<Self>, artifact: &mut Artifact) -> Fallible<()>;
+}
+
+#[async_trait]
+pub trait Discarder<Artifact>: ::core::marker::Send + ::core::marker::Sync {
+    async fn discard(self: ::std::sync::Arc<Self>, artifact: &Artifact) -> Fallible<()>;
+}
+
+#[async_trait]
+pub trait Exporter<Artifact>: ::core::marker::Send + ::core::marker::Sync {
+    async fn export(self: ::std::sync::Arc<Self>, artifact: &Artifact, path: &::std::path::Path) -> Fallible<()>;
+}
+
+#[async_trait]
+pub trait Importer<Artifact>: ::core::marker::Send + ::core::marker::Sync {
+    async fn import(self: ::std::sync::Arc<Self>, path: &::std::path::Path) -> Fallible<Artifact>;
 }
 
 #[async_trait]
 pub trait UrlRepository:
-    Insert<VideoUrl> + Insert<PlaylistUrl> + Insert<ChannelUrl> + ::core::marker::Send + ::core::marker::Sync
+    Insert<VideoUrl>
+    + Insert<PlaylistUrl>
+    + Insert<ChannelUrl>
+    + Insert<FeedUrl>
+    + Remove<VideoUrl>
+    + Remove<PlaylistUrl>
+    + Remove<ChannelUrl>
+    + Remove<FeedUrl>
+    + ::core::marker::Send
+    + ::core::marker::Sync
 {
     async fn values(
         self: ::std::sync::Arc<Self>,
-    ) -> Fallible<(BoxedStream<VideoUrl>, BoxedStream<PlaylistUrl>, BoxedStream<ChannelUrl>)>;
+    ) -> Fallible<(BoxedStream<VideoUrl>, BoxedStream<PlaylistUrl>, BoxedStream<ChannelUrl>, BoxedStream<FeedUrl>)>;
+}
+
+#[async_trait]
+pub trait LibraryRepository:
+    Insert<Video>
+    + Insert<Playlist>
+    + Insert<Channel>
+    + Remove<Video>
+    + Remove<Playlist>
+    + Remove<Channel>
+    + ::core::marker::Send
+    + ::core::marker::Sync
+{
+    async fn values(
+        self: ::std::sync::Arc<Self>,
+    ) -> Fallible<(BoxedStream<Video>, BoxedStream<Playlist>, BoxedStream<Channel>)>;
 }
 
 #[async_trait]
 pub trait Insert<Item>: ::core::marker::Send + ::core::marker::Sync {
     async fn insert(self: ::std::sync::Arc<Self>, item: Item) -> Fallible<()>;
 }
+
+#[async_trait]
+pub trait Remove<Item>: ::core::marker::Send + ::core::marker::Sync {
+    async fn remove(self: ::std::sync::Arc<Self>, item: Item) -> Fallible<bool>;
+}
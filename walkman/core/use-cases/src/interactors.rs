@@ -1,32 +1,86 @@
 use ::async_trait::async_trait;
+use ::domain::Channel;
 use ::domain::ChannelUrl;
+use ::domain::FeedUrl;
+use ::domain::Playlist;
 use ::domain::PlaylistUrl;
+use ::domain::Video;
 use ::domain::VideoUrl;
 use ::futures::prelude::*;
+use ::std::ops::Not;
 
 use crate::boundaries::Accept;
 use crate::boundaries::DownloadChannelOutputBoundary;
 use crate::boundaries::DownloadChannelRequestModel;
+use crate::boundaries::DownloadFeedOutputBoundary;
+use crate::boundaries::DownloadFeedRequestModel;
 use crate::boundaries::DownloadPlaylistOutputBoundary;
 use crate::boundaries::DownloadPlaylistRequestModel;
 use crate::boundaries::DownloadVideoOutputBoundary;
 use crate::boundaries::DownloadVideoRequestModel;
+use crate::boundaries::ExportSubscriptionsOutputBoundary;
+use crate::boundaries::ExportSubscriptionsRequestModel;
+use crate::boundaries::ImportMediaOutputBoundary;
+use crate::boundaries::ImportMediaRequestModel;
+use crate::boundaries::ImportSubscriptionsOutputBoundary;
+use crate::boundaries::ImportSubscriptionsRequestModel;
+use crate::boundaries::ListSubscriptionsOutputBoundary;
+use crate::boundaries::ListSubscriptionsRequestModel;
+use crate::boundaries::RemoveSubscriptionOutputBoundary;
+use crate::boundaries::RemoveSubscriptionRequestModel;
 use crate::boundaries::UpdateMediaOutputBoundary;
 use crate::boundaries::UpdateMediaRequestModel;
 use crate::gateways::ChannelDownloader;
+use crate::gateways::ChannelResolver;
+use crate::gateways::Discarder;
+use crate::gateways::Exporter;
+use crate::gateways::FeedDownloader;
+use crate::gateways::FeedResolver;
+use crate::gateways::Importer;
+use crate::gateways::LibraryRepository;
+use crate::gateways::MediaImporter;
 use crate::gateways::PlaylistDownloader;
+use crate::gateways::PlaylistResolver;
 use crate::gateways::PostProcessor;
 use crate::gateways::UrlRepository;
 use crate::gateways::VideoDownloader;
+use crate::gateways::VideoResolver;
+use crate::models::descriptors::PartiallyResolvedPlaylist;
+use crate::models::descriptors::PlannedVideo;
 use crate::models::descriptors::ResolvedChannel;
 use crate::models::descriptors::ResolvedPlaylist;
 use crate::models::descriptors::ResolvedVideo;
+use crate::models::descriptors::RunSummary;
+use crate::models::descriptors::RunSummaryItem;
+use crate::models::descriptors::Subscription;
+use crate::models::descriptors::SubscriptionKind;
+use crate::models::descriptors::SubscriptionUrls;
+use crate::models::descriptors::UnresolvedVideo;
 use crate::models::events::ChannelDownloadEvent;
 use crate::models::events::DiagnosticEvent;
+use crate::models::events::DiagnosticLevel;
+use crate::models::events::DownloadPlanChannelPlannedEvent;
+use crate::models::events::DownloadPlanEvent;
+use crate::models::events::DownloadPlanPlaylistPlannedEvent;
+use crate::models::events::DownloadPlanVideoPlannedEvent;
 use crate::models::events::PlaylistDownloadEvent;
+use crate::models::events::PlaylistDownloadVideoRemovedEvent;
+use crate::models::events::RunSummaryCompletedEvent;
+use crate::models::events::RunSummaryEvent;
+use crate::models::events::SubscriptionExportCompletedEvent;
+use crate::models::events::SubscriptionExportEvent;
+use crate::models::events::SubscriptionImportCompletedEvent;
+use crate::models::events::SubscriptionImportEvent;
+use crate::models::events::SubscriptionListingCompletedEvent;
+use crate::models::events::SubscriptionListingEvent;
+use crate::models::events::SubscriptionListingSubscriptionListedEvent;
+use crate::models::events::SubscriptionRemovalCompletedEvent;
+use crate::models::events::SubscriptionRemovalEvent;
+use crate::models::events::SubscriptionRemovalVideoRemovedEvent;
 use crate::models::events::VideoDownloadEvent;
 use crate::utils::aliases::BoxedStream;
 use crate::utils::aliases::Fallible;
+use crate::utils::aliases::MaybeOwnedString;
 use crate::utils::aliases::MaybeOwnedVec;
 
 #[derive(::bon::Builder)]
@@ -35,8 +89,16 @@ pub struct DownloadVideoInteractor {
     view: ::std::sync::Arc<dyn DownloadVideoOutputBoundary>,
 
     urls: ::std::sync::Arc<dyn UrlRepository>,
+    library: ::std::sync::Arc<dyn LibraryRepository>,
     downloader: ::std::sync::Arc<dyn VideoDownloader>,
+    resolver: ::std::sync::Arc<dyn VideoResolver>,
     postprocessors: MaybeOwnedVec<::std::sync::Arc<dyn PostProcessor<ResolvedVideo>>>,
+
+    #[builder(default)]
+    dry_run: bool,
+
+    #[builder(skip)]
+    recorder: RunSummaryRecorder,
 }
 
 #[async_trait]
@@ -44,6 +106,12 @@ impl Accept<DownloadVideoRequestModel> for DownloadVideoInteractor {
     async fn accept(self: ::std::sync::Arc<Self>, request: DownloadVideoRequestModel) -> Fallible<()> {
         let url: VideoUrl = request.url.into();
 
+        if self.dry_run {
+            return ::std::sync::Arc::clone(&self).accept((url, DryRun)).await;
+        }
+
+        let started_at = ::std::time::Instant::now();
+
         let (_, (video_download_events, diagnostic_events)) = ::tokio::try_join!(
             ::std::sync::Arc::clone(&self.urls).insert(url.clone()),
             ::std::sync::Arc::clone(&self.downloader).download(url.clone()),
@@ -56,6 +124,33 @@ impl Accept<DownloadVideoRequestModel> for DownloadVideoInteractor {
             ::std::sync::Arc::clone(&self).accept(diagnostic_events),
         )?;
 
+        let event = RunSummaryCompletedEvent::builder()
+            .summary(self.recorder.summarize(started_at.elapsed()))
+            .build();
+        ::std::sync::Arc::clone(&self.view)
+            .update(&RunSummaryEvent::Completed(event))
+            .await?;
+
+        ::std::sync::Arc::clone(&self.view).deactivate().await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Accept<(VideoUrl, DryRun)> for DownloadVideoInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, (url, _): (VideoUrl, DryRun)) -> Fallible<()> {
+        let (video, diagnostic_events) = ::std::sync::Arc::clone(&self.resolver).resolve(url).await?;
+
+        ::std::sync::Arc::clone(&self.view).activate().await?;
+
+        ::std::sync::Arc::clone(&self).accept(diagnostic_events).await?;
+
+        let event = DownloadPlanVideoPlannedEvent::builder().video(video).build();
+        ::std::sync::Arc::clone(&self.view)
+            .update(&DownloadPlanEvent::VideoPlanned(event))
+            .await?;
+
         ::std::sync::Arc::clone(&self.view).deactivate().await?;
 
         Ok(())
@@ -71,11 +166,14 @@ impl Accept<BoxedStream<VideoDownloadEvent>> for DownloadVideoInteractor {
             ::tracing::debug!("Received (IB) event `{:?}`", event);
 
             ::std::sync::Arc::clone(&self.view).update(&event).await?;
+            self.recorder.record_video_event(&event);
 
-            if let VideoDownloadEvent::Completed(event) = event {
+            if let VideoDownloadEvent::Completed(mut event) = event {
                 for postprocessor in &*self.postprocessors {
-                    ::std::sync::Arc::clone(postprocessor).process(&event.video).await?;
+                    ::std::sync::Arc::clone(postprocessor).process(&mut event.video).await?;
                 }
+
+                ::std::sync::Arc::clone(&self.library).insert(Video::from(event.video)).await?;
             }
         }
 
@@ -104,8 +202,16 @@ pub struct DownloadPlaylistInteractor {
     view: ::std::sync::Arc<dyn DownloadPlaylistOutputBoundary>,
 
     urls: ::std::sync::Arc<dyn UrlRepository>,
+    library: ::std::sync::Arc<dyn LibraryRepository>,
     downloader: ::std::sync::Arc<dyn PlaylistDownloader>,
+    resolver: ::std::sync::Arc<dyn PlaylistResolver>,
     postprocessors: MaybeOwnedVec<::std::sync::Arc<dyn PostProcessor<ResolvedPlaylist>>>,
+
+    #[builder(default)]
+    dry_run: bool,
+
+    #[builder(skip)]
+    recorder: RunSummaryRecorder,
 }
 
 #[async_trait]
@@ -113,9 +219,15 @@ impl Accept<DownloadPlaylistRequestModel> for DownloadPlaylistInteractor {
     async fn accept(self: ::std::sync::Arc<Self>, request: DownloadPlaylistRequestModel) -> Fallible<()> {
         let url: PlaylistUrl = request.url.into();
 
+        if self.dry_run {
+            return ::std::sync::Arc::clone(&self).accept((url, DryRun)).await;
+        }
+
+        let started_at = ::std::time::Instant::now();
+
         let (_, (video_download_events, playlist_download_events, diagnostic_events)) = ::tokio::try_join!(
             ::std::sync::Arc::clone(&self.urls).insert(url.clone()),
-            ::std::sync::Arc::clone(&self.downloader).download(url.clone()),
+            ::std::sync::Arc::clone(&self.downloader).download(url.clone(), Default::default()),
         )?;
 
         ::std::sync::Arc::clone(&self.view).activate().await?;
@@ -126,6 +238,33 @@ impl Accept<DownloadPlaylistRequestModel> for DownloadPlaylistInteractor {
             ::std::sync::Arc::clone(&self).accept(diagnostic_events),
         )?;
 
+        let event = RunSummaryCompletedEvent::builder()
+            .summary(self.recorder.summarize(started_at.elapsed()))
+            .build();
+        ::std::sync::Arc::clone(&self.view)
+            .update(&RunSummaryEvent::Completed(event))
+            .await?;
+
+        ::std::sync::Arc::clone(&self.view).deactivate().await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Accept<(PlaylistUrl, DryRun)> for DownloadPlaylistInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, (url, _): (PlaylistUrl, DryRun)) -> Fallible<()> {
+        let (playlist, diagnostic_events) = ::std::sync::Arc::clone(&self.resolver).resolve(url).await?;
+
+        ::std::sync::Arc::clone(&self.view).activate().await?;
+
+        ::std::sync::Arc::clone(&self).accept(diagnostic_events).await?;
+
+        let event = DownloadPlanPlaylistPlannedEvent::builder().playlist(playlist).build();
+        ::std::sync::Arc::clone(&self.view)
+            .update(&DownloadPlanEvent::PlaylistPlanned(event))
+            .await?;
+
         ::std::sync::Arc::clone(&self.view).deactivate().await?;
 
         Ok(())
@@ -141,6 +280,11 @@ impl Accept<BoxedStream<VideoDownloadEvent>> for DownloadPlaylistInteractor {
             ::tracing::debug!("Received (IB) event `{:?}`", event);
 
             ::std::sync::Arc::clone(&self.view).update(&event).await?;
+            self.recorder.record_video_event(&event);
+
+            if let VideoDownloadEvent::Completed(event) = event {
+                ::std::sync::Arc::clone(&self.library).insert(Video::from(event.video)).await?;
+            }
         }
 
         Ok(())
@@ -156,11 +300,21 @@ impl Accept<BoxedStream<PlaylistDownloadEvent>> for DownloadPlaylistInteractor {
             ::tracing::debug!("Received (IB) event `{:?}`", event);
 
             ::std::sync::Arc::clone(&self.view).update(&event).await?;
+            self.recorder.record_playlist_event(&event);
+
+            if let PlaylistDownloadEvent::Completed(mut event) = event {
+                let videos = event.playlist.videos.clone();
 
-            if let PlaylistDownloadEvent::Completed(event) = event {
                 for postprocessor in &*self.postprocessors {
-                    ::std::sync::Arc::clone(postprocessor).process(&event.playlist).await?;
+                    ::std::sync::Arc::clone(postprocessor).process(&mut event.playlist).await?;
                 }
+
+                ProcessedVideoRecorder::record(&self.library, videos.as_deref(), event.playlist.videos.as_deref())
+                    .await?;
+
+                ::std::sync::Arc::clone(&self.library)
+                    .insert(Playlist::from(event.playlist))
+                    .await?;
             }
         }
 
@@ -189,8 +343,16 @@ pub struct DownloadChannelInteractor {
     view: ::std::sync::Arc<dyn DownloadChannelOutputBoundary>,
 
     urls: ::std::sync::Arc<dyn UrlRepository>,
+    library: ::std::sync::Arc<dyn LibraryRepository>,
     downloader: ::std::sync::Arc<dyn ChannelDownloader>,
+    resolver: ::std::sync::Arc<dyn ChannelResolver>,
     postprocessors: MaybeOwnedVec<::std::sync::Arc<dyn PostProcessor<ResolvedChannel>>>,
+
+    #[builder(default)]
+    dry_run: bool,
+
+    #[builder(skip)]
+    recorder: RunSummaryRecorder,
 }
 
 #[async_trait]
@@ -198,9 +360,15 @@ impl Accept<DownloadChannelRequestModel> for DownloadChannelInteractor {
     async fn accept(self: ::std::sync::Arc<Self>, request: DownloadChannelRequestModel) -> Fallible<()> {
         let url: ChannelUrl = request.url.into();
 
+        if self.dry_run {
+            return ::std::sync::Arc::clone(&self).accept((url, DryRun)).await;
+        }
+
+        let started_at = ::std::time::Instant::now();
+
         let (_, (video_download_events, playlist_download_events, channel_download_events, diagnostic_events)) = ::tokio::try_join!(
             ::std::sync::Arc::clone(&self.urls).insert(url.clone()),
-            ::std::sync::Arc::clone(&self.downloader).download(url.clone()),
+            ::std::sync::Arc::clone(&self.downloader).download(url.clone(), Default::default()),
         )?;
 
         ::std::sync::Arc::clone(&self.view).activate().await?;
@@ -212,6 +380,33 @@ impl Accept<DownloadChannelRequestModel> for DownloadChannelInteractor {
             ::std::sync::Arc::clone(&self).accept(diagnostic_events),
         )?;
 
+        let event = RunSummaryCompletedEvent::builder()
+            .summary(self.recorder.summarize(started_at.elapsed()))
+            .build();
+        ::std::sync::Arc::clone(&self.view)
+            .update(&RunSummaryEvent::Completed(event))
+            .await?;
+
+        ::std::sync::Arc::clone(&self.view).deactivate().await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Accept<(ChannelUrl, DryRun)> for DownloadChannelInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, (url, _): (ChannelUrl, DryRun)) -> Fallible<()> {
+        let (channel, diagnostic_events) = ::std::sync::Arc::clone(&self.resolver).resolve(url).await?;
+
+        ::std::sync::Arc::clone(&self.view).activate().await?;
+
+        ::std::sync::Arc::clone(&self).accept(diagnostic_events).await?;
+
+        let event = DownloadPlanChannelPlannedEvent::builder().channel(channel).build();
+        ::std::sync::Arc::clone(&self.view)
+            .update(&DownloadPlanEvent::ChannelPlanned(event))
+            .await?;
+
         ::std::sync::Arc::clone(&self.view).deactivate().await?;
 
         Ok(())
@@ -227,6 +422,11 @@ impl Accept<BoxedStream<VideoDownloadEvent>> for DownloadChannelInteractor {
             ::tracing::debug!("Received (IB) event `{:?}`", event);
 
             ::std::sync::Arc::clone(&self.view).update(&event).await?;
+            self.recorder.record_video_event(&event);
+
+            if let VideoDownloadEvent::Completed(event) = event {
+                ::std::sync::Arc::clone(&self.library).insert(Video::from(event.video)).await?;
+            }
         }
 
         Ok(())
@@ -242,6 +442,13 @@ impl Accept<BoxedStream<PlaylistDownloadEvent>> for DownloadChannelInteractor {
             ::tracing::debug!("Received (IB) event `{:?}`", event);
 
             ::std::sync::Arc::clone(&self.view).update(&event).await?;
+            self.recorder.record_playlist_event(&event);
+
+            if let PlaylistDownloadEvent::Completed(event) = event {
+                ::std::sync::Arc::clone(&self.library)
+                    .insert(Playlist::from(event.playlist))
+                    .await?;
+            }
         }
 
         Ok(())
@@ -257,11 +464,40 @@ impl Accept<BoxedStream<ChannelDownloadEvent>> for DownloadChannelInteractor {
             ::tracing::debug!("Received (IB) event `{:?}`", event);
 
             ::std::sync::Arc::clone(&self.view).update(&event).await?;
+            self.recorder.record_channel_event(&event);
+
+            if let ChannelDownloadEvent::Completed(mut event) = event {
+                let (videos, playlists) = (event.channel.videos.clone(), event.channel.playlists.clone());
 
-            if let ChannelDownloadEvent::Completed(event) = event {
                 for postprocessor in &*self.postprocessors {
-                    ::std::sync::Arc::clone(postprocessor).process(&event.channel).await?;
+                    ::std::sync::Arc::clone(postprocessor).process(&mut event.channel).await?;
                 }
+
+                ProcessedVideoRecorder::record(&self.library, videos.as_deref(), event.channel.videos.as_deref())
+                    .await?;
+
+                let processed_playlists = event.channel.playlists.as_deref().unwrap_or_default();
+
+                for (playlist, processed_playlist) in
+                    playlists.as_deref().unwrap_or_default().iter().zip(processed_playlists)
+                {
+                    let changed = ProcessedVideoRecorder::record(
+                        &self.library,
+                        playlist.videos.as_deref(),
+                        processed_playlist.videos.as_deref(),
+                    )
+                    .await?;
+
+                    if changed {
+                        ::std::sync::Arc::clone(&self.library)
+                            .insert(Playlist::from(processed_playlist.clone()))
+                            .await?;
+                    }
+                }
+
+                ::std::sync::Arc::clone(&self.library)
+                    .insert(Channel::from(event.channel))
+                    .await?;
             }
         }
 
@@ -284,26 +520,189 @@ impl Accept<BoxedStream<DiagnosticEvent>> for DownloadChannelInteractor {
     }
 }
 
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct DownloadFeedInteractor {
+    view: ::std::sync::Arc<dyn DownloadFeedOutputBoundary>,
+
+    urls: ::std::sync::Arc<dyn UrlRepository>,
+    library: ::std::sync::Arc<dyn LibraryRepository>,
+    downloader: ::std::sync::Arc<dyn FeedDownloader>,
+    resolver: ::std::sync::Arc<dyn FeedResolver>,
+    postprocessors: MaybeOwnedVec<::std::sync::Arc<dyn PostProcessor<ResolvedPlaylist>>>,
+
+    #[builder(default)]
+    dry_run: bool,
+
+    #[builder(skip)]
+    recorder: RunSummaryRecorder,
+}
+
+#[async_trait]
+impl Accept<DownloadFeedRequestModel> for DownloadFeedInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, request: DownloadFeedRequestModel) -> Fallible<()> {
+        let url: FeedUrl = request.url.into();
+
+        if self.dry_run {
+            return ::std::sync::Arc::clone(&self).accept((url, DryRun)).await;
+        }
+
+        let started_at = ::std::time::Instant::now();
+
+        let (_, (video_download_events, playlist_download_events, diagnostic_events)) = ::tokio::try_join!(
+            ::std::sync::Arc::clone(&self.urls).insert(url.clone()),
+            ::std::sync::Arc::clone(&self.downloader).download(url.clone(), Default::default()),
+        )?;
+
+        ::std::sync::Arc::clone(&self.view).activate().await?;
+
+        ::tokio::try_join!(
+            ::std::sync::Arc::clone(&self).accept(video_download_events),
+            ::std::sync::Arc::clone(&self).accept(playlist_download_events),
+            ::std::sync::Arc::clone(&self).accept(diagnostic_events),
+        )?;
+
+        let event = RunSummaryCompletedEvent::builder()
+            .summary(self.recorder.summarize(started_at.elapsed()))
+            .build();
+        ::std::sync::Arc::clone(&self.view)
+            .update(&RunSummaryEvent::Completed(event))
+            .await?;
+
+        ::std::sync::Arc::clone(&self.view).deactivate().await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Accept<(FeedUrl, DryRun)> for DownloadFeedInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, (url, _): (FeedUrl, DryRun)) -> Fallible<()> {
+        let (playlist, diagnostic_events) = ::std::sync::Arc::clone(&self.resolver).resolve(url).await?;
+
+        ::std::sync::Arc::clone(&self.view).activate().await?;
+
+        ::std::sync::Arc::clone(&self).accept(diagnostic_events).await?;
+
+        let event = DownloadPlanPlaylistPlannedEvent::builder().playlist(playlist).build();
+        ::std::sync::Arc::clone(&self.view)
+            .update(&DownloadPlanEvent::PlaylistPlanned(event))
+            .await?;
+
+        ::std::sync::Arc::clone(&self.view).deactivate().await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Accept<BoxedStream<VideoDownloadEvent>> for DownloadFeedInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, events: BoxedStream<VideoDownloadEvent>) -> Fallible<()> {
+        ::futures::pin_mut!(events);
+
+        while let Some(event) = events.next().await {
+            ::tracing::debug!("Received (IB) event `{:?}`", event);
+
+            ::std::sync::Arc::clone(&self.view).update(&event).await?;
+            self.recorder.record_video_event(&event);
+
+            if let VideoDownloadEvent::Completed(event) = event {
+                ::std::sync::Arc::clone(&self.library).insert(Video::from(event.video)).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Accept<BoxedStream<PlaylistDownloadEvent>> for DownloadFeedInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, events: BoxedStream<PlaylistDownloadEvent>) -> Fallible<()> {
+        ::futures::pin_mut!(events);
+
+        while let Some(event) = events.next().await {
+            ::tracing::debug!("Received (IB) event `{:?}`", event);
+
+            ::std::sync::Arc::clone(&self.view).update(&event).await?;
+            self.recorder.record_playlist_event(&event);
+
+            if let PlaylistDownloadEvent::Completed(mut event) = event {
+                let videos = event.playlist.videos.clone();
+
+                for postprocessor in &*self.postprocessors {
+                    ::std::sync::Arc::clone(postprocessor).process(&mut event.playlist).await?;
+                }
+
+                ProcessedVideoRecorder::record(&self.library, videos.as_deref(), event.playlist.videos.as_deref())
+                    .await?;
+
+                ::std::sync::Arc::clone(&self.library)
+                    .insert(Playlist::from(event.playlist))
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Accept<BoxedStream<DiagnosticEvent>> for DownloadFeedInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, events: BoxedStream<DiagnosticEvent>) -> Fallible<()> {
+        ::futures::pin_mut!(events);
+
+        while let Some(event) = events.next().await {
+            ::tracing::debug!("Received (IB) event `{:?}`", event);
+
+            ::std::sync::Arc::clone(&self.view).update(&event).await?;
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(::bon::Builder)]
 #[builder(on(_, into))]
 pub struct UpdateMediaInteractor {
     view: ::std::sync::Arc<dyn UpdateMediaOutputBoundary>,
 
     urls: ::std::sync::Arc<dyn UrlRepository>,
+    library: ::std::sync::Arc<dyn LibraryRepository>,
 
     video_downloader: ::std::sync::Arc<dyn VideoDownloader>,
     playlist_downloader: ::std::sync::Arc<dyn PlaylistDownloader>,
     channel_downloader: ::std::sync::Arc<dyn ChannelDownloader>,
+    feed_downloader: ::std::sync::Arc<dyn FeedDownloader>,
+
+    video_resolver: ::std::sync::Arc<dyn VideoResolver>,
+    playlist_resolver: ::std::sync::Arc<dyn PlaylistResolver>,
+    channel_resolver: ::std::sync::Arc<dyn ChannelResolver>,
+    feed_resolver: ::std::sync::Arc<dyn FeedResolver>,
 
     video_postprocessors: MaybeOwnedVec<::std::sync::Arc<dyn PostProcessor<ResolvedVideo>>>,
     playlist_postprocessors: MaybeOwnedVec<::std::sync::Arc<dyn PostProcessor<ResolvedPlaylist>>>,
     channel_postprocessors: MaybeOwnedVec<::std::sync::Arc<dyn PostProcessor<ResolvedChannel>>>,
+
+    discarder: Option<::std::sync::Arc<dyn Discarder<ResolvedVideo>>>,
+
+    #[builder(default)]
+    dry_run: bool,
+
+    #[builder(skip)]
+    recorder: RunSummaryRecorder,
+
+    // Loaded on first use and kept up to date as videos complete, so that the
+    // library is read and its files checked once per run rather than once per url
+    #[builder(skip)]
+    downloaded_videos_cache: ::tokio::sync::OnceCell<::std::sync::Mutex<Vec<ResolvedVideo>>>,
 }
 
 #[async_trait]
 impl Accept<UpdateMediaRequestModel> for UpdateMediaInteractor {
     async fn accept(self: ::std::sync::Arc<Self>, _: UpdateMediaRequestModel) -> Fallible<()> {
-        let (video_urls, playlist_urls, channel_urls) = ::std::sync::Arc::clone(&self.urls).values().await?;
+        let started_at = ::std::time::Instant::now();
+
+        let (video_urls, playlist_urls, channel_urls, feed_urls) = ::std::sync::Arc::clone(&self.urls).values().await?;
 
         ::std::sync::Arc::clone(&self.view).activate().await?;
 
@@ -311,8 +710,18 @@ impl Accept<UpdateMediaRequestModel> for UpdateMediaInteractor {
             ::std::sync::Arc::clone(&self).accept(video_urls),
             ::std::sync::Arc::clone(&self).accept(playlist_urls),
             ::std::sync::Arc::clone(&self).accept(channel_urls),
+            ::std::sync::Arc::clone(&self).accept(feed_urls),
         )?;
 
+        if self.dry_run.not() {
+            let event = RunSummaryCompletedEvent::builder()
+                .summary(self.recorder.summarize(started_at.elapsed()))
+                .build();
+            ::std::sync::Arc::clone(&self.view)
+                .update(&RunSummaryEvent::Completed(event))
+                .await?;
+        }
+
         ::std::sync::Arc::clone(&self.view).deactivate().await?;
 
         Ok(())
@@ -335,6 +744,23 @@ impl Accept<BoxedStream<VideoUrl>> for UpdateMediaInteractor {
 #[async_trait]
 impl Accept<VideoUrl> for UpdateMediaInteractor {
     async fn accept(self: ::std::sync::Arc<Self>, url: VideoUrl) -> Fallible<()> {
+        if self.dry_run {
+            return ::std::sync::Arc::clone(&self).accept((url, DryRun)).await;
+        }
+
+        let downloaded_videos = ::std::sync::Arc::clone(&self).downloaded_videos().await?;
+
+        if let Some(video) = downloaded_videos
+            .iter()
+            .find(|video| VideoUrlMatcher::matches(&url, &video.id, &video.url))
+        {
+            ::tracing::debug!("Skipped video `{:?}`", url);
+
+            self.recorder.record_skipped_video(video);
+
+            return Ok(());
+        }
+
         let (video_download_events, diagnostic_events) =
             ::std::sync::Arc::clone(&self.video_downloader).download(url.clone()).await?;
 
@@ -363,8 +789,17 @@ impl Accept<BoxedStream<PlaylistUrl>> for UpdateMediaInteractor {
 #[async_trait]
 impl Accept<PlaylistUrl> for UpdateMediaInteractor {
     async fn accept(self: ::std::sync::Arc<Self>, url: PlaylistUrl) -> Fallible<()> {
+        if self.dry_run {
+            return ::std::sync::Arc::clone(&self).accept((url, DryRun)).await;
+        }
+
+        let downloaded_videos = ::std::sync::Arc::clone(&self).downloaded_videos().await?;
+        self.recorder.remember_downloaded_videos(&downloaded_videos);
+
         let (video_download_events, playlist_download_events, diagnostic_events) =
-            ::std::sync::Arc::clone(&self.playlist_downloader).download(url.clone()).await?;
+            ::std::sync::Arc::clone(&self.playlist_downloader)
+                .download(url.clone(), downloaded_videos)
+                .await?;
 
         ::tokio::try_join!(
             ::std::sync::Arc::clone(&self).accept(video_download_events),
@@ -392,8 +827,17 @@ impl Accept<BoxedStream<ChannelUrl>> for UpdateMediaInteractor {
 #[async_trait]
 impl Accept<ChannelUrl> for UpdateMediaInteractor {
     async fn accept(self: ::std::sync::Arc<Self>, url: ChannelUrl) -> Fallible<()> {
+        if self.dry_run {
+            return ::std::sync::Arc::clone(&self).accept((url, DryRun)).await;
+        }
+
+        let downloaded_videos = ::std::sync::Arc::clone(&self).downloaded_videos().await?;
+        self.recorder.remember_downloaded_videos(&downloaded_videos);
+
         let (video_download_events, playlist_download_events, channel_download_events, diagnostic_events) =
-            ::std::sync::Arc::clone(&self.channel_downloader).download(url.clone()).await?;
+            ::std::sync::Arc::clone(&self.channel_downloader)
+                .download(url.clone(), downloaded_videos)
+                .await?;
 
         ::tokio::try_join!(
             ::std::sync::Arc::clone(&self).accept(video_download_events),
@@ -407,14 +851,12 @@ impl Accept<ChannelUrl> for UpdateMediaInteractor {
 }
 
 #[async_trait]
-impl Accept<BoxedStream<VideoDownloadEvent>> for UpdateMediaInteractor {
-    async fn accept(self: ::std::sync::Arc<Self>, events: BoxedStream<VideoDownloadEvent>) -> Fallible<()> {
-        ::futures::pin_mut!(events);
-
-        while let Some(event) = events.next().await {
-            ::tracing::debug!("Received (IB) event `{:?}`", event);
+impl Accept<BoxedStream<FeedUrl>> for UpdateMediaInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, urls: BoxedStream<FeedUrl>) -> Fallible<()> {
+        ::futures::pin_mut!(urls);
 
-            ::std::sync::Arc::clone(&self.view).update(&event).await?;
+        while let Some(url) = urls.next().await {
+            ::std::sync::Arc::clone(&self).accept(url).await?;
         }
 
         Ok(())
@@ -422,82 +864,278 @@ impl Accept<BoxedStream<VideoDownloadEvent>> for UpdateMediaInteractor {
 }
 
 #[async_trait]
-impl Accept<(BoxedStream<VideoDownloadEvent>, WithPreprocessors)> for UpdateMediaInteractor {
-    async fn accept(
-        self: ::std::sync::Arc<Self>, (events, _): (BoxedStream<VideoDownloadEvent>, WithPreprocessors),
-    ) -> Fallible<()> {
-        ::futures::pin_mut!(events);
+impl Accept<FeedUrl> for UpdateMediaInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, url: FeedUrl) -> Fallible<()> {
+        if self.dry_run {
+            return ::std::sync::Arc::clone(&self).accept((url, DryRun)).await;
+        }
 
-        while let Some(event) = events.next().await {
-            ::std::sync::Arc::clone(&self.view).update(&event).await?;
+        let downloaded_videos = ::std::sync::Arc::clone(&self).downloaded_videos().await?;
+        self.recorder.remember_downloaded_videos(&downloaded_videos);
 
-            if let VideoDownloadEvent::Completed(event) = event {
-                ::tracing::debug!("Received (IB) event `{:?}`", event);
+        let (video_download_events, playlist_download_events, diagnostic_events) =
+            ::std::sync::Arc::clone(&self.feed_downloader)
+                .download(url.clone(), downloaded_videos)
+                .await?;
 
-                for postprocessor in &*self.video_postprocessors {
-                    ::std::sync::Arc::clone(postprocessor).process(&event.video).await?;
-                }
-            }
-        }
+        ::tokio::try_join!(
+            ::std::sync::Arc::clone(&self).accept(video_download_events),
+            ::std::sync::Arc::clone(&self).accept((playlist_download_events, WithPreprocessors)),
+            ::std::sync::Arc::clone(&self).accept(diagnostic_events),
+        )?;
 
         Ok(())
     }
 }
 
 #[async_trait]
-impl Accept<BoxedStream<PlaylistDownloadEvent>> for UpdateMediaInteractor {
-    async fn accept(self: ::std::sync::Arc<Self>, events: BoxedStream<PlaylistDownloadEvent>) -> Fallible<()> {
-        ::futures::pin_mut!(events);
-
-        while let Some(event) = events.next().await {
-            ::tracing::debug!("Received (IB) event `{:?}`", event);
-
-            ::std::sync::Arc::clone(&self.view).update(&event).await?;
-        }
+impl Accept<(VideoUrl, DryRun)> for UpdateMediaInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, (url, _): (VideoUrl, DryRun)) -> Fallible<()> {
+        let downloaded_videos = ::std::sync::Arc::clone(&self).downloaded_videos().await?;
+
+        let video = match downloaded_videos
+            .iter()
+            .find(|video| VideoUrlMatcher::matches(&url, &video.id, &video.url))
+        {
+            Some(video) => PlannedVideo::from(video.clone()),
+            None => {
+                let (video, diagnostic_events) = ::std::sync::Arc::clone(&self.video_resolver).resolve(url).await?;
+                ::std::sync::Arc::clone(&self).accept(diagnostic_events).await?;
+
+                video
+            },
+        };
+
+        let event = DownloadPlanVideoPlannedEvent::builder().video(video).build();
+        ::std::sync::Arc::clone(&self.view)
+            .update(&DownloadPlanEvent::VideoPlanned(event))
+            .await?;
 
         Ok(())
     }
 }
 
 #[async_trait]
-impl Accept<(BoxedStream<PlaylistDownloadEvent>, WithPreprocessors)> for UpdateMediaInteractor {
-    async fn accept(
-        self: ::std::sync::Arc<Self>, (events, _): (BoxedStream<PlaylistDownloadEvent>, WithPreprocessors),
-    ) -> Fallible<()> {
-        ::futures::pin_mut!(events);
+impl Accept<(PlaylistUrl, DryRun)> for UpdateMediaInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, (url, _): (PlaylistUrl, DryRun)) -> Fallible<()> {
+        let downloaded_videos = ::std::sync::Arc::clone(&self).downloaded_videos().await?;
 
-        while let Some(event) = events.next().await {
-            ::tracing::debug!("Received (IB) event `{:?}`", event);
+        let (mut playlist, diagnostic_events) = ::std::sync::Arc::clone(&self.playlist_resolver).resolve(url).await?;
+        ::std::sync::Arc::clone(&self).accept(diagnostic_events).await?;
 
-            ::std::sync::Arc::clone(&self.view).update(&event).await?;
+        Self::skip_downloaded_videos(&mut playlist.videos, &downloaded_videos);
 
-            if let PlaylistDownloadEvent::Completed(event) = event {
-                for postprocessor in &*self.playlist_postprocessors {
-                    ::std::sync::Arc::clone(postprocessor).process(&event.playlist).await?;
-                }
-            }
-        }
+        let event = DownloadPlanPlaylistPlannedEvent::builder().playlist(playlist).build();
+        ::std::sync::Arc::clone(&self.view)
+            .update(&DownloadPlanEvent::PlaylistPlanned(event))
+            .await?;
 
         Ok(())
     }
 }
 
 #[async_trait]
-impl Accept<(BoxedStream<ChannelDownloadEvent>, WithPreprocessors)> for UpdateMediaInteractor {
-    async fn accept(
-        self: ::std::sync::Arc<Self>, (events, _): (BoxedStream<ChannelDownloadEvent>, WithPreprocessors),
-    ) -> Fallible<()> {
-        ::futures::pin_mut!(events);
+impl Accept<(ChannelUrl, DryRun)> for UpdateMediaInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, (url, _): (ChannelUrl, DryRun)) -> Fallible<()> {
+        let downloaded_videos = ::std::sync::Arc::clone(&self).downloaded_videos().await?;
 
-        while let Some(event) = events.next().await {
-            ::tracing::debug!("Received (IB) event `{:?}`", event);
+        let (mut channel, diagnostic_events) = ::std::sync::Arc::clone(&self.channel_resolver).resolve(url).await?;
+        ::std::sync::Arc::clone(&self).accept(diagnostic_events).await?;
 
-            ::std::sync::Arc::clone(&self.view).update(&event).await?;
+        Self::skip_downloaded_videos(&mut channel.videos, &downloaded_videos);
 
-            if let ChannelDownloadEvent::Completed(event) = event {
-                for postprocessor in &*self.channel_postprocessors {
-                    ::std::sync::Arc::clone(postprocessor).process(&event.channel).await?;
-                }
+        if let Some(playlists) = channel.playlists.as_mut() {
+            for playlist in playlists.to_mut() {
+                Self::skip_downloaded_videos(&mut playlist.videos, &downloaded_videos);
+            }
+        }
+
+        let event = DownloadPlanChannelPlannedEvent::builder().channel(channel).build();
+        ::std::sync::Arc::clone(&self.view)
+            .update(&DownloadPlanEvent::ChannelPlanned(event))
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Accept<(FeedUrl, DryRun)> for UpdateMediaInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, (url, _): (FeedUrl, DryRun)) -> Fallible<()> {
+        let downloaded_videos = ::std::sync::Arc::clone(&self).downloaded_videos().await?;
+
+        let (mut playlist, diagnostic_events) = ::std::sync::Arc::clone(&self.feed_resolver).resolve(url).await?;
+        ::std::sync::Arc::clone(&self).accept(diagnostic_events).await?;
+
+        Self::skip_downloaded_videos(&mut playlist.videos, &downloaded_videos);
+
+        let event = DownloadPlanPlaylistPlannedEvent::builder().playlist(playlist).build();
+        ::std::sync::Arc::clone(&self.view)
+            .update(&DownloadPlanEvent::PlaylistPlanned(event))
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Accept<BoxedStream<VideoDownloadEvent>> for UpdateMediaInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, events: BoxedStream<VideoDownloadEvent>) -> Fallible<()> {
+        ::futures::pin_mut!(events);
+
+        while let Some(event) = events.next().await {
+            ::tracing::debug!("Received (IB) event `{:?}`", event);
+
+            ::std::sync::Arc::clone(&self.view).update(&event).await?;
+            self.recorder.record_video_event(&event);
+
+            if let VideoDownloadEvent::Completed(event) = event {
+                self.remember_downloaded_video(&event.video);
+
+                ::std::sync::Arc::clone(&self.library).insert(Video::from(event.video)).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Accept<(BoxedStream<VideoDownloadEvent>, WithPreprocessors)> for UpdateMediaInteractor {
+    async fn accept(
+        self: ::std::sync::Arc<Self>, (events, _): (BoxedStream<VideoDownloadEvent>, WithPreprocessors),
+    ) -> Fallible<()> {
+        ::futures::pin_mut!(events);
+
+        while let Some(event) = events.next().await {
+            ::std::sync::Arc::clone(&self.view).update(&event).await?;
+            self.recorder.record_video_event(&event);
+
+            if let VideoDownloadEvent::Completed(mut event) = event {
+                ::tracing::debug!("Received (IB) event `{:?}`", event);
+
+                for postprocessor in &*self.video_postprocessors {
+                    ::std::sync::Arc::clone(postprocessor).process(&mut event.video).await?;
+                }
+
+                self.remember_downloaded_video(&event.video);
+
+                ::std::sync::Arc::clone(&self.library).insert(Video::from(event.video)).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Accept<BoxedStream<PlaylistDownloadEvent>> for UpdateMediaInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, events: BoxedStream<PlaylistDownloadEvent>) -> Fallible<()> {
+        ::futures::pin_mut!(events);
+
+        while let Some(event) = events.next().await {
+            ::tracing::debug!("Received (IB) event `{:?}`", event);
+
+            ::std::sync::Arc::clone(&self.view).update(&event).await?;
+            self.recorder.record_playlist_event(&event);
+
+            if let PlaylistDownloadEvent::Started(ref event) = event {
+                ::std::sync::Arc::clone(&self).discard_removed_videos(&event.playlist).await?;
+            }
+
+            if let PlaylistDownloadEvent::Completed(event) = event {
+                ::std::sync::Arc::clone(&self.library)
+                    .insert(Playlist::from(event.playlist))
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Accept<(BoxedStream<PlaylistDownloadEvent>, WithPreprocessors)> for UpdateMediaInteractor {
+    async fn accept(
+        self: ::std::sync::Arc<Self>, (events, _): (BoxedStream<PlaylistDownloadEvent>, WithPreprocessors),
+    ) -> Fallible<()> {
+        ::futures::pin_mut!(events);
+
+        while let Some(event) = events.next().await {
+            ::tracing::debug!("Received (IB) event `{:?}`", event);
+
+            ::std::sync::Arc::clone(&self.view).update(&event).await?;
+            self.recorder.record_playlist_event(&event);
+
+            if let PlaylistDownloadEvent::Started(ref event) = event {
+                ::std::sync::Arc::clone(&self).discard_removed_videos(&event.playlist).await?;
+            }
+
+            if let PlaylistDownloadEvent::Completed(mut event) = event {
+                let videos = event.playlist.videos.clone();
+
+                for postprocessor in &*self.playlist_postprocessors {
+                    ::std::sync::Arc::clone(postprocessor).process(&mut event.playlist).await?;
+                }
+
+                ProcessedVideoRecorder::record(&self.library, videos.as_deref(), event.playlist.videos.as_deref())
+                    .await?;
+
+                ::std::sync::Arc::clone(&self.library)
+                    .insert(Playlist::from(event.playlist))
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Accept<(BoxedStream<ChannelDownloadEvent>, WithPreprocessors)> for UpdateMediaInteractor {
+    async fn accept(
+        self: ::std::sync::Arc<Self>, (events, _): (BoxedStream<ChannelDownloadEvent>, WithPreprocessors),
+    ) -> Fallible<()> {
+        ::futures::pin_mut!(events);
+
+        while let Some(event) = events.next().await {
+            ::tracing::debug!("Received (IB) event `{:?}`", event);
+
+            ::std::sync::Arc::clone(&self.view).update(&event).await?;
+            self.recorder.record_channel_event(&event);
+
+            if let ChannelDownloadEvent::Completed(mut event) = event {
+                let (videos, playlists) = (event.channel.videos.clone(), event.channel.playlists.clone());
+
+                for postprocessor in &*self.channel_postprocessors {
+                    ::std::sync::Arc::clone(postprocessor).process(&mut event.channel).await?;
+                }
+
+                ProcessedVideoRecorder::record(&self.library, videos.as_deref(), event.channel.videos.as_deref())
+                    .await?;
+
+                let processed_playlists = event.channel.playlists.as_deref().unwrap_or_default();
+
+                for (playlist, processed_playlist) in
+                    playlists.as_deref().unwrap_or_default().iter().zip(processed_playlists)
+                {
+                    let changed = ProcessedVideoRecorder::record(
+                        &self.library,
+                        playlist.videos.as_deref(),
+                        processed_playlist.videos.as_deref(),
+                    )
+                    .await?;
+
+                    if changed {
+                        ::std::sync::Arc::clone(&self.library)
+                            .insert(Playlist::from(processed_playlist.clone()))
+                            .await?;
+                    }
+                }
+
+                ::std::sync::Arc::clone(&self.library)
+                    .insert(Channel::from(event.channel))
+                    .await?;
             }
         }
 
@@ -520,4 +1158,970 @@ impl Accept<BoxedStream<DiagnosticEvent>> for UpdateMediaInteractor {
     }
 }
 
+impl UpdateMediaInteractor {
+    async fn downloaded_videos(self: ::std::sync::Arc<Self>) -> Fallible<MaybeOwnedVec<ResolvedVideo>> {
+        let videos = self
+            .downloaded_videos_cache
+            .get_or_try_init(|| async {
+                let (videos, ..) = ::std::sync::Arc::clone(&self.library).values().await?;
+
+                let videos = videos
+                    .filter(|video| {
+                        let path = video.path.clone();
+                        async move { ::tokio::fs::try_exists(&*path).await.unwrap_or(false) }
+                    })
+                    .map(ResolvedVideo::from)
+                    .collect::<Vec<_>>()
+                    .await;
+
+                Fallible::Ok(::std::sync::Mutex::new(videos))
+            })
+            .await?;
+
+        let videos = videos.lock().unwrap_or_else(::std::sync::PoisonError::into_inner).clone();
+
+        Ok(videos.into())
+    }
+
+    fn remember_downloaded_video(&self, video: &ResolvedVideo) {
+        let Some(videos) = self.downloaded_videos_cache.get() else {
+            return;
+        };
+
+        let mut videos = videos.lock().unwrap_or_else(::std::sync::PoisonError::into_inner);
+        videos.retain(|other_video| other_video.id != video.id);
+        videos.push(video.clone());
+    }
+
+    fn forget_downloaded_video(&self, video: &ResolvedVideo) {
+        let Some(videos) = self.downloaded_videos_cache.get() else {
+            return;
+        };
+
+        let mut videos = videos.lock().unwrap_or_else(::std::sync::PoisonError::into_inner);
+        videos.retain(|other_video| other_video.id != video.id);
+    }
+
+    fn skip_downloaded_videos(videos: &mut Option<MaybeOwnedVec<PlannedVideo>>, downloaded_videos: &[ResolvedVideo]) {
+        let Some(videos) = videos.as_mut() else {
+            return;
+        };
+
+        for video in videos.to_mut() {
+            if let Some(downloaded_video) = downloaded_videos
+                .iter()
+                .find(|downloaded_video| downloaded_video.id == video.id)
+            {
+                video.path = Some(downloaded_video.path.clone());
+                video.skipped = true;
+            }
+        }
+    }
+
+    async fn discard_removed_videos(
+        self: ::std::sync::Arc<Self>, playlist: &PartiallyResolvedPlaylist,
+    ) -> Fallible<()> {
+        let Some(discarder) = self.discarder.as_ref() else {
+            return Ok(());
+        };
+
+        let references =
+            VideoReferences::load(::std::sync::Arc::clone(&self.library), ::std::sync::Arc::clone(&self.urls)).await?;
+
+        let Some(previous_playlist) = references
+            .playlists
+            .iter()
+            .find(|previous_playlist| **previous_playlist.id == *playlist.id)
+        else {
+            return Ok(());
+        };
+
+        let current_video_ids = playlist
+            .videos
+            .as_deref()
+            .into_iter()
+            .flatten()
+            .map(|video| &*video.id)
+            .collect::<::std::collections::HashSet<_>>();
+
+        let removed_videos = previous_playlist
+            .videos
+            .as_deref()
+            .into_iter()
+            .flatten()
+            .filter(|video| current_video_ids.contains(&**video.id).not())
+            .filter(|video| references.contains(video, Some(&playlist.id)).not())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        // Files are discarded before their records, so that a failed discard leaves
+        // the record behind to try again with
+        for video in removed_videos {
+            let resolved_video = ResolvedVideo::from(video.clone());
+
+            ::std::sync::Arc::clone(discarder).discard(&resolved_video).await?;
+            ::std::sync::Arc::clone(&self.library).remove(video).await?;
+
+            self.forget_downloaded_video(&resolved_video);
+
+            let event = PlaylistDownloadVideoRemovedEvent::builder()
+                .playlist_id(playlist.id.clone())
+                .video(resolved_video)
+                .build();
+
+            ::std::sync::Arc::clone(&self.view)
+                .update(&PlaylistDownloadEvent::VideoRemoved(event))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct RemoveSubscriptionInteractor {
+    view: ::std::sync::Arc<dyn RemoveSubscriptionOutputBoundary>,
+
+    urls: ::std::sync::Arc<dyn UrlRepository>,
+    library: ::std::sync::Arc<dyn LibraryRepository>,
+
+    discarder: Option<::std::sync::Arc<dyn Discarder<ResolvedVideo>>>,
+}
+
+#[async_trait]
+impl Accept<RemoveSubscriptionRequestModel> for RemoveSubscriptionInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, request: RemoveSubscriptionRequestModel) -> Fallible<()> {
+        let (video_removed, playlist_removed, channel_removed, feed_removed) = ::tokio::try_join!(
+            ::std::sync::Arc::clone(&self.urls).remove(VideoUrl::from(request.url.clone())),
+            ::std::sync::Arc::clone(&self.urls).remove(PlaylistUrl::from(request.url.clone())),
+            ::std::sync::Arc::clone(&self.urls).remove(ChannelUrl::from(request.url.clone())),
+            ::std::sync::Arc::clone(&self.urls).remove(FeedUrl::from(request.url.clone())),
+        )?;
+
+        ::std::sync::Arc::clone(&self.view).activate().await?;
+
+        if (video_removed || playlist_removed || channel_removed || feed_removed).not() {
+            let event = DiagnosticEvent::builder()
+                .level(DiagnosticLevel::Warning)
+                .message(format!("`{}` is not subscribed", request.url).into())
+                .build();
+
+            ::std::sync::Arc::clone(&self.view).update(&event).await?;
+            ::std::sync::Arc::clone(&self.view).deactivate().await?;
+
+            return Ok(());
+        }
+
+        let videos = ::std::sync::Arc::clone(&self).forget(request.url.clone()).await?;
+        ::std::sync::Arc::clone(&self).discard_orphaned_videos(videos).await?;
+
+        let event = SubscriptionRemovalCompletedEvent::builder().url(request.url).build();
+        ::std::sync::Arc::clone(&self.view)
+            .update(&SubscriptionRemovalEvent::Completed(event))
+            .await?;
+
+        ::std::sync::Arc::clone(&self.view).deactivate().await?;
+
+        Ok(())
+    }
+}
+
+impl RemoveSubscriptionInteractor {
+    async fn forget(self: ::std::sync::Arc<Self>, url: MaybeOwnedString) -> Fallible<Vec<Video>> {
+        let (videos, playlists, channels) = ::std::sync::Arc::clone(&self.library).values().await?;
+        let (_, playlist_urls, ..) = ::std::sync::Arc::clone(&self.urls).values().await?;
+
+        let videos = videos.collect::<Vec<_>>().await;
+        let playlists = playlists.collect::<Vec<_>>().await;
+        let channels = channels.collect::<Vec<_>>().await;
+        let playlist_urls = playlist_urls.collect::<Vec<_>>().await;
+
+        let mut orphaned_videos = videos
+            .into_iter()
+            .filter(|video| VideoUrlMatcher::matches(&url, &video.id, &video.url))
+            .collect::<Vec<_>>();
+
+        for playlist in playlists.iter().filter(|playlist| **playlist.url == *url) {
+            ::std::sync::Arc::clone(&self.library).remove(playlist.clone()).await?;
+
+            orphaned_videos.extend(playlist.videos.as_deref().into_iter().flatten().cloned());
+        }
+
+        for channel in channels.into_iter().filter(|channel| **channel.url == *url) {
+            ::std::sync::Arc::clone(&self.library).remove(channel.clone()).await?;
+
+            orphaned_videos.extend(channel.videos.as_deref().into_iter().flatten().cloned());
+
+            for playlist in channel.playlists.as_deref().into_iter().flatten() {
+                if playlist_urls.iter().any(|playlist_url| **playlist_url == *playlist.url).not() {
+                    ::std::sync::Arc::clone(&self.library).remove(playlist.clone()).await?;
+                }
+
+                orphaned_videos.extend(playlist.videos.as_deref().into_iter().flatten().cloned());
+            }
+        }
+
+        let mut video_ids = ::std::collections::HashSet::new();
+        orphaned_videos.retain(|video| video_ids.insert(MaybeOwnedString::from(video.id.clone())));
+
+        Ok(orphaned_videos)
+    }
+
+    async fn discard_orphaned_videos(self: ::std::sync::Arc<Self>, videos: Vec<Video>) -> Fallible<()> {
+        let Some(discarder) = self.discarder.as_ref() else {
+            return Ok(());
+        };
+
+        let references =
+            VideoReferences::load(::std::sync::Arc::clone(&self.library), ::std::sync::Arc::clone(&self.urls)).await?;
+
+        // Files are discarded before their records, so that a failed discard leaves
+        // the record behind to try again with
+        for video in videos.into_iter().filter(|video| references.contains(video, None).not()) {
+            let resolved_video = ResolvedVideo::from(video.clone());
+
+            ::std::sync::Arc::clone(discarder).discard(&resolved_video).await?;
+            ::std::sync::Arc::clone(&self.library).remove(video).await?;
+
+            let event = SubscriptionRemovalVideoRemovedEvent::builder().video(resolved_video).build();
+            ::std::sync::Arc::clone(&self.view)
+                .update(&SubscriptionRemovalEvent::VideoRemoved(event))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct ListSubscriptionsInteractor {
+    view: ::std::sync::Arc<dyn ListSubscriptionsOutputBoundary>,
+
+    urls: ::std::sync::Arc<dyn UrlRepository>,
+    library: ::std::sync::Arc<dyn LibraryRepository>,
+}
+
+#[async_trait]
+impl Accept<ListSubscriptionsRequestModel> for ListSubscriptionsInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, _: ListSubscriptionsRequestModel) -> Fallible<()> {
+        let ((video_urls, playlist_urls, channel_urls, feed_urls), (videos, playlists, channels)) = ::tokio::try_join!(
+            ::std::sync::Arc::clone(&self.urls).values(),
+            ::std::sync::Arc::clone(&self.library).values(),
+        )?;
+
+        let mut video_urls = video_urls.collect::<Vec<_>>().await;
+        let mut playlist_urls = playlist_urls.collect::<Vec<_>>().await;
+        let mut channel_urls = channel_urls.collect::<Vec<_>>().await;
+        let mut feed_urls = feed_urls.collect::<Vec<_>>().await;
+
+        video_urls.sort_by(|this, other| (**this).cmp(&**other));
+        playlist_urls.sort_by(|this, other| (**this).cmp(&**other));
+        channel_urls.sort_by(|this, other| (**this).cmp(&**other));
+        feed_urls.sort_by(|this, other| (**this).cmp(&**other));
+
+        let videos = videos.collect::<Vec<_>>().await;
+        let playlists = playlists.collect::<Vec<_>>().await;
+        let channels = channels.collect::<Vec<_>>().await;
+
+        let mut subscriptions = Vec::new();
+
+        for url in video_urls {
+            let video = videos
+                .iter()
+                .find(|video| VideoUrlMatcher::matches(&url, &video.id, &video.url));
+
+            let subscription = Subscription::builder()
+                .kind(SubscriptionKind::Video)
+                .url(MaybeOwnedString::from(url))
+                .title(video.and_then(|video| video.metadata.title.clone()))
+                .updated_at(video.and_then(|video| video.updated_at))
+                .local_videos(Self::count_local_videos(video.into_iter()).await)
+                .build();
+
+            subscriptions.push(subscription);
+        }
+
+        for url in playlist_urls {
+            let playlist = playlists.iter().find(|playlist| playlist.url == url);
+
+            let subscription = Subscription::builder()
+                .kind(SubscriptionKind::Playlist)
+                .url(MaybeOwnedString::from(url))
+                .title(playlist.and_then(|playlist| playlist.metadata.title.clone()))
+                .updated_at(playlist.and_then(|playlist| playlist.updated_at))
+                .local_videos(
+                    Self::count_local_videos(
+                        playlist.and_then(|playlist| playlist.videos.as_deref()).into_iter().flatten(),
+                    )
+                    .await,
+                )
+                .build();
+
+            subscriptions.push(subscription);
+        }
+
+        for url in channel_urls {
+            let channel = channels.iter().find(|channel| channel.url == url);
+
+            let subscription = Subscription::builder()
+                .kind(SubscriptionKind::Channel)
+                .url(MaybeOwnedString::from(url))
+                .title(channel.and_then(|channel| channel.metadata.title.clone()))
+                .updated_at(channel.and_then(|channel| channel.updated_at))
+                .local_videos(
+                    Self::count_local_videos(
+                        channel
+                            .and_then(|channel| channel.videos.as_deref())
+                            .into_iter()
+                            .flatten()
+                            .chain(
+                                channel
+                                    .and_then(|channel| channel.playlists.as_deref())
+                                    .into_iter()
+                                    .flatten()
+                                    .flat_map(|playlist| playlist.videos.as_deref().into_iter().flatten()),
+                            ),
+                    )
+                    .await,
+                )
+                .build();
+
+            subscriptions.push(subscription);
+        }
+
+        for url in feed_urls {
+            // Feeds are stored as playlists, so that their episodes are tracked like any
+            // other video
+            let playlist = playlists.iter().find(|playlist| **playlist.url == **url);
+
+            let subscription = Subscription::builder()
+                .kind(SubscriptionKind::Feed)
+                .url(MaybeOwnedString::from(url))
+                .title(playlist.and_then(|playlist| playlist.metadata.title.clone()))
+                .updated_at(playlist.and_then(|playlist| playlist.updated_at))
+                .local_videos(
+                    Self::count_local_videos(
+                        playlist.and_then(|playlist| playlist.videos.as_deref()).into_iter().flatten(),
+                    )
+                    .await,
+                )
+                .build();
+
+            subscriptions.push(subscription);
+        }
+
+        ::std::sync::Arc::clone(&self.view).activate().await?;
+
+        let total_subscriptions = subscriptions.len() as u64;
+
+        for subscription in subscriptions {
+            let event = SubscriptionListingSubscriptionListedEvent::builder()
+                .subscription(subscription)
+                .build();
+
+            ::std::sync::Arc::clone(&self.view)
+                .update(&SubscriptionListingEvent::SubscriptionListed(event))
+                .await?;
+        }
+
+        let event = SubscriptionListingCompletedEvent::builder()
+            .total_subscriptions(total_subscriptions)
+            .build();
+        ::std::sync::Arc::clone(&self.view)
+            .update(&SubscriptionListingEvent::Completed(event))
+            .await?;
+
+        ::std::sync::Arc::clone(&self.view).deactivate().await?;
+
+        Ok(())
+    }
+}
+
+impl ListSubscriptionsInteractor {
+    async fn count_local_videos(videos: impl Iterator<Item = &Video>) -> u64 {
+        let mut video_ids = ::std::collections::HashSet::new();
+        let mut local_videos = 0;
+
+        for video in videos {
+            if video_ids.insert(&**video.id) && ::tokio::fs::try_exists(&**video.path).await.unwrap_or(false) {
+                local_videos += 1;
+            }
+        }
+
+        local_videos
+    }
+}
+
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct ExportSubscriptionsInteractor {
+    view: ::std::sync::Arc<dyn ExportSubscriptionsOutputBoundary>,
+
+    urls: ::std::sync::Arc<dyn UrlRepository>,
+
+    exporter: ::std::sync::Arc<dyn Exporter<SubscriptionUrls>>,
+}
+
+#[async_trait]
+impl Accept<ExportSubscriptionsRequestModel> for ExportSubscriptionsInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, request: ExportSubscriptionsRequestModel) -> Fallible<()> {
+        let (video_urls, playlist_urls, channel_urls, feed_urls) = ::std::sync::Arc::clone(&self.urls).values().await?;
+
+        let mut video_urls = video_urls.map(MaybeOwnedString::from).collect::<Vec<_>>().await;
+        let mut playlist_urls = playlist_urls.map(MaybeOwnedString::from).collect::<Vec<_>>().await;
+        let mut channel_urls = channel_urls.map(MaybeOwnedString::from).collect::<Vec<_>>().await;
+        let mut feed_urls = feed_urls.map(MaybeOwnedString::from).collect::<Vec<_>>().await;
+
+        video_urls.sort();
+        playlist_urls.sort();
+        channel_urls.sort();
+        feed_urls.sort();
+
+        let subscription_urls = SubscriptionUrls::builder()
+            .video_urls(video_urls)
+            .playlist_urls(playlist_urls)
+            .channel_urls(channel_urls)
+            .feed_urls(feed_urls)
+            .build();
+
+        ::std::sync::Arc::clone(&self.view).activate().await?;
+
+        ::std::sync::Arc::clone(&self.exporter)
+            .export(&subscription_urls, &request.path)
+            .await?;
+
+        let event = SubscriptionExportCompletedEvent::builder()
+            .path(request.path)
+            .total_video_urls(subscription_urls.video_urls.len() as u64)
+            .total_playlist_urls(subscription_urls.playlist_urls.len() as u64)
+            .total_channel_urls(subscription_urls.channel_urls.len() as u64)
+            .total_feed_urls(subscription_urls.feed_urls.len() as u64)
+            .build();
+        ::std::sync::Arc::clone(&self.view)
+            .update(&SubscriptionExportEvent::Completed(event))
+            .await?;
+
+        ::std::sync::Arc::clone(&self.view).deactivate().await?;
+
+        Ok(())
+    }
+}
+
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct ImportSubscriptionsInteractor {
+    view: ::std::sync::Arc<dyn ImportSubscriptionsOutputBoundary>,
+
+    urls: ::std::sync::Arc<dyn UrlRepository>,
+
+    importer: ::std::sync::Arc<dyn Importer<SubscriptionUrls>>,
+}
+
+#[async_trait]
+impl Accept<ImportSubscriptionsRequestModel> for ImportSubscriptionsInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, request: ImportSubscriptionsRequestModel) -> Fallible<()> {
+        let subscription_urls = ::std::sync::Arc::clone(&self.importer).import(&request.path).await?;
+
+        let (video_urls, playlist_urls, channel_urls, feed_urls) = ::std::sync::Arc::clone(&self.urls).values().await?;
+
+        let mut video_urls = video_urls
+            .map(MaybeOwnedString::from)
+            .collect::<::std::collections::HashSet<_>>()
+            .await;
+        let mut playlist_urls = playlist_urls
+            .map(MaybeOwnedString::from)
+            .collect::<::std::collections::HashSet<_>>()
+            .await;
+        let mut channel_urls = channel_urls
+            .map(MaybeOwnedString::from)
+            .collect::<::std::collections::HashSet<_>>()
+            .await;
+        let mut feed_urls = feed_urls
+            .map(MaybeOwnedString::from)
+            .collect::<::std::collections::HashSet<_>>()
+            .await;
+
+        ::std::sync::Arc::clone(&self.view).activate().await?;
+
+        let mut imported_urls = 0;
+        let mut skipped_urls = 0;
+
+        for url in subscription_urls.video_urls.iter().cloned() {
+            if video_urls.insert(url.clone()) {
+                ::std::sync::Arc::clone(&self.urls).insert(VideoUrl::from(url)).await?;
+                imported_urls += 1;
+            } else {
+                skipped_urls += 1;
+            }
+        }
+
+        for url in subscription_urls.playlist_urls.iter().cloned() {
+            if playlist_urls.insert(url.clone()) {
+                ::std::sync::Arc::clone(&self.urls).insert(PlaylistUrl::from(url)).await?;
+                imported_urls += 1;
+            } else {
+                skipped_urls += 1;
+            }
+        }
+
+        for url in subscription_urls.channel_urls.iter().cloned() {
+            if channel_urls.insert(url.clone()) {
+                ::std::sync::Arc::clone(&self.urls).insert(ChannelUrl::from(url)).await?;
+                imported_urls += 1;
+            } else {
+                skipped_urls += 1;
+            }
+        }
+
+        for url in subscription_urls.feed_urls.iter().cloned() {
+            if feed_urls.insert(url.clone()) {
+                ::std::sync::Arc::clone(&self.urls).insert(FeedUrl::from(url)).await?;
+                imported_urls += 1;
+            } else {
+                skipped_urls += 1;
+            }
+        }
+
+        let event = SubscriptionImportCompletedEvent::builder()
+            .path(request.path)
+            .imported_urls(imported_urls)
+            .skipped_urls(skipped_urls)
+            .build();
+        ::std::sync::Arc::clone(&self.view)
+            .update(&SubscriptionImportEvent::Completed(event))
+            .await?;
+
+        ::std::sync::Arc::clone(&self.view).deactivate().await?;
+
+        Ok(())
+    }
+}
+
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct ImportMediaInteractor {
+    view: ::std::sync::Arc<dyn ImportMediaOutputBoundary>,
+
+    library: ::std::sync::Arc<dyn LibraryRepository>,
+    importer: ::std::sync::Arc<dyn MediaImporter>,
+    postprocessors: MaybeOwnedVec<::std::sync::Arc<dyn PostProcessor<ResolvedVideo>>>,
+
+    #[builder(skip)]
+    recorder: RunSummaryRecorder,
+}
+
+#[async_trait]
+impl Accept<ImportMediaRequestModel> for ImportMediaInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, request: ImportMediaRequestModel) -> Fallible<()> {
+        let started_at = ::std::time::Instant::now();
+
+        let imported_videos = ::std::sync::Arc::clone(&self).imported_videos().await?;
+        self.recorder.remember_downloaded_videos(&imported_videos);
+
+        let (video_download_events, playlist_download_events, diagnostic_events) =
+            ::std::sync::Arc::clone(&self.importer)
+                .import(request.path, imported_videos)
+                .await?;
+
+        ::std::sync::Arc::clone(&self.view).activate().await?;
+
+        ::tokio::try_join!(
+            ::std::sync::Arc::clone(&self).accept(video_download_events),
+            ::std::sync::Arc::clone(&self).accept(playlist_download_events),
+            ::std::sync::Arc::clone(&self).accept(diagnostic_events),
+        )?;
+
+        let event = RunSummaryCompletedEvent::builder()
+            .summary(self.recorder.summarize(started_at.elapsed()))
+            .build();
+        ::std::sync::Arc::clone(&self.view)
+            .update(&RunSummaryEvent::Completed(event))
+            .await?;
+
+        ::std::sync::Arc::clone(&self.view).deactivate().await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Accept<BoxedStream<VideoDownloadEvent>> for ImportMediaInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, events: BoxedStream<VideoDownloadEvent>) -> Fallible<()> {
+        ::futures::pin_mut!(events);
+
+        while let Some(event) = events.next().await {
+            ::tracing::debug!("Received (IB) event `{:?}`", event);
+
+            ::std::sync::Arc::clone(&self.view).update(&event).await?;
+            self.recorder.record_video_event(&event);
+
+            if let VideoDownloadEvent::Completed(mut event) = event {
+                for postprocessor in &*self.postprocessors {
+                    ::std::sync::Arc::clone(postprocessor).process(&mut event.video).await?;
+                }
+
+                ::std::sync::Arc::clone(&self.library).insert(Video::from(event.video)).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Accept<BoxedStream<PlaylistDownloadEvent>> for ImportMediaInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, events: BoxedStream<PlaylistDownloadEvent>) -> Fallible<()> {
+        ::futures::pin_mut!(events);
+
+        // The scanned folder only groups the progress, so that it is never stored as a
+        // playlist of its own
+        while let Some(event) = events.next().await {
+            ::tracing::debug!("Received (IB) event `{:?}`", event);
+
+            ::std::sync::Arc::clone(&self.view).update(&event).await?;
+            self.recorder.record_playlist_event(&event);
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Accept<BoxedStream<DiagnosticEvent>> for ImportMediaInteractor {
+    async fn accept(self: ::std::sync::Arc<Self>, events: BoxedStream<DiagnosticEvent>) -> Fallible<()> {
+        ::futures::pin_mut!(events);
+
+        while let Some(event) = events.next().await {
+            ::tracing::debug!("Received (IB) event `{:?}`", event);
+
+            ::std::sync::Arc::clone(&self.view).update(&event).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ImportMediaInteractor {
+    async fn imported_videos(self: ::std::sync::Arc<Self>) -> Fallible<MaybeOwnedVec<ResolvedVideo>> {
+        let (videos, ..) = ::std::sync::Arc::clone(&self.library).values().await?;
+
+        let videos = videos
+            .filter(|video| {
+                let path = video.path.clone();
+                async move { ::tokio::fs::try_exists(&*path).await.unwrap_or(false) }
+            })
+            .map(ResolvedVideo::from)
+            .collect::<Vec<_>>()
+            .await;
+
+        Ok(videos.into())
+    }
+}
+
+struct VideoUrlMatcher;
+
+impl VideoUrlMatcher {
+    // Subscribed urls and library urls often differ in form only, like `youtu.be`
+    // links or extra query parameters, so that videos are matched by the id their
+    // url carries, and by the url itself when it carries none
+    fn matches(url: &str, video_id: &str, video_url: &str) -> bool {
+        match Self::video_id(url) {
+            Some(id) => id == video_id,
+            None => url == video_url,
+        }
+    }
+
+    fn video_id(url: &str) -> Option<&str> {
+        let url = url.split_once("://").map_or(url, |(_, url)| url);
+        let url = url.split('#').next().unwrap_or_default();
+        let (location, query) = url.split_once('?').unwrap_or((url, ""));
+        let (host, path) = location.split_once('/').unwrap_or((location, ""));
+
+        let host = ["www.", "m.", "music."]
+            .into_iter()
+            .fold(host, |host, prefix| host.strip_prefix(prefix).unwrap_or(host));
+        let mut segments = path.split('/').filter(|segment| segment.is_empty().not());
+
+        let id = match (host, segments.next()) {
+            ("youtu.be", id) => id,
+            ("youtube.com" | "youtube-nocookie.com", Some("watch")) =>
+                query.split('&').find_map(|parameter| parameter.strip_prefix("v=")),
+            ("youtube.com" | "youtube-nocookie.com", Some("shorts" | "embed" | "live" | "v")) => segments.next(),
+            _ => None,
+        };
+
+        id.filter(|id| id.is_empty().not())
+    }
+}
+
+struct ProcessedVideoRecorder;
+
+impl ProcessedVideoRecorder {
+    // Videos are recorded as soon as they complete, ahead of the post-processors of
+    // their playlist or channel, so that whatever those changed about them is
+    // recorded over again
+    async fn record(
+        library: &::std::sync::Arc<dyn LibraryRepository>, videos: Option<&[ResolvedVideo]>,
+        processed_videos: Option<&[ResolvedVideo]>,
+    ) -> Fallible<bool> {
+        let mut changed = false;
+
+        for (video, processed_video) in videos.unwrap_or_default().iter().zip(processed_videos.unwrap_or_default()) {
+            if video == processed_video {
+                continue;
+            }
+
+            ::std::sync::Arc::clone(library)
+                .insert(Video::from(processed_video.clone()))
+                .await?;
+
+            changed = true;
+        }
+
+        Ok(changed)
+    }
+}
+
+struct VideoReferences {
+    playlists: Vec<Playlist>,
+    channels: Vec<Channel>,
+    video_urls: Vec<VideoUrl>,
+}
+
+impl VideoReferences {
+    async fn load(
+        library: ::std::sync::Arc<dyn LibraryRepository>, urls: ::std::sync::Arc<dyn UrlRepository>,
+    ) -> Fallible<Self> {
+        let ((_, playlists, channels), (video_urls, ..)) = ::tokio::try_join!(library.values(), urls.values())?;
+
+        Ok(Self {
+            playlists: playlists.collect().await,
+            channels: channels.collect().await,
+            video_urls: video_urls.collect().await,
+        })
+    }
+
+    fn contains(&self, video: &Video, excluded_playlist_id: Option<&str>) -> bool {
+        let referenced_by_playlists = self
+            .playlists
+            .iter()
+            .chain(
+                self.channels
+                    .iter()
+                    .flat_map(|channel| channel.playlists.as_deref().into_iter().flatten()),
+            )
+            .filter(|playlist| excluded_playlist_id != Some(&**playlist.id))
+            .flat_map(|playlist| playlist.videos.as_deref().into_iter().flatten())
+            .any(|other_video| *other_video.id == *video.id);
+        let referenced_by_channels = self
+            .channels
+            .iter()
+            .flat_map(|channel| channel.videos.as_deref().into_iter().flatten())
+            .any(|other_video| *other_video.id == *video.id);
+        let referenced_by_videos = self
+            .video_urls
+            .iter()
+            .any(|url| VideoUrlMatcher::matches(url, &video.id, &video.url));
+
+        referenced_by_playlists || referenced_by_channels || referenced_by_videos
+    }
+}
+
+#[derive(Default)]
+struct RunSummaryRecorder {
+    state: ::std::sync::Mutex<RunSummaryRecorderState>,
+}
+
+#[derive(Default)]
+struct RunSummaryRecorderState {
+    succeeded: RunSummaryItems,
+    failed: RunSummaryItems,
+    skipped: RunSummaryItems,
+    retried: RunSummaryItems,
+
+    total_bytes: u64,
+    total_bytes_by_video_ids: ::std::collections::HashMap<MaybeOwnedString, u64>,
+
+    titles_by_ids: ::std::collections::HashMap<MaybeOwnedString, MaybeOwnedString>,
+    downloaded_videos_by_ids: ::std::collections::HashMap<MaybeOwnedString, ResolvedVideo>,
+}
+
+// Items are kept in the order they were first recorded, and indexed by url so
+// that later events replace them without a scan
+#[derive(Default)]
+struct RunSummaryItems {
+    items: Vec<RunSummaryItem>,
+    positions_by_urls: ::std::collections::HashMap<MaybeOwnedString, usize>,
+}
+
+impl RunSummaryRecorder {
+    const ALREADY_DOWNLOADED: &str = "already downloaded";
+
+    fn remember_downloaded_videos(&self, videos: &[ResolvedVideo]) {
+        let mut state = self.state();
+
+        for video in videos {
+            state.downloaded_videos_by_ids.insert(video.id.clone(), video.clone());
+        }
+    }
+
+    fn record_skipped_video(&self, video: &ResolvedVideo) {
+        let item = RunSummaryItem::builder()
+            .url(video.url.clone())
+            .title(video.metadata.title.clone())
+            .reason(Some(Self::ALREADY_DOWNLOADED.into()))
+            .build();
+
+        self.state().skipped.record(item);
+    }
+
+    fn record_video_event(&self, event: &VideoDownloadEvent) {
+        let mut state = self.state();
+
+        match event {
+            VideoDownloadEvent::Started(event) =>
+                if let Some(title) = event.video.metadata.title.clone() {
+                    state.titles_by_ids.insert(event.video.id.clone(), title);
+                },
+            VideoDownloadEvent::ProgressUpdated(event) => {
+                state.total_bytes_by_video_ids.insert(event.video_id.clone(), event.total_bytes);
+            },
+            VideoDownloadEvent::RetryScheduled(event) => {
+                let item = RunSummaryItem::builder()
+                    .url(event.url.clone())
+                    .title(state.title(event.video_id.as_deref()))
+                    .reason(Some(event.reason.clone()))
+                    .build();
+
+                state.retried.record(item);
+            },
+            VideoDownloadEvent::Completed(event) => {
+                state.total_bytes += state.total_bytes_by_video_ids.remove(&event.video.id).unwrap_or(0);
+
+                let item = RunSummaryItem::builder()
+                    .url(event.video.url.clone())
+                    .title(event.video.metadata.title.clone())
+                    .reason(None)
+                    .build();
+
+                state.succeeded.record(item);
+            },
+            VideoDownloadEvent::Failed(event) => {
+                let item = RunSummaryItem::builder()
+                    .url(event.url.clone())
+                    .title(state.title(event.video_id.as_deref()))
+                    .reason(Some(event.reason.clone()))
+                    .build();
+
+                state.failed.record(item);
+            },
+        }
+    }
+
+    fn record_playlist_event(&self, event: &PlaylistDownloadEvent) {
+        let mut state = self.state();
+
+        match event {
+            PlaylistDownloadEvent::Started(event) => {
+                if let Some(title) = event.playlist.metadata.title.clone() {
+                    state.titles_by_ids.insert(event.playlist.id.clone(), title);
+                }
+
+                state.skip_downloaded_videos(event.playlist.videos.as_deref().unwrap_or_default());
+            },
+            PlaylistDownloadEvent::Failed(event) => {
+                let item = RunSummaryItem::builder()
+                    .url(event.url.clone())
+                    .title(state.title(event.playlist_id.as_deref()))
+                    .reason(Some(event.reason.clone()))
+                    .build();
+
+                state.failed.record(item);
+            },
+            PlaylistDownloadEvent::ProgressUpdated(_)
+            | PlaylistDownloadEvent::VideoRemoved(_)
+            | PlaylistDownloadEvent::Completed(_) => {},
+        }
+    }
+
+    fn record_channel_event(&self, event: &ChannelDownloadEvent) {
+        let mut state = self.state();
+
+        match event {
+            ChannelDownloadEvent::Started(event) => {
+                if let Some(title) = event.channel.metadata.title.clone() {
+                    state.titles_by_ids.insert(event.channel.id.clone(), title);
+                }
+
+                state.skip_downloaded_videos(event.channel.videos.as_deref().unwrap_or_default());
+            },
+            ChannelDownloadEvent::Failed(event) => {
+                let item = RunSummaryItem::builder()
+                    .url(event.url.clone())
+                    .title(state.title(event.channel_id.as_deref()))
+                    .reason(Some(event.reason.clone()))
+                    .build();
+
+                state.failed.record(item);
+            },
+            ChannelDownloadEvent::ProgressUpdated(_) | ChannelDownloadEvent::Completed(_) => {},
+        }
+    }
+
+    fn summarize(&self, elapsed: ::std::time::Duration) -> RunSummary {
+        let state = self.state();
+
+        RunSummary::builder()
+            .succeeded(state.succeeded.items.clone().into())
+            .failed(state.failed.items.clone().into())
+            .skipped(state.skipped.items.clone().into())
+            .retried(state.retried.items.clone().into())
+            .total_bytes(state.total_bytes)
+            .elapsed(elapsed)
+            .build()
+    }
+
+    fn state(&self) -> ::std::sync::MutexGuard<'_, RunSummaryRecorderState> {
+        self.state.lock().unwrap_or_else(::std::sync::PoisonError::into_inner)
+    }
+}
+
+impl RunSummaryRecorderState {
+    fn title(&self, id: Option<&str>) -> Option<MaybeOwnedString> {
+        id.and_then(|id| self.titles_by_ids.get(id)).cloned()
+    }
+
+    fn skip_downloaded_videos(&mut self, videos: &[UnresolvedVideo]) {
+        for video in videos {
+            let Some(downloaded_video) = self.downloaded_videos_by_ids.get(&video.id) else {
+                continue;
+            };
+
+            let item = RunSummaryItem::builder()
+                .url(downloaded_video.url.clone())
+                .title(downloaded_video.metadata.title.clone())
+                .reason(Some(RunSummaryRecorder::ALREADY_DOWNLOADED.into()))
+                .build();
+
+            self.skipped.record(item);
+        }
+    }
+}
+
+impl RunSummaryItems {
+    fn record(&mut self, item: RunSummaryItem) {
+        match self.positions_by_urls.get(&item.url) {
+            Some(&position) => self.items[position] = item,
+            None => {
+                self.positions_by_urls.insert(item.url.clone(), self.items.len());
+                self.items.push(item);
+            },
+        }
+    }
+}
+
 struct WithPreprocessors;
+
+struct DryRun;
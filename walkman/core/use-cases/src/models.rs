@@ -2,16 +2,24 @@ pub mod events {
     use crate::models::descriptors::PartiallyResolvedChannel;
     use crate::models::descriptors::PartiallyResolvedPlaylist;
     use crate::models::descriptors::PartiallyResolvedVideo;
+    use crate::models::descriptors::PlannedChannel;
+    use crate::models::descriptors::PlannedPlaylist;
+    use crate::models::descriptors::PlannedVideo;
     use crate::models::descriptors::ResolvedChannel;
     use crate::models::descriptors::ResolvedPlaylist;
     use crate::models::descriptors::ResolvedVideo;
+    use crate::models::descriptors::RunSummary;
+    use crate::models::descriptors::Subscription;
+    use crate::utils::aliases::MaybeOwnedPath;
     use crate::utils::aliases::MaybeOwnedString;
 
     #[derive(Debug, Clone)]
     pub enum VideoDownloadEvent {
         Started(VideoDownloadStartedEvent),
         ProgressUpdated(VideoDownloadProgressUpdatedEvent),
+        RetryScheduled(VideoDownloadRetryScheduledEvent),
         Completed(VideoDownloadCompletedEvent),
+        Failed(VideoDownloadFailedEvent),
     }
 
     #[derive(Debug, Clone)]
@@ -36,17 +44,48 @@ pub mod events {
         pub percentage: u64,
     }
 
+    #[derive(Debug, Clone)]
+    #[derive(::bon::Builder)]
+    pub struct VideoDownloadRetryScheduledEvent {
+        pub url: MaybeOwnedString,
+
+        #[builder(required)]
+        pub video_id: Option<MaybeOwnedString>,
+
+        pub attempt: u64,
+        pub max_attempts: u64,
+        pub delay: ::std::time::Duration,
+
+        pub reason: MaybeOwnedString,
+    }
+
     #[derive(Debug, Clone)]
     #[derive(::bon::Builder)]
     pub struct VideoDownloadCompletedEvent {
         pub video: ResolvedVideo,
     }
 
+    #[derive(Debug, Clone)]
+    #[derive(::bon::Builder)]
+    pub struct VideoDownloadFailedEvent {
+        pub url: MaybeOwnedString,
+
+        #[builder(required)]
+        pub video_id: Option<MaybeOwnedString>,
+
+        pub reason: MaybeOwnedString,
+
+        #[builder(required)]
+        pub exit_status: Option<i32>,
+    }
+
     #[derive(Debug, Clone)]
     pub enum PlaylistDownloadEvent {
         Started(PlaylistDownloadStartedEvent),
         ProgressUpdated(PlaylistDownloadProgressUpdatedEvent),
+        VideoRemoved(PlaylistDownloadVideoRemovedEvent),
         Completed(PlaylistDownloadCompletedEvent),
+        Failed(PlaylistDownloadFailedEvent),
     }
 
     #[derive(Debug, Clone)]
@@ -61,20 +100,45 @@ pub mod events {
         pub playlist_id: MaybeOwnedString,
 
         pub completed_videos: u64,
+        pub failed_videos: u64,
+        pub skipped_videos: u64,
         pub total_videos: u64,
     }
 
+    #[derive(Debug, Clone)]
+    #[derive(::bon::Builder)]
+    pub struct PlaylistDownloadVideoRemovedEvent {
+        pub playlist_id: MaybeOwnedString,
+
+        pub video: ResolvedVideo,
+    }
+
     #[derive(Debug, Clone)]
     #[derive(::bon::Builder)]
     pub struct PlaylistDownloadCompletedEvent {
         pub playlist: ResolvedPlaylist,
     }
 
+    #[derive(Debug, Clone)]
+    #[derive(::bon::Builder)]
+    pub struct PlaylistDownloadFailedEvent {
+        pub url: MaybeOwnedString,
+
+        #[builder(required)]
+        pub playlist_id: Option<MaybeOwnedString>,
+
+        pub reason: MaybeOwnedString,
+
+        #[builder(required)]
+        pub exit_status: Option<i32>,
+    }
+
     #[derive(Debug, Clone)]
     pub enum ChannelDownloadEvent {
         Started(ChannelDownloadStartedEvent),
         ProgressUpdated(ChannelDownloadProgressUpdatedEvent),
         Completed(ChannelDownloadCompletedEvent),
+        Failed(ChannelDownloadFailedEvent),
     }
 
     #[derive(Debug, Clone)]
@@ -89,9 +153,12 @@ pub mod events {
         pub channel_id: MaybeOwnedString,
 
         pub completed_videos: u64,
+        pub failed_videos: u64,
+        pub skipped_videos: u64,
         pub total_videos: u64,
 
         pub completed_playlists: u64,
+        pub failed_playlists: u64,
         pub total_playlists: u64,
     }
 
@@ -101,6 +168,122 @@ pub mod events {
         pub channel: ResolvedChannel,
     }
 
+    #[derive(Debug, Clone)]
+    #[derive(::bon::Builder)]
+    pub struct ChannelDownloadFailedEvent {
+        pub url: MaybeOwnedString,
+
+        #[builder(required)]
+        pub channel_id: Option<MaybeOwnedString>,
+
+        pub reason: MaybeOwnedString,
+
+        #[builder(required)]
+        pub exit_status: Option<i32>,
+    }
+
+    #[derive(Debug, Clone)]
+    pub enum DownloadPlanEvent {
+        VideoPlanned(DownloadPlanVideoPlannedEvent),
+        PlaylistPlanned(DownloadPlanPlaylistPlannedEvent),
+        ChannelPlanned(DownloadPlanChannelPlannedEvent),
+    }
+
+    #[derive(Debug, Clone)]
+    #[derive(::bon::Builder)]
+    pub struct DownloadPlanVideoPlannedEvent {
+        pub video: PlannedVideo,
+    }
+
+    #[derive(Debug, Clone)]
+    #[derive(::bon::Builder)]
+    pub struct DownloadPlanPlaylistPlannedEvent {
+        pub playlist: PlannedPlaylist,
+    }
+
+    #[derive(Debug, Clone)]
+    #[derive(::bon::Builder)]
+    pub struct DownloadPlanChannelPlannedEvent {
+        pub channel: PlannedChannel,
+    }
+
+    #[derive(Debug, Clone)]
+    pub enum SubscriptionRemovalEvent {
+        VideoRemoved(SubscriptionRemovalVideoRemovedEvent),
+        Completed(SubscriptionRemovalCompletedEvent),
+    }
+
+    #[derive(Debug, Clone)]
+    #[derive(::bon::Builder)]
+    pub struct SubscriptionRemovalVideoRemovedEvent {
+        pub video: ResolvedVideo,
+    }
+
+    #[derive(Debug, Clone)]
+    #[derive(::bon::Builder)]
+    pub struct SubscriptionRemovalCompletedEvent {
+        pub url: MaybeOwnedString,
+    }
+
+    #[derive(Debug, Clone)]
+    pub enum SubscriptionListingEvent {
+        SubscriptionListed(SubscriptionListingSubscriptionListedEvent),
+        Completed(SubscriptionListingCompletedEvent),
+    }
+
+    #[derive(Debug, Clone)]
+    #[derive(::bon::Builder)]
+    pub struct SubscriptionListingSubscriptionListedEvent {
+        pub subscription: Subscription,
+    }
+
+    #[derive(Debug, Clone)]
+    #[derive(::bon::Builder)]
+    pub struct SubscriptionListingCompletedEvent {
+        pub total_subscriptions: u64,
+    }
+
+    #[derive(Debug, Clone)]
+    pub enum SubscriptionExportEvent {
+        Completed(SubscriptionExportCompletedEvent),
+    }
+
+    #[derive(Debug, Clone)]
+    #[derive(::bon::Builder)]
+    pub struct SubscriptionExportCompletedEvent {
+        pub path: MaybeOwnedPath,
+
+        pub total_video_urls: u64,
+        pub total_playlist_urls: u64,
+        pub total_channel_urls: u64,
+        pub total_feed_urls: u64,
+    }
+
+    #[derive(Debug, Clone)]
+    pub enum SubscriptionImportEvent {
+        Completed(SubscriptionImportCompletedEvent),
+    }
+
+    #[derive(Debug, Clone)]
+    #[derive(::bon::Builder)]
+    pub struct SubscriptionImportCompletedEvent {
+        pub path: MaybeOwnedPath,
+
+        pub imported_urls: u64,
+        pub skipped_urls: u64,
+    }
+
+    #[derive(Debug, Clone)]
+    pub enum RunSummaryEvent {
+        Completed(RunSummaryCompletedEvent),
+    }
+
+    #[derive(Debug, Clone)]
+    #[derive(::bon::Builder)]
+    pub struct RunSummaryCompletedEvent {
+        pub summary: RunSummary,
+    }
+
     #[derive(Debug, Clone)]
     #[derive(::bon::Builder)]
     pub struct DiagnosticEvent {
@@ -125,6 +308,9 @@ pub mod descriptors {
     pub struct UnresolvedVideo {
         pub id: MaybeOwnedString,
         pub url: MaybeOwnedString,
+
+        // Where the video was listed in its playlist or channel, counted from one
+        pub index: Option<u64>,
     }
 
     #[derive(Debug, Clone)]
@@ -136,7 +322,7 @@ pub mod descriptors {
         pub metadata: VideoMetadata,
     }
 
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq)]
     #[derive(::bon::Builder)]
     pub struct ResolvedVideo {
         pub id: MaybeOwnedString,
@@ -145,6 +331,10 @@ pub mod descriptors {
         pub metadata: VideoMetadata,
 
         pub path: MaybeOwnedPath,
+
+        // Where the video was listed in its playlist or channel, counted from one,
+        // which is only known while it is being downloaded
+        pub index: Option<u64>,
     }
 
     impl From<::domain::Video> for ResolvedVideo {
@@ -154,12 +344,60 @@ pub mod descriptors {
                 url: this.url.into(),
                 metadata: this.metadata.into(),
                 path: this.path.into(),
+                index: None,
+            }
+        }
+    }
+
+    impl From<ResolvedVideo> for ::domain::Video {
+        fn from(this: ResolvedVideo) -> Self {
+            Self {
+                id: this.id.into(),
+                url: this.url.into(),
+                metadata: this.metadata.into(),
+                path: this.path.into(),
+                updated_at: None,
             }
         }
     }
 
     #[derive(Debug, Clone)]
     #[derive(::bon::Builder)]
+    pub struct PlannedVideo {
+        pub id: MaybeOwnedString,
+        pub url: MaybeOwnedString,
+
+        pub metadata: VideoMetadata,
+
+        #[builder(required)]
+        pub path: Option<MaybeOwnedPath>,
+
+        #[builder(required)]
+        pub duration: Option<::std::time::Duration>,
+
+        #[builder(required)]
+        pub estimated_bytes: Option<u64>,
+
+        #[builder(default)]
+        pub skipped: bool,
+    }
+
+    impl From<ResolvedVideo> for PlannedVideo {
+        fn from(this: ResolvedVideo) -> Self {
+            Self {
+                id: this.id,
+                url: this.url,
+                metadata: this.metadata,
+                path: Some(this.path),
+                duration: None,
+                estimated_bytes: None,
+                skipped: true,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[derive(::bon::Builder)]
     pub struct VideoMetadata {
         #[builder(required)]
         pub title: Option<MaybeOwnedString>,
@@ -185,6 +423,17 @@ pub mod descriptors {
         }
     }
 
+    impl From<VideoMetadata> for ::domain::VideoMetadata {
+        fn from(this: VideoMetadata) -> Self {
+            Self {
+                title: this.title,
+                album: this.album,
+                artists: this.artists,
+                genres: this.genres,
+            }
+        }
+    }
+
     #[derive(Debug, Clone)]
     #[derive(::bon::Builder)]
     pub struct UnresolvedPlaylist {
@@ -214,6 +463,10 @@ pub mod descriptors {
 
         #[builder(required)]
         pub videos: Option<MaybeOwnedVec<ResolvedVideo>>,
+
+        // Every video that was listed, downloaded or not, which is only known while
+        // the playlist is being downloaded
+        pub listed_videos: Option<u64>,
     }
 
     impl From<::domain::Playlist> for ResolvedPlaylist {
@@ -225,19 +478,56 @@ pub mod descriptors {
                 videos: this
                     .videos
                     .map(|videos| videos.iter().cloned().map(Into::into).collect::<Vec<_>>().into()),
+                listed_videos: None,
             }
         }
     }
 
+    impl From<ResolvedPlaylist> for ::domain::Playlist {
+        fn from(this: ResolvedPlaylist) -> Self {
+            Self {
+                id: this.id.into(),
+                url: this.url.into(),
+                metadata: this.metadata.into(),
+                videos: this
+                    .videos
+                    .map(|videos| videos.iter().cloned().map(Into::into).collect::<Vec<_>>().into()),
+                updated_at: None,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    #[derive(::bon::Builder)]
+    pub struct PlannedPlaylist {
+        pub id: MaybeOwnedString,
+        pub url: MaybeOwnedString,
+
+        pub metadata: PlaylistMetadata,
+
+        #[builder(required)]
+        pub videos: Option<MaybeOwnedVec<PlannedVideo>>,
+    }
+
     #[derive(Debug, Clone)]
     #[derive(::bon::Builder)]
     pub struct PlaylistMetadata {
         #[builder(required)]
         pub title: Option<MaybeOwnedString>,
+
+        // Only known while the playlist is being downloaded, so that it is never
+        // stored in the library
+        pub thumbnail_url: Option<MaybeOwnedString>,
     }
 
     impl From<::domain::PlaylistMetadata> for PlaylistMetadata {
         fn from(this: ::domain::PlaylistMetadata) -> Self {
+            Self { title: this.title, thumbnail_url: None }
+        }
+    }
+
+    impl From<PlaylistMetadata> for ::domain::PlaylistMetadata {
+        fn from(this: PlaylistMetadata) -> Self {
             Self { title: this.title }
         }
     }
@@ -277,6 +567,10 @@ pub mod descriptors {
 
         #[builder(required)]
         pub playlists: Option<MaybeOwnedVec<ResolvedPlaylist>>,
+
+        // Every upload that was listed, downloaded or not, which is only known while
+        // the channel is being downloaded
+        pub listed_videos: Option<u64>,
     }
 
     impl From<::domain::Channel> for ResolvedChannel {
@@ -291,10 +585,43 @@ pub mod descriptors {
                 playlists: this
                     .playlists
                     .map(|playlists| playlists.iter().cloned().map(Into::into).collect::<Vec<_>>().into()),
+                listed_videos: None,
+            }
+        }
+    }
+
+    impl From<ResolvedChannel> for ::domain::Channel {
+        fn from(this: ResolvedChannel) -> Self {
+            Self {
+                id: this.id.into(),
+                url: this.url.into(),
+                metadata: this.metadata.into(),
+                videos: this
+                    .videos
+                    .map(|videos| videos.iter().cloned().map(Into::into).collect::<Vec<_>>().into()),
+                playlists: this
+                    .playlists
+                    .map(|playlists| playlists.iter().cloned().map(Into::into).collect::<Vec<_>>().into()),
+                updated_at: None,
             }
         }
     }
 
+    #[derive(Debug, Clone)]
+    #[derive(::bon::Builder)]
+    pub struct PlannedChannel {
+        pub id: MaybeOwnedString,
+        pub url: MaybeOwnedString,
+
+        pub metadata: ChannelMetadata,
+
+        #[builder(required)]
+        pub videos: Option<MaybeOwnedVec<PlannedVideo>>,
+
+        #[builder(required)]
+        pub playlists: Option<MaybeOwnedVec<PlannedPlaylist>>,
+    }
+
     #[derive(Debug, Clone)]
     #[derive(::bon::Builder)]
     pub struct ChannelMetadata {
@@ -307,4 +634,73 @@ pub mod descriptors {
             Self { title: this.title }
         }
     }
+
+    impl From<ChannelMetadata> for ::domain::ChannelMetadata {
+        fn from(this: ChannelMetadata) -> Self {
+            Self { title: this.title }
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    #[derive(::bon::Builder)]
+    pub struct Subscription {
+        pub kind: SubscriptionKind,
+        pub url: MaybeOwnedString,
+
+        #[builder(required)]
+        pub title: Option<MaybeOwnedString>,
+
+        #[builder(required)]
+        pub updated_at: Option<::std::time::SystemTime>,
+
+        pub local_videos: u64,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SubscriptionKind {
+        Video,
+        Playlist,
+        Channel,
+        Feed,
+    }
+
+    #[derive(Debug, Clone, Default)]
+    #[derive(::bon::Builder)]
+    #[builder(on(_, into))]
+    pub struct SubscriptionUrls {
+        pub video_urls: MaybeOwnedVec<MaybeOwnedString>,
+        pub playlist_urls: MaybeOwnedVec<MaybeOwnedString>,
+        pub channel_urls: MaybeOwnedVec<MaybeOwnedString>,
+        pub feed_urls: MaybeOwnedVec<MaybeOwnedString>,
+    }
+
+    #[derive(Debug, Clone)]
+    #[derive(::bon::Builder)]
+    pub struct RunSummary {
+        pub succeeded: MaybeOwnedVec<RunSummaryItem>,
+        pub failed: MaybeOwnedVec<RunSummaryItem>,
+        pub skipped: MaybeOwnedVec<RunSummaryItem>,
+        pub retried: MaybeOwnedVec<RunSummaryItem>,
+
+        pub total_bytes: u64,
+        pub elapsed: ::std::time::Duration,
+    }
+
+    impl RunSummary {
+        pub fn is_successful(&self) -> bool {
+            self.failed.is_empty()
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    #[derive(::bon::Builder)]
+    pub struct RunSummaryItem {
+        pub url: MaybeOwnedString,
+
+        #[builder(required)]
+        pub title: Option<MaybeOwnedString>,
+
+        #[builder(required)]
+        pub reason: Option<MaybeOwnedString>,
+    }
 }
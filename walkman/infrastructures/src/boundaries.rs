@@ -1,20 +1,47 @@
 use ::async_trait::async_trait;
 use ::futures::prelude::*;
+use ::std::ops::Not;
 use ::use_cases::boundaries::Activate;
 use ::use_cases::boundaries::Update;
+use ::use_cases::models::descriptors::PlannedPlaylist;
+use ::use_cases::models::descriptors::PlannedVideo;
+use ::use_cases::models::descriptors::RunSummaryItem;
+use ::use_cases::models::descriptors::Subscription;
+use ::use_cases::models::descriptors::SubscriptionKind;
 use ::use_cases::models::events::ChannelDownloadCompletedEvent;
 use ::use_cases::models::events::ChannelDownloadEvent;
+use ::use_cases::models::events::ChannelDownloadFailedEvent;
 use ::use_cases::models::events::ChannelDownloadProgressUpdatedEvent;
 use ::use_cases::models::events::ChannelDownloadStartedEvent;
 use ::use_cases::models::events::DiagnosticEvent;
 use ::use_cases::models::events::DiagnosticLevel;
+use ::use_cases::models::events::DownloadPlanChannelPlannedEvent;
+use ::use_cases::models::events::DownloadPlanEvent;
+use ::use_cases::models::events::DownloadPlanPlaylistPlannedEvent;
+use ::use_cases::models::events::DownloadPlanVideoPlannedEvent;
 use ::use_cases::models::events::PlaylistDownloadCompletedEvent;
 use ::use_cases::models::events::PlaylistDownloadEvent;
+use ::use_cases::models::events::PlaylistDownloadFailedEvent;
 use ::use_cases::models::events::PlaylistDownloadProgressUpdatedEvent;
 use ::use_cases::models::events::PlaylistDownloadStartedEvent;
+use ::use_cases::models::events::PlaylistDownloadVideoRemovedEvent;
+use ::use_cases::models::events::RunSummaryCompletedEvent;
+use ::use_cases::models::events::RunSummaryEvent;
+use ::use_cases::models::events::SubscriptionExportCompletedEvent;
+use ::use_cases::models::events::SubscriptionExportEvent;
+use ::use_cases::models::events::SubscriptionImportCompletedEvent;
+use ::use_cases::models::events::SubscriptionImportEvent;
+use ::use_cases::models::events::SubscriptionListingCompletedEvent;
+use ::use_cases::models::events::SubscriptionListingEvent;
+use ::use_cases::models::events::SubscriptionListingSubscriptionListedEvent;
+use ::use_cases::models::events::SubscriptionRemovalCompletedEvent;
+use ::use_cases::models::events::SubscriptionRemovalEvent;
+use ::use_cases::models::events::SubscriptionRemovalVideoRemovedEvent;
 use ::use_cases::models::events::VideoDownloadCompletedEvent;
 use ::use_cases::models::events::VideoDownloadEvent;
+use ::use_cases::models::events::VideoDownloadFailedEvent;
 use ::use_cases::models::events::VideoDownloadProgressUpdatedEvent;
+use ::use_cases::models::events::VideoDownloadRetryScheduledEvent;
 use ::use_cases::models::events::VideoDownloadStartedEvent;
 
 use crate::utils::aliases::Fallible;
@@ -25,6 +52,9 @@ use crate::utils::extensions::OptionExt;
 #[derive(::bon::Builder)]
 #[builder(on(_, into))]
 pub struct AggregateView {
+    #[builder(default)]
+    cancellation: ::tokio_util::sync::CancellationToken,
+
     #[builder(skip)]
     progress_bars: ::indicatif::MultiProgress,
 
@@ -50,6 +80,23 @@ pub struct AggregateView {
     #[builder(skip)]
     channel_ids_by_playlist_ids:
         ::std::sync::Arc<::tokio::sync::Mutex<::std::collections::HashMap<MaybeOwnedString, MaybeOwnedString>>>,
+
+    #[builder(skip)]
+    failed: ::std::sync::atomic::AtomicBool,
+}
+
+impl AggregateView {
+    // A cancelled run stopped short of its pending work, so that it exits the way
+    // an interrupted process conventionally does
+    pub fn exit_code(&self) -> ::std::process::ExitCode {
+        if self.cancellation.is_cancelled() {
+            ::std::process::ExitCode::from(130)
+        } else if self.failed.load(::std::sync::atomic::Ordering::SeqCst) {
+            ::std::process::ExitCode::FAILURE
+        } else {
+            ::std::process::ExitCode::SUCCESS
+        }
+    }
 }
 
 #[async_trait]
@@ -83,6 +130,47 @@ impl Activate for AggregateView {
     async fn deactivate(self: ::std::sync::Arc<Self>) -> Fallible<()> {
         ::tracing::debug!("Deactivated `AggregateView`");
 
+        if self.cancellation.is_cancelled() {
+            use ::colored::Colorize as _;
+
+            let video_progress_bars = self.video_progress_bars_by_ids.lock().await;
+            let (completed_video_progress_bars, cancelled_video_progress_bars): (Vec<_>, Vec<_>) = video_progress_bars
+                .values()
+                .partition(|progress_bar| progress_bar.is_finished());
+
+            cancelled_video_progress_bars
+                .iter()
+                .for_each(|progress_bar| progress_bar.abandon());
+
+            self.playlist_progress_bars_by_ids
+                .lock()
+                .await
+                .values()
+                .filter(|progress_bar| progress_bar.is_finished().not())
+                .for_each(|progress_bar| progress_bar.abandon());
+
+            self.channel_progress_bars_by_ids
+                .lock()
+                .await
+                .values()
+                .filter(|progress_bar| progress_bar.is_finished().not())
+                .for_each(|progress_bar| progress_bar.abandon());
+
+            let decoy_progress_bar = self.progress_bars.add(::indicatif::ProgressBar::no_length());
+
+            decoy_progress_bar.set_style(::indicatif::ProgressStyle::with_template("{msg}")?);
+            decoy_progress_bar.finish_with_message(format!(
+                "{} {}",
+                "[cancelled]".red(),
+                format!(
+                    "({} videos completed, {} videos interrupted)",
+                    completed_video_progress_bars.len(),
+                    cancelled_video_progress_bars.len()
+                )
+                .gray(),
+            ));
+        }
+
         self.progress_bars.set_draw_target(::indicatif::ProgressDrawTarget::hidden());
 
         Ok(())
@@ -95,7 +183,9 @@ impl Update<VideoDownloadEvent> for AggregateView {
         match event {
             VideoDownloadEvent::Started(event) => self.update(event).await,
             VideoDownloadEvent::ProgressUpdated(event) => self.update(event).await,
+            VideoDownloadEvent::RetryScheduled(event) => self.update(event).await,
             VideoDownloadEvent::Completed(event) => self.update(event).await,
+            VideoDownloadEvent::Failed(event) => self.update(event).await,
         }
     }
 }
@@ -148,6 +238,35 @@ impl Update<VideoDownloadProgressUpdatedEvent> for AggregateView {
     }
 }
 
+#[async_trait]
+impl Update<VideoDownloadRetryScheduledEvent> for AggregateView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &VideoDownloadRetryScheduledEvent) -> Fallible<()> {
+        ::tracing::debug!("Received (OB) event `{:?}`", event);
+
+        let video_progress_bars = self.video_progress_bars_by_ids.lock().await;
+        let video_progress_bar = event.video_id.as_ref().and_then(|video_id| video_progress_bars.get(video_id));
+
+        match video_progress_bar {
+            Some(video_progress_bar) => ::std::sync::Arc::clone(video_progress_bar).update(event).await?,
+            None => {
+                use ::colored::Colorize as _;
+
+                let decoy_progress_bar = self.progress_bars.add(::indicatif::ProgressBar::no_length());
+
+                decoy_progress_bar.set_style(::indicatif::ProgressStyle::with_template("{msg}")?);
+                decoy_progress_bar.finish_with_message(format!(
+                    "{} {} {}",
+                    format!("[retry {}/{}]", event.attempt, event.max_attempts).yellow(),
+                    event.url.bold(),
+                    format!("({})", event.reason).gray(),
+                ));
+            },
+        }
+
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl Update<VideoDownloadCompletedEvent> for AggregateView {
     async fn update(self: ::std::sync::Arc<Self>, event: &VideoDownloadCompletedEvent) -> Fallible<()> {
@@ -162,13 +281,44 @@ impl Update<VideoDownloadCompletedEvent> for AggregateView {
     }
 }
 
+#[async_trait]
+impl Update<VideoDownloadFailedEvent> for AggregateView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &VideoDownloadFailedEvent) -> Fallible<()> {
+        ::tracing::debug!("Received (OB) event `{:?}`", event);
+
+        let video_progress_bars = self.video_progress_bars_by_ids.lock().await;
+        let video_progress_bar = event.video_id.as_ref().and_then(|video_id| video_progress_bars.get(video_id));
+
+        match video_progress_bar {
+            Some(video_progress_bar) => ::std::sync::Arc::clone(video_progress_bar).update(event).await?,
+            None => {
+                use ::colored::Colorize as _;
+
+                let decoy_progress_bar = self.progress_bars.add(::indicatif::ProgressBar::no_length());
+
+                decoy_progress_bar.set_style(::indicatif::ProgressStyle::with_template("{msg}")?);
+                decoy_progress_bar.finish_with_message(format!(
+                    "{} {} {}",
+                    "[failed]".red(),
+                    event.url.bold(),
+                    format!("({})", FormattedFailureReason(event.reason.as_ref(), event.exit_status)).gray(),
+                ));
+            },
+        }
+
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl Update<PlaylistDownloadEvent> for AggregateView {
     async fn update(self: ::std::sync::Arc<Self>, event: &PlaylistDownloadEvent) -> Fallible<()> {
         match event {
             PlaylistDownloadEvent::Started(event) => self.update(event).await,
             PlaylistDownloadEvent::ProgressUpdated(event) => self.update(event).await,
+            PlaylistDownloadEvent::VideoRemoved(event) => self.update(event).await,
             PlaylistDownloadEvent::Completed(event) => self.update(event).await,
+            PlaylistDownloadEvent::Failed(event) => self.update(event).await,
         }
     }
 }
@@ -225,6 +375,35 @@ impl Update<PlaylistDownloadProgressUpdatedEvent> for AggregateView {
     }
 }
 
+#[async_trait]
+impl Update<PlaylistDownloadVideoRemovedEvent> for AggregateView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &PlaylistDownloadVideoRemovedEvent) -> Fallible<()> {
+        ::tracing::debug!("Received (OB) event `{:?}`", event);
+
+        use ::colored::Colorize as _;
+
+        let title = event
+            .video
+            .metadata
+            .title
+            .as_deref()
+            .map(|title| title.normal())
+            .unwrap_or_else(|| "N/A".normal());
+
+        let playlist_progress_bars = self.playlist_progress_bars_by_ids.lock().await;
+        let playlist_progress_bar = playlist_progress_bars.get(&event.playlist_id).ok()?;
+
+        let decoy_progress_bar = self
+            .progress_bars
+            .insert_after(playlist_progress_bar, ::indicatif::ProgressBar::no_length());
+
+        decoy_progress_bar.set_style(::indicatif::ProgressStyle::with_template("{msg}")?);
+        decoy_progress_bar.finish_with_message(format!("{} {}", "[removed]".yellow(), title.gray()));
+
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl Update<PlaylistDownloadCompletedEvent> for AggregateView {
     async fn update(self: ::std::sync::Arc<Self>, event: &PlaylistDownloadCompletedEvent) -> Fallible<()> {
@@ -239,6 +418,38 @@ impl Update<PlaylistDownloadCompletedEvent> for AggregateView {
     }
 }
 
+#[async_trait]
+impl Update<PlaylistDownloadFailedEvent> for AggregateView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &PlaylistDownloadFailedEvent) -> Fallible<()> {
+        ::tracing::debug!("Received (OB) event `{:?}`", event);
+
+        let playlist_progress_bars = self.playlist_progress_bars_by_ids.lock().await;
+        let playlist_progress_bar = event
+            .playlist_id
+            .as_ref()
+            .and_then(|playlist_id| playlist_progress_bars.get(playlist_id));
+
+        match playlist_progress_bar {
+            Some(playlist_progress_bar) => ::std::sync::Arc::clone(playlist_progress_bar).update(event).await?,
+            None => {
+                use ::colored::Colorize as _;
+
+                let decoy_progress_bar = self.progress_bars.add(::indicatif::ProgressBar::no_length());
+
+                decoy_progress_bar.set_style(::indicatif::ProgressStyle::with_template("{msg}")?);
+                decoy_progress_bar.finish_with_message(format!(
+                    "{} {} {}",
+                    "[failed]".red(),
+                    event.url.bold(),
+                    format!("({})", FormattedFailureReason(event.reason.as_ref(), event.exit_status)).gray(),
+                ));
+            },
+        }
+
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl Update<ChannelDownloadEvent> for AggregateView {
     async fn update(self: ::std::sync::Arc<Self>, event: &ChannelDownloadEvent) -> Fallible<()> {
@@ -246,6 +457,7 @@ impl Update<ChannelDownloadEvent> for AggregateView {
             ChannelDownloadEvent::Started(event) => self.update(event).await,
             ChannelDownloadEvent::ProgressUpdated(event) => self.update(event).await,
             ChannelDownloadEvent::Completed(event) => self.update(event).await,
+            ChannelDownloadEvent::Failed(event) => self.update(event).await,
         }
     }
 }
@@ -313,6 +525,343 @@ impl Update<ChannelDownloadCompletedEvent> for AggregateView {
     }
 }
 
+#[async_trait]
+impl Update<ChannelDownloadFailedEvent> for AggregateView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &ChannelDownloadFailedEvent) -> Fallible<()> {
+        ::tracing::debug!("Received (OB) event `{:?}`", event);
+
+        let channel_progress_bars = self.channel_progress_bars_by_ids.lock().await;
+        let channel_progress_bar = event
+            .channel_id
+            .as_ref()
+            .and_then(|channel_id| channel_progress_bars.get(channel_id));
+
+        match channel_progress_bar {
+            Some(channel_progress_bar) => ::std::sync::Arc::clone(channel_progress_bar).update(event).await?,
+            None => {
+                use ::colored::Colorize as _;
+
+                let decoy_progress_bar = self.progress_bars.add(::indicatif::ProgressBar::no_length());
+
+                decoy_progress_bar.set_style(::indicatif::ProgressStyle::with_template("{msg}")?);
+                decoy_progress_bar.finish_with_message(format!(
+                    "{} {} {}",
+                    "[failed]".red(),
+                    event.url.bold(),
+                    format!("({})", FormattedFailureReason(event.reason.as_ref(), event.exit_status)).gray(),
+                ));
+            },
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Update<DownloadPlanEvent> for AggregateView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &DownloadPlanEvent) -> Fallible<()> {
+        match event {
+            DownloadPlanEvent::VideoPlanned(event) => self.update(event).await,
+            DownloadPlanEvent::PlaylistPlanned(event) => self.update(event).await,
+            DownloadPlanEvent::ChannelPlanned(event) => self.update(event).await,
+        }
+    }
+}
+
+#[async_trait]
+impl Update<DownloadPlanVideoPlannedEvent> for AggregateView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &DownloadPlanVideoPlannedEvent) -> Fallible<()> {
+        ::tracing::debug!("Received (OB) event `{:?}`", event);
+
+        let decoy_progress_bar = self.progress_bars.add(::indicatif::ProgressBar::no_length());
+
+        decoy_progress_bar.set_style(::indicatif::ProgressStyle::with_template("{msg}")?);
+        decoy_progress_bar.finish_with_message(format!("{}", FormattedPlannedVideo(&event.video)));
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Update<DownloadPlanPlaylistPlannedEvent> for AggregateView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &DownloadPlanPlaylistPlannedEvent) -> Fallible<()> {
+        ::tracing::debug!("Received (OB) event `{:?}`", event);
+
+        let decoy_progress_bar = self.progress_bars.add(::indicatif::ProgressBar::no_length());
+
+        decoy_progress_bar.set_style(::indicatif::ProgressStyle::with_template("{msg}")?);
+        decoy_progress_bar.finish_with_message(format!("{}", FormattedPlannedPlaylist(&event.playlist, 0)));
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Update<DownloadPlanChannelPlannedEvent> for AggregateView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &DownloadPlanChannelPlannedEvent) -> Fallible<()> {
+        ::tracing::debug!("Received (OB) event `{:?}`", event);
+
+        use ::colored::Colorize as _;
+
+        let channel = &event.channel;
+
+        let title = channel
+            .metadata
+            .title
+            .as_deref()
+            .map(|title| title.bold())
+            .unwrap_or_else(|| "N/A".bold());
+
+        let videos = channel
+            .videos
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .chain(
+                channel
+                    .playlists
+                    .as_deref()
+                    .unwrap_or_default()
+                    .iter()
+                    .flat_map(|playlist| playlist.videos.as_deref().unwrap_or_default()),
+            )
+            .collect::<Vec<_>>();
+
+        let mut lines =
+            vec![format!("{} {} {}", "[plan]".cyan(), title, format!("({})", FormattedPlanSummary(&videos)).gray(),)];
+
+        lines.extend(
+            channel
+                .videos
+                .as_deref()
+                .unwrap_or_default()
+                .iter()
+                .map(|video| format!("  {}", FormattedPlannedVideo(video))),
+        );
+        lines.extend(
+            channel
+                .playlists
+                .as_deref()
+                .unwrap_or_default()
+                .iter()
+                .map(|playlist| format!("{}", FormattedPlannedPlaylist(playlist, 1))),
+        );
+
+        let decoy_progress_bar = self.progress_bars.add(::indicatif::ProgressBar::no_length());
+
+        decoy_progress_bar.set_style(::indicatif::ProgressStyle::with_template("{msg}")?);
+        decoy_progress_bar.finish_with_message(lines.join("\n"));
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Update<SubscriptionRemovalEvent> for AggregateView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &SubscriptionRemovalEvent) -> Fallible<()> {
+        match event {
+            SubscriptionRemovalEvent::VideoRemoved(event) => self.update(event).await,
+            SubscriptionRemovalEvent::Completed(event) => self.update(event).await,
+        }
+    }
+}
+
+#[async_trait]
+impl Update<SubscriptionRemovalVideoRemovedEvent> for AggregateView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &SubscriptionRemovalVideoRemovedEvent) -> Fallible<()> {
+        ::tracing::debug!("Received (OB) event `{:?}`", event);
+
+        use ::colored::Colorize as _;
+
+        let title = event
+            .video
+            .metadata
+            .title
+            .as_deref()
+            .map(|title| title.normal())
+            .unwrap_or_else(|| "N/A".normal());
+
+        let decoy_progress_bar = self.progress_bars.add(::indicatif::ProgressBar::no_length());
+
+        decoy_progress_bar.set_style(::indicatif::ProgressStyle::with_template("{msg}")?);
+        decoy_progress_bar.finish_with_message(format!("{} {}", "[removed]".yellow(), title.gray()));
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Update<SubscriptionRemovalCompletedEvent> for AggregateView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &SubscriptionRemovalCompletedEvent) -> Fallible<()> {
+        ::tracing::debug!("Received (OB) event `{:?}`", event);
+
+        use ::colored::Colorize as _;
+
+        let decoy_progress_bar = self.progress_bars.add(::indicatif::ProgressBar::no_length());
+
+        decoy_progress_bar.set_style(::indicatif::ProgressStyle::with_template("{msg}")?);
+        decoy_progress_bar.finish_with_message(format!("{} {}", "[unsubscribed]".green(), event.url.bold()));
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Update<SubscriptionExportEvent> for AggregateView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &SubscriptionExportEvent) -> Fallible<()> {
+        match event {
+            SubscriptionExportEvent::Completed(event) => self.update(event).await,
+        }
+    }
+}
+
+#[async_trait]
+impl Update<SubscriptionExportCompletedEvent> for AggregateView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &SubscriptionExportCompletedEvent) -> Fallible<()> {
+        ::tracing::debug!("Received (OB) event `{:?}`", event);
+
+        use ::colored::Colorize as _;
+
+        let decoy_progress_bar = self.progress_bars.add(::indicatif::ProgressBar::no_length());
+
+        decoy_progress_bar.set_style(::indicatif::ProgressStyle::with_template("{msg}")?);
+        decoy_progress_bar.finish_with_message(format!(
+            "{} {} {}",
+            "[exported]".green(),
+            event.path.display().to_string().bold(),
+            format!(
+                "({} videos, {} playlists, {} channels, {} feeds)",
+                event.total_video_urls, event.total_playlist_urls, event.total_channel_urls, event.total_feed_urls
+            )
+            .gray(),
+        ));
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Update<SubscriptionImportEvent> for AggregateView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &SubscriptionImportEvent) -> Fallible<()> {
+        match event {
+            SubscriptionImportEvent::Completed(event) => self.update(event).await,
+        }
+    }
+}
+
+#[async_trait]
+impl Update<SubscriptionImportCompletedEvent> for AggregateView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &SubscriptionImportCompletedEvent) -> Fallible<()> {
+        ::tracing::debug!("Received (OB) event `{:?}`", event);
+
+        use ::colored::Colorize as _;
+
+        let decoy_progress_bar = self.progress_bars.add(::indicatif::ProgressBar::no_length());
+
+        decoy_progress_bar.set_style(::indicatif::ProgressStyle::with_template("{msg}")?);
+        decoy_progress_bar.finish_with_message(format!(
+            "{} {} {}",
+            "[imported]".green(),
+            event.path.display().to_string().bold(),
+            format!("({} new, {} already subscribed)", event.imported_urls, event.skipped_urls).gray(),
+        ));
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Update<RunSummaryEvent> for AggregateView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &RunSummaryEvent) -> Fallible<()> {
+        match event {
+            RunSummaryEvent::Completed(event) => self.update(event).await,
+        }
+    }
+}
+
+#[async_trait]
+impl Update<RunSummaryCompletedEvent> for AggregateView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &RunSummaryCompletedEvent) -> Fallible<()> {
+        ::tracing::debug!("Received (OB) event `{:?}`", event);
+
+        use ::colored::Colorize as _;
+
+        let summary = &event.summary;
+
+        if summary.is_successful().not() {
+            self.failed.store(true, ::std::sync::atomic::Ordering::SeqCst);
+        }
+
+        let status = if summary.is_successful() {
+            "[summary]".green()
+        } else {
+            "[summary]".red()
+        };
+
+        let mut messages = vec![format!(
+            "{} {} {}",
+            status,
+            format!(
+                "{} succeeded, {} failed, {} skipped, {} retried",
+                summary.succeeded.len(),
+                summary.failed.len(),
+                summary.skipped.len(),
+                summary.retried.len()
+            )
+            .bold(),
+            format!("({} in {})", FormattedBytes(summary.total_bytes), FormattedTotalDuration(summary.elapsed)).gray(),
+        )];
+
+        messages.extend(
+            summary
+                .succeeded
+                .iter()
+                .map(|item| format!("  {} {}", "[succeeded]".green(), FormattedRunSummaryItem(item))),
+        );
+        messages.extend(
+            summary
+                .skipped
+                .iter()
+                .map(|item| format!("  {} {}", "[skipped]".gray(), FormattedRunSummaryItem(item))),
+        );
+        messages.extend(
+            summary
+                .failed
+                .iter()
+                .map(|item| format!("  {} {}", "[failed]".red(), FormattedRunSummaryItem(item))),
+        );
+        messages.extend(
+            summary
+                .retried
+                .iter()
+                .map(|item| format!("  {} {}", "[retried]".yellow(), FormattedRunSummaryItem(item))),
+        );
+
+        // Progress bars are never drawn when stderr is no terminal, as under cron, so
+        // that the summary is written out plainly there instead
+        if ::std::io::IsTerminal::is_terminal(&::std::io::stderr()).not() {
+            use ::std::io::Write as _;
+
+            let mut stderr = ::std::io::stderr().lock();
+
+            for message in messages {
+                writeln!(stderr, "{}", message)?;
+            }
+
+            return Ok(());
+        }
+
+        for message in messages {
+            let decoy_progress_bar = self.progress_bars.add(::indicatif::ProgressBar::no_length());
+
+            decoy_progress_bar.set_style(::indicatif::ProgressStyle::with_template("{msg}")?);
+            decoy_progress_bar.finish_with_message(message);
+        }
+
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl Update<DiagnosticEvent> for AggregateView {
     async fn update(self: ::std::sync::Arc<Self>, event: &DiagnosticEvent) -> Fallible<()> {
@@ -336,6 +885,129 @@ impl Update<DiagnosticEvent> for AggregateView {
     }
 }
 
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct SubscriptionListView {
+    format: SubscriptionListFormat,
+
+    #[builder(skip)]
+    subscriptions: ::tokio::sync::Mutex<Vec<Subscription>>,
+}
+
+pub enum SubscriptionListFormat {
+    Table,
+    Json,
+}
+
+#[async_trait]
+impl Activate for SubscriptionListView {
+    async fn activate(self: ::std::sync::Arc<Self>) -> Fallible<()> {
+        ::tracing::debug!("Activated `SubscriptionListView`");
+
+        Ok(())
+    }
+
+    async fn deactivate(self: ::std::sync::Arc<Self>) -> Fallible<()> {
+        ::tracing::debug!("Deactivated `SubscriptionListView`");
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Update<SubscriptionListingEvent> for SubscriptionListView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &SubscriptionListingEvent) -> Fallible<()> {
+        match event {
+            SubscriptionListingEvent::SubscriptionListed(event) => self.update(event).await,
+            SubscriptionListingEvent::Completed(event) => self.update(event).await,
+        }
+    }
+}
+
+#[async_trait]
+impl Update<SubscriptionListingSubscriptionListedEvent> for SubscriptionListView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &SubscriptionListingSubscriptionListedEvent) -> Fallible<()> {
+        ::tracing::debug!("Received (OB) event `{:?}`", event);
+
+        self.subscriptions.lock().await.push(event.subscription.clone());
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Update<SubscriptionListingCompletedEvent> for SubscriptionListView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &SubscriptionListingCompletedEvent) -> Fallible<()> {
+        ::tracing::debug!("Received (OB) event `{:?}`", event);
+
+        let subscriptions = ::std::mem::take(&mut *self.subscriptions.lock().await);
+
+        let output = match self.format {
+            SubscriptionListFormat::Table => FormattedSubscriptionsTable(&subscriptions).to_string(),
+            SubscriptionListFormat::Json => {
+                let rows = subscriptions
+                    .iter()
+                    .map(SubscriptionRow::try_from)
+                    .collect::<Fallible<Vec<_>>>()?;
+
+                ::serde_json::to_string_pretty(&rows)?
+            },
+        };
+
+        println!("{}", output);
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Update<DiagnosticEvent> for SubscriptionListView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &DiagnosticEvent) -> Fallible<()> {
+        ::tracing::debug!("Received (OB) event `{:?}`", event);
+
+        use ::colored::Colorize as _;
+
+        let DiagnosticEvent { message, level } = event;
+
+        let message = match level {
+            DiagnosticLevel::Warning => message.yellow(),
+            DiagnosticLevel::Error => message.red(),
+        };
+
+        eprintln!("{}", message);
+
+        Ok(())
+    }
+}
+
+#[derive(::serde::Serialize)]
+struct SubscriptionRow<'a> {
+    kind: &'static str,
+    url: &'a str,
+    title: Option<&'a str>,
+    updated_at: Option<::std::string::String>,
+    local_videos: u64,
+}
+
+impl<'a> TryFrom<&'a Subscription> for SubscriptionRow<'a> {
+    type Error = ::anyhow::Error;
+
+    fn try_from(subscription: &'a Subscription) -> Fallible<Self> {
+        Ok(Self {
+            kind: FormattedSubscriptionKind(subscription.kind).as_str(),
+            url: &subscription.url,
+            title: subscription.title.as_deref(),
+            updated_at: subscription
+                .updated_at
+                .map(|updated_at| {
+                    ::time::OffsetDateTime::from(updated_at).format(&::time::format_description::well_known::Rfc3339)
+                })
+                .transpose()?,
+            local_videos: subscription.local_videos,
+        })
+    }
+}
+
 struct VideoProgressBar(::indicatif::ProgressBar);
 
 impl Default for VideoProgressBar {
@@ -412,6 +1084,17 @@ impl Update<VideoDownloadProgressUpdatedEvent> for VideoProgressBar {
     }
 }
 
+#[async_trait]
+impl Update<VideoDownloadRetryScheduledEvent> for VideoProgressBar {
+    async fn update(self: ::std::sync::Arc<Self>, event: &VideoDownloadRetryScheduledEvent) -> Fallible<()> {
+        use ::colored::Colorize as _;
+
+        self.set_prefix(format!("[retry {}/{}]", event.attempt, event.max_attempts).yellow().to_string());
+
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl Update<VideoDownloadCompletedEvent> for VideoProgressBar {
     async fn update(self: ::std::sync::Arc<Self>, event: &VideoDownloadCompletedEvent) -> Fallible<()> {
@@ -445,6 +1128,28 @@ impl Update<VideoDownloadCompletedEvent> for VideoProgressBar {
     }
 }
 
+#[async_trait]
+impl Update<VideoDownloadFailedEvent> for VideoProgressBar {
+    async fn update(self: ::std::sync::Arc<Self>, event: &VideoDownloadFailedEvent) -> Fallible<()> {
+        use ::colored::Colorize as _;
+
+        let message = self.message();
+        let title = message.rfind("] ").map(|idx| &message[idx + 2..]).ok()?;
+
+        self.set_style(::indicatif::ProgressStyle::with_template("{prefix} {bar:50} {msg}")?.progress_chars("#>-"));
+        self.set_prefix("[failed]".red().to_string());
+        self.set_message(format!(
+            "{} {}",
+            title,
+            format!("({})", FormattedFailureReason(event.reason.as_ref(), event.exit_status)).gray()
+        ));
+
+        self.abandon();
+
+        Ok(())
+    }
+}
+
 struct PlaylistProgressBar(::indicatif::ProgressBar);
 
 impl Default for PlaylistProgressBar {
@@ -506,10 +1211,17 @@ impl Update<PlaylistDownloadProgressUpdatedEvent> for PlaylistProgressBar {
         let message = self.message();
         let title = message.rfind("] ").map(|idx| &message[idx + 2..]).ok()?;
 
+        let videos = FormattedProgressCount {
+            completed: event.completed_videos,
+            failed: event.failed_videos,
+            skipped: event.skipped_videos,
+            total: event.total_videos,
+        };
+
         self.set_length(event.total_videos);
-        self.set_position(event.completed_videos);
+        self.set_position(event.completed_videos + event.failed_videos + event.skipped_videos);
 
-        self.set_message(format!("[{}/{}] {}", event.completed_videos, event.total_videos, title));
+        self.set_message(format!("[{}] {}", videos, title));
 
         Ok(())
     }
@@ -546,6 +1258,27 @@ impl Update<PlaylistDownloadCompletedEvent> for PlaylistProgressBar {
     }
 }
 
+#[async_trait]
+impl Update<PlaylistDownloadFailedEvent> for PlaylistProgressBar {
+    async fn update(self: ::std::sync::Arc<Self>, event: &PlaylistDownloadFailedEvent) -> Fallible<()> {
+        use ::colored::Colorize as _;
+
+        let message = self.message();
+        let title = message.rfind("] ").map(|idx| &message[idx + 2..]).ok()?;
+
+        self.set_message(format!(
+            "{} {} {}",
+            "[failed]".red(),
+            title,
+            format!("({})", FormattedFailureReason(event.reason.as_ref(), event.exit_status)).gray()
+        ));
+
+        self.abandon();
+
+        Ok(())
+    }
+}
+
 struct ChannelProgressBar(::indicatif::ProgressBar);
 
 impl Default for ChannelProgressBar {
@@ -613,13 +1346,29 @@ impl Update<ChannelDownloadProgressUpdatedEvent> for ChannelProgressBar {
         let message = self.message();
         let title = message.rfind("] ").map(|idx| &message[idx + 2..]).ok()?;
 
+        let videos = FormattedProgressCount {
+            completed: event.completed_videos,
+            failed: event.failed_videos,
+            skipped: event.skipped_videos,
+            total: event.total_videos,
+        };
+        let playlists = FormattedProgressCount {
+            completed: event.completed_playlists,
+            failed: event.failed_playlists,
+            skipped: 0,
+            total: event.total_playlists,
+        };
+
         self.set_length(event.total_videos + event.total_playlists);
-        self.set_position(event.completed_videos + event.completed_playlists);
+        self.set_position(
+            event.completed_videos
+                + event.failed_videos
+                + event.skipped_videos
+                + event.completed_playlists
+                + event.failed_playlists,
+        );
 
-        self.set_message(format!(
-            "[{}/{} | {}/{}] {}",
-            event.completed_videos, event.total_videos, event.completed_playlists, event.total_playlists, title
-        ));
+        self.set_message(format!("[{} | {}] {}", videos, playlists, title));
 
         Ok(())
     }
@@ -656,6 +1405,27 @@ impl Update<ChannelDownloadCompletedEvent> for ChannelProgressBar {
     }
 }
 
+#[async_trait]
+impl Update<ChannelDownloadFailedEvent> for ChannelProgressBar {
+    async fn update(self: ::std::sync::Arc<Self>, event: &ChannelDownloadFailedEvent) -> Fallible<()> {
+        use ::colored::Colorize as _;
+
+        let message = self.message();
+        let title = message.rfind("] ").map(|idx| &message[idx + 2..]).ok()?;
+
+        self.set_message(format!(
+            "{} {} {}",
+            "[failed]".red(),
+            title,
+            format!("({})", FormattedFailureReason(event.reason.as_ref(), event.exit_status)).gray()
+        ));
+
+        self.abandon();
+
+        Ok(())
+    }
+}
+
 trait ColorizeExt {
     fn gray(self) -> ::colored::ColoredString
     where
@@ -709,6 +1479,20 @@ impl ::std::fmt::Display for FormattedDuration {
     }
 }
 
+struct FormattedTotalDuration(::std::time::Duration);
+
+impl ::std::fmt::Display for FormattedTotalDuration {
+    fn fmt(&self, formatter: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        let duration = ::time::Duration::try_from(self.0).unwrap();
+
+        let hours = duration.whole_hours();
+        let minutes = duration.whole_minutes() % 60;
+        let seconds = duration.whole_seconds() % 60;
+
+        write!(formatter, "{:02}:{:02}:{:02}", hours, minutes, seconds)
+    }
+}
+
 struct FormattedUninitDuration;
 
 impl ::std::fmt::Display for FormattedUninitDuration {
@@ -717,6 +1501,59 @@ impl ::std::fmt::Display for FormattedUninitDuration {
     }
 }
 
+struct FormattedProgressCount {
+    completed: u64,
+    failed: u64,
+    skipped: u64,
+    total: u64,
+}
+
+impl ::std::fmt::Display for FormattedProgressCount {
+    fn fmt(&self, formatter: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        let annotations = [(self.skipped, "skipped"), (self.failed, "failed")]
+            .into_iter()
+            .filter(|(count, _)| *count > 0)
+            .map(|(count, annotation)| format!("{} {}", count, annotation))
+            .collect::<Vec<_>>();
+
+        write!(formatter, "{}/{}", self.completed + self.failed + self.skipped, self.total)?;
+
+        match annotations.is_empty() {
+            true => Ok(()),
+            false => write!(formatter, " ({})", annotations.join(", ")),
+        }
+    }
+}
+
+struct FormattedFailureReason<'a>(&'a str, Option<i32>);
+
+impl ::std::fmt::Display for FormattedFailureReason<'_> {
+    fn fmt(&self, formatter: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        match self.1 {
+            Some(exit_status) => write!(formatter, "{}, exit status {}", self.0, exit_status),
+            None => write!(formatter, "{}", self.0),
+        }
+    }
+}
+
+struct FormattedRunSummaryItem<'a>(&'a RunSummaryItem);
+
+impl ::std::fmt::Display for FormattedRunSummaryItem<'_> {
+    fn fmt(&self, formatter: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        use ::colored::Colorize as _;
+
+        match self.0.title.as_deref() {
+            Some(title) => write!(formatter, "{} {}", title.bold(), self.0.url.gray())?,
+            None => write!(formatter, "{}", self.0.url.bold())?,
+        }
+
+        match self.0.reason.as_deref() {
+            Some(reason) => write!(formatter, " {}", format!("({})", reason).gray()),
+            None => Ok(()),
+        }
+    }
+}
+
 struct FormattedBytes(u64);
 
 impl ::std::fmt::Display for FormattedBytes {
@@ -748,3 +1585,183 @@ impl ::std::fmt::Display for FormattedUninitBytesPerSecond {
         write!(formatter, "{}/s", FormattedUninitBytes)
     }
 }
+
+struct FormattedPlannedVideo<'a>(&'a PlannedVideo);
+
+impl ::std::fmt::Display for FormattedPlannedVideo<'_> {
+    fn fmt(&self, formatter: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        use ::colored::Colorize as _;
+
+        let video = self.0;
+
+        let title = video.metadata.title.as_deref().unwrap_or("N/A");
+        let path = video
+            .path
+            .as_deref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| "N/A".to_owned());
+
+        if video.skipped {
+            return write!(formatter, "{} {} {}", "[skip]".yellow(), title.gray(), format!("({})", path).gray());
+        }
+
+        let estimated_bytes = video
+            .estimated_bytes
+            .map(|estimated_bytes| FormattedBytes(estimated_bytes).to_string())
+            .unwrap_or_else(|| FormattedUninitBytes.to_string());
+        let duration = video
+            .duration
+            .map(|duration| FormattedTotalDuration(duration).to_string())
+            .unwrap_or_else(|| FormattedUninitDuration.to_string());
+
+        write!(
+            formatter,
+            "{} {} {} {} {}",
+            "[download]".green(),
+            title,
+            "->".gray(),
+            path,
+            format!("({}, {})", estimated_bytes, duration).gray(),
+        )
+    }
+}
+
+struct FormattedPlannedPlaylist<'a>(&'a PlannedPlaylist, usize);
+
+impl ::std::fmt::Display for FormattedPlannedPlaylist<'_> {
+    fn fmt(&self, formatter: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        use ::colored::Colorize as _;
+
+        let (playlist, indent) = (self.0, "  ".repeat(self.1));
+
+        let title = playlist
+            .metadata
+            .title
+            .as_deref()
+            .map(|title| title.bold())
+            .unwrap_or_else(|| "N/A".bold());
+        let videos = playlist.videos.as_deref().unwrap_or_default().iter().collect::<Vec<_>>();
+
+        write!(
+            formatter,
+            "{}{} {} {}",
+            indent,
+            "[plan]".cyan(),
+            title,
+            format!("({})", FormattedPlanSummary(&videos)).gray(),
+        )?;
+
+        for video in videos {
+            write!(formatter, "\n{}  {}", indent, FormattedPlannedVideo(video))?;
+        }
+
+        Ok(())
+    }
+}
+
+struct FormattedPlanSummary<'a>(&'a [&'a PlannedVideo]);
+
+impl ::std::fmt::Display for FormattedPlanSummary<'_> {
+    fn fmt(&self, formatter: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        let (skipped_videos, pending_videos): (Vec<&PlannedVideo>, Vec<&PlannedVideo>) =
+            self.0.iter().copied().partition(|video| video.skipped);
+
+        let estimated_bytes = pending_videos.iter().filter_map(|video| video.estimated_bytes).sum::<u64>();
+        let duration = pending_videos
+            .iter()
+            .filter_map(|video| video.duration)
+            .sum::<::std::time::Duration>();
+
+        write!(
+            formatter,
+            "{} to download, {} to skip, {}, {}",
+            pending_videos.len(),
+            skipped_videos.len(),
+            FormattedBytes(estimated_bytes),
+            FormattedTotalDuration(duration),
+        )
+    }
+}
+
+struct FormattedSubscriptionsTable<'a>(&'a [Subscription]);
+
+impl ::std::fmt::Display for FormattedSubscriptionsTable<'_> {
+    fn fmt(&self, formatter: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        use ::colored::Colorize as _;
+
+        let header = ["KIND", "TITLE", "LAST UPDATED", "LOCAL VIDEOS", "URL"].map(::std::string::String::from);
+        let rows = self
+            .0
+            .iter()
+            .map(|subscription| {
+                [
+                    FormattedSubscriptionKind(subscription.kind).as_str().to_owned(),
+                    subscription.title.as_deref().unwrap_or("N/A").to_owned(),
+                    subscription
+                        .updated_at
+                        .map(|updated_at| FormattedTimestamp(updated_at).to_string())
+                        .unwrap_or_else(|| "never".to_owned()),
+                    subscription.local_videos.to_string(),
+                    subscription.url.to_string(),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        let widths = ::std::iter::once(&header).chain(&rows).fold([0; 5], |mut widths, row| {
+            widths
+                .iter_mut()
+                .zip(row)
+                .for_each(|(width, cell)| *width = (*width).max(cell.chars().count()));
+            widths
+        });
+
+        let format_row = |row: &[::std::string::String; 5]| {
+            row.iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_owned()
+        };
+
+        write!(formatter, "{}", format_row(&header).bold())?;
+
+        for row in &rows {
+            write!(formatter, "\n{}", format_row(row))?;
+        }
+
+        Ok(())
+    }
+}
+
+struct FormattedSubscriptionKind(SubscriptionKind);
+
+impl FormattedSubscriptionKind {
+    fn as_str(&self) -> &'static str {
+        match self.0 {
+            SubscriptionKind::Video => "video",
+            SubscriptionKind::Playlist => "playlist",
+            SubscriptionKind::Channel => "channel",
+            SubscriptionKind::Feed => "feed",
+        }
+    }
+}
+
+struct FormattedTimestamp(::std::time::SystemTime);
+
+impl ::std::fmt::Display for FormattedTimestamp {
+    fn fmt(&self, formatter: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        let timestamp = ::time::OffsetDateTime::from(self.0);
+
+        write!(
+            formatter,
+            "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+            timestamp.year(),
+            timestamp.month() as u8,
+            timestamp.day(),
+            timestamp.hour(),
+            timestamp.minute()
+        )
+    }
+}
@@ -0,0 +1,102 @@
+use ::async_trait::async_trait;
+use ::use_cases::gateways::Discarder;
+use ::use_cases::models::descriptors::ResolvedVideo;
+
+use crate::utils::aliases::Fallible;
+use crate::utils::aliases::MaybeOwnedPath;
+use crate::utils::extensions::OptionExt;
+
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct FilesystemDiscarder {
+    trash_directory: Option<MaybeOwnedPath>,
+}
+
+#[async_trait]
+impl Discarder<ResolvedVideo> for FilesystemDiscarder {
+    async fn discard(self: ::std::sync::Arc<Self>, video: &ResolvedVideo) -> Fallible<()> {
+        let result = match self.trash_directory.as_deref() {
+            Some(trash_directory) => {
+                ::tokio::fs::create_dir_all(trash_directory).await?;
+
+                let trashed_path = TrashPathReserver::reserve(trash_directory, &video.path).await?;
+                TrashMover::move_file(&video.path, &trashed_path).await
+            },
+            // Videos split into tracks are recorded as the folder holding them, so that
+            // the tracks go along with it
+            None => match video.path.is_dir() {
+                true => ::tokio::fs::remove_dir_all(&video.path).await,
+                false => ::tokio::fs::remove_file(&video.path).await,
+            },
+        };
+
+        match result {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == ::std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+struct TrashPathReserver;
+
+impl TrashPathReserver {
+    // Files of the same name are numbered, so that an earlier one in the trash is
+    // never overwritten
+    async fn reserve(trash_directory: &::std::path::Path, path: &::std::path::Path) -> Fallible<::std::path::PathBuf> {
+        let stem = path.file_stem().ok()?.to_string_lossy();
+        let extension = path.extension().map(|extension| extension.to_string_lossy());
+
+        for attempt in 1_u64.. {
+            let file_name = match (attempt, extension.as_deref()) {
+                (1, _) => path.file_name().ok()?.to_string_lossy().into_owned(),
+                (_, Some(extension)) => format!("{}_{}.{}", stem, attempt, extension),
+                (_, None) => format!("{}_{}", stem, attempt),
+            };
+
+            let trashed_path = trash_directory.join(file_name);
+
+            if ::tokio::fs::try_exists(&trashed_path).await? {
+                continue;
+            }
+
+            return Ok(trashed_path);
+        }
+
+        unreachable!()
+    }
+}
+
+struct TrashMover;
+
+impl TrashMover {
+    // Renaming only works within a filesystem, so that a trash directory on another
+    // one gets a copy instead
+    async fn move_file(path: &::std::path::Path, trashed_path: &::std::path::Path) -> ::std::io::Result<()> {
+        match ::tokio::fs::rename(path, trashed_path).await {
+            Err(err) if err.kind() == ::std::io::ErrorKind::CrossesDevices && path.is_dir() => {
+                Self::copy_folder(path, trashed_path).await?;
+                ::tokio::fs::remove_dir_all(path).await
+            },
+            Err(err) if err.kind() == ::std::io::ErrorKind::CrossesDevices => {
+                ::tokio::fs::copy(path, trashed_path).await?;
+                ::tokio::fs::remove_file(path).await
+            },
+            result => result,
+        }
+    }
+
+    // Folders of split tracks hold nothing but files, so that they are copied
+    // without descending any further
+    async fn copy_folder(path: &::std::path::Path, trashed_path: &::std::path::Path) -> ::std::io::Result<()> {
+        ::tokio::fs::create_dir_all(trashed_path).await?;
+
+        let mut entries = ::tokio::fs::read_dir(path).await?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            ::tokio::fs::copy(entry.path(), trashed_path.join(entry.file_name())).await?;
+        }
+
+        Ok(())
+    }
+}
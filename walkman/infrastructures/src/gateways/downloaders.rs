@@ -1,16 +1,25 @@
 use ::async_trait::async_trait;
 use ::domain::ChannelUrl;
+use ::domain::FeedUrl;
 use ::domain::PlaylistUrl;
 use ::domain::VideoUrl;
 use ::futures::prelude::*;
 use ::std::ops::Not;
 use ::use_cases::gateways::ChannelDownloader;
+use ::use_cases::gateways::ChannelResolver;
+use ::use_cases::gateways::FeedDownloader;
+use ::use_cases::gateways::FeedResolver;
 use ::use_cases::gateways::PlaylistDownloader;
+use ::use_cases::gateways::PlaylistResolver;
 use ::use_cases::gateways::VideoDownloader;
+use ::use_cases::gateways::VideoResolver;
 use ::use_cases::models::descriptors::ChannelMetadata;
 use ::use_cases::models::descriptors::PartiallyResolvedChannel;
 use ::use_cases::models::descriptors::PartiallyResolvedPlaylist;
 use ::use_cases::models::descriptors::PartiallyResolvedVideo;
+use ::use_cases::models::descriptors::PlannedChannel;
+use ::use_cases::models::descriptors::PlannedPlaylist;
+use ::use_cases::models::descriptors::PlannedVideo;
 use ::use_cases::models::descriptors::PlaylistMetadata;
 use ::use_cases::models::descriptors::ResolvedChannel;
 use ::use_cases::models::descriptors::ResolvedPlaylist;
@@ -20,19 +29,26 @@ use ::use_cases::models::descriptors::UnresolvedVideo;
 use ::use_cases::models::descriptors::VideoMetadata;
 use ::use_cases::models::events::ChannelDownloadCompletedEvent;
 use ::use_cases::models::events::ChannelDownloadEvent;
+use ::use_cases::models::events::ChannelDownloadFailedEvent;
 use ::use_cases::models::events::ChannelDownloadProgressUpdatedEvent;
 use ::use_cases::models::events::ChannelDownloadStartedEvent;
 use ::use_cases::models::events::DiagnosticEvent;
 use ::use_cases::models::events::DiagnosticLevel;
 use ::use_cases::models::events::PlaylistDownloadCompletedEvent;
 use ::use_cases::models::events::PlaylistDownloadEvent;
+use ::use_cases::models::events::PlaylistDownloadFailedEvent;
 use ::use_cases::models::events::PlaylistDownloadProgressUpdatedEvent;
 use ::use_cases::models::events::PlaylistDownloadStartedEvent;
 use ::use_cases::models::events::VideoDownloadCompletedEvent;
 use ::use_cases::models::events::VideoDownloadEvent;
+use ::use_cases::models::events::VideoDownloadFailedEvent;
 use ::use_cases::models::events::VideoDownloadProgressUpdatedEvent;
+use ::use_cases::models::events::VideoDownloadRetryScheduledEvent;
 use ::use_cases::models::events::VideoDownloadStartedEvent;
 
+use crate::gateways::executors::CommandExecutor;
+use crate::gateways::postprocessors::CommandRunner;
+use crate::gateways::profiles::DeviceProfile;
 use crate::utils::aliases::BoxedStream;
 use crate::utils::aliases::Fallible;
 use crate::utils::aliases::MaybeOwnedPath;
@@ -45,13 +61,247 @@ use crate::utils::extensions::OptionExt;
 pub struct YtdlpDownloader {
     directory: MaybeOwnedPath,
 
-    #[allow(dead_code)]
+    profile: DeviceProfile,
+
     workers: u64,
 
-    per_worker_cooldown: ::std::time::Duration,
+    resolvers: u64,
+
+    #[builder(default)]
+    rate_limiter: ::std::sync::Arc<RateLimiter>,
+
+    #[builder(default)]
+    retry_policy: RetryPolicy,
+
+    #[builder(default)]
+    cancellation: ::tokio_util::sync::CancellationToken,
+
+    executor: ::std::sync::Arc<dyn CommandExecutor>,
+
+    // Bounds every event stream, so that a slow consumer holds the downloads back
+    // instead of letting the events pile up
+    #[builder(default = 256_usize)]
+    event_buffer: usize,
+
+    #[builder(skip = ::std::sync::Arc::new(WorkerPool::new(workers)))]
+    download_pool: ::std::sync::Arc<WorkerPool>,
+
+    #[builder(skip = ::std::sync::Arc::new(WorkerPool::new(resolvers)))]
+    resolution_pool: ::std::sync::Arc<WorkerPool>,
+}
+
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct RateLimiter {
+    #[builder(required)]
+    requests_per_minute: Option<u64>,
+
+    #[builder(required)]
+    bytes_per_second: Option<u64>,
+
+    #[builder(default = 1_u64)]
+    burst: u64,
+
+    #[builder(skip = ::tokio::sync::Mutex::new(TokenBucket { tokens: burst as f64, refilled_at: ::tokio::time::Instant::now() }))]
+    bucket: ::tokio::sync::Mutex<TokenBucket>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::builder().requests_per_minute(None).bytes_per_second(None).build()
+    }
+}
+
+impl RateLimiter {
+    pub(crate) async fn acquire(&self, requests: u64) {
+        let Some(requests_per_minute) = self.requests_per_minute else {
+            return;
+        };
+
+        let tokens_per_second = requests_per_minute as f64 / 60.0;
+
+        // Tokens are reserved upfront and may go into debt, so that waiters are served
+        // in order
+        let delay = {
+            let mut bucket = self.bucket.lock().await;
+            let now = ::tokio::time::Instant::now();
+
+            bucket.tokens = (bucket.tokens + now.duration_since(bucket.refilled_at).as_secs_f64() * tokens_per_second)
+                .min(self.burst as f64);
+            bucket.refilled_at = now;
+            bucket.tokens -= requests as f64;
+
+            (bucket.tokens < 0.0).then(|| ::std::time::Duration::from_secs_f64(-bucket.tokens / tokens_per_second))
+        };
+
+        if let Some(delay) = delay {
+            ::tracing::debug!("Throttling request for `{:?}`", delay);
+
+            ::tokio::time::sleep(delay).await;
+        }
+    }
+
+    fn bytes_per_second(&self, workers: u64) -> Option<u64> {
+        // Every worker gets an equal share, so that the aggregate bandwidth stays
+        // within the limit
+        self.bytes_per_second
+            .map(|bytes_per_second| (bytes_per_second / workers.max(1)).max(1))
+    }
+}
+
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: ::tokio::time::Instant,
+}
+
+// Workers are only ever held by a single yt-dlp process, never while waiting
+// for nested work, so that playlists and channels cannot starve their own
+// videos
+struct WorkerPool {
+    state: ::std::sync::Mutex<WorkerPoolState>,
+}
+
+struct WorkerPoolState {
+    idle_workers: u64,
+    queues: ::std::collections::VecDeque<(
+        MaybeOwnedString,
+        ::std::collections::VecDeque<::tokio::sync::oneshot::Sender<Worker>>,
+    )>,
+}
+
+impl WorkerPool {
+    fn new(workers: u64) -> Self {
+        let state = WorkerPoolState {
+            idle_workers: workers.max(1),
+            queues: Default::default(),
+        };
+
+        Self { state: ::std::sync::Mutex::new(state) }
+    }
+
+    async fn acquire(self: &::std::sync::Arc<Self>, queue: &str) -> Fallible<Worker> {
+        let waiter = {
+            let mut state = self.state.lock().unwrap_or_else(::std::sync::PoisonError::into_inner);
+
+            if state.idle_workers > 0 {
+                state.idle_workers -= 1;
+
+                return Ok(Worker {
+                    pool: Some(::std::sync::Arc::clone(self)),
+                });
+            }
+
+            let (waiter_tx, waiter_rx) = ::tokio::sync::oneshot::channel();
+
+            match state.queues.iter_mut().find(|(name, _)| name == queue) {
+                Some((_, waiters)) => waiters.push_back(waiter_tx),
+                None => state.queues.push_back((queue.to_owned().into(), [waiter_tx].into())),
+            }
+
+            waiter_rx
+        };
+
+        Ok(waiter.await?)
+    }
+
+    fn release(self: &::std::sync::Arc<Self>) {
+        loop {
+            // Queues take turns, so that a large playlist cannot hold back the others
+            let waiter = {
+                let mut state = self.state.lock().unwrap_or_else(::std::sync::PoisonError::into_inner);
+
+                let waiter = loop {
+                    let Some((queue, mut waiters)) = state.queues.pop_front() else {
+                        break None;
+                    };
+
+                    if let Some(waiter) = waiters.pop_front() {
+                        if waiters.is_empty().not() {
+                            state.queues.push_back((queue, waiters));
+                        }
+
+                        break Some(waiter);
+                    }
+                };
+
+                match waiter {
+                    Some(waiter) => waiter,
+                    None => {
+                        state.idle_workers += 1;
+
+                        return;
+                    },
+                }
+            };
+
+            match waiter.send(Worker {
+                pool: Some(::std::sync::Arc::clone(self)),
+            }) {
+                Ok(()) => return,
+                // The waiter was cancelled in the meantime
+                Err(mut worker) => worker.pool = None,
+            }
+        }
+    }
+}
+
+struct Worker {
+    pool: Option<::std::sync::Arc<WorkerPool>>,
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        if let Some(pool) = self.pool.take() {
+            pool.release();
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct RetryPolicy {
+    #[builder(default = 3_u64)]
+    max_attempts: u64,
+
+    #[builder(default = ::std::time::Duration::from_secs(1))]
+    base_delay: ::std::time::Duration,
+
+    #[builder(default = ::std::time::Duration::from_millis(500))]
+    jitter: ::std::time::Duration,
+
+    #[builder(default = MaybeOwnedVec::Borrowed(RetryPolicy::RETRYABLE_MESSAGES))]
+    retryable_messages: MaybeOwnedVec<MaybeOwnedString>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl RetryPolicy {
+    const RETRYABLE_MESSAGES: &'static [MaybeOwnedString] = &[
+        MaybeOwnedString::Borrowed("HTTP Error 403"),
+        MaybeOwnedString::Borrowed("HTTP Error 429"),
+        MaybeOwnedString::Borrowed("HTTP Error 5"),
+        MaybeOwnedString::Borrowed("timed out"),
+        MaybeOwnedString::Borrowed("Connection reset"),
+        MaybeOwnedString::Borrowed("Temporary failure in name resolution"),
+    ];
+
+    fn is_retryable(&self, event: &DiagnosticEvent) -> bool {
+        self.retryable_messages.iter().any(|message| event.message.contains(&**message))
+    }
 
-    #[builder(skip = ::std::sync::Arc::new(::tokio::sync::Semaphore::new(workers as usize)))]
-    worker_pool: ::std::sync::Arc<::tokio::sync::Semaphore>,
+    fn delay(&self, attempt: u64) -> ::std::time::Duration {
+        let backoff = self
+            .base_delay
+            .saturating_mul(2_u32.saturating_pow(attempt.saturating_sub(1) as u32));
+        let jitter = self.jitter.mul_f64(::fastrand::f64());
+
+        backoff.saturating_add(jitter)
+    }
 }
 
 #[async_trait]
@@ -59,57 +309,230 @@ impl VideoDownloader for YtdlpDownloader {
     async fn download(
         self: ::std::sync::Arc<Self>, url: VideoUrl,
     ) -> Fallible<(BoxedStream<VideoDownloadEvent>, BoxedStream<DiagnosticEvent>)> {
-        let (video_download_events_tx, video_download_events_rx) = ::tokio::sync::mpsc::unbounded_channel();
-        let (diagnostic_events_tx, diagnostic_events_rx) = ::tokio::sync::mpsc::unbounded_channel();
+        let queue = (*url).clone();
 
-        ::tokio::spawn(async move {
-            #[rustfmt::skip]
-            let (stdout, stderr) = TokioCommandExecutor::execute("yt-dlp", [
-                &*url,
-                "--quiet",
-                "--color", "no_color",
-                "--paths", self.directory.to_str().ok()?,
-                "--no-playlist",
-                "--format", "bestaudio",
-                "--extract-audio",
-                "--audio-format", "mp3",
-                "--output", "%(title)+U.%(ext)s",
-                "--newline",
-                "--restrict-filenames",
-                "--windows-filenames",
-                "--abort-on-error",
-                "--force-overwrites",
-                "--progress",
-                "--print", "before_dl:[video-started]%(id)s;%(original_url)s;%(title)+U;%(album)s;%(artist)s;%(genre)s",
-                "--progress-template", "[video-downloading]%(info.id)s;%(progress.eta)s;%(progress.elapsed)s;%(progress.downloaded_bytes)s;%(progress.total_bytes)s;%(progress.speed)s",
-                "--print", "after_move:[video-completed]%(id)s;%(original_url)s;%(title)+U;%(album)s;%(artist)s;%(genre)s;%(filepath)+U",
-            ])?;
+        self.download_video(url, queue)
+    }
+}
 
-            ::tokio::try_join!(
-                async {
-                    stdout
-                        .filter_map(|line| async { VideoDownloadEvent::from_line(line) })
-                        .map(Ok)
-                        .try_for_each(|event| async { video_download_events_tx.send(event) })
-                        .await
-                        .map_err(::anyhow::Error::from)
-                },
-                async {
-                    stderr
-                        .filter_map(|line| async { DiagnosticEvent::from_line(line) })
-                        .map(Ok)
-                        .try_for_each(|event| async { diagnostic_events_tx.send(event) })
-                        .await
-                        .map_err(::anyhow::Error::from)
-                },
-            )?;
+impl YtdlpDownloader {
+    fn download_video(
+        self: ::std::sync::Arc<Self>, url: VideoUrl, queue: MaybeOwnedString,
+    ) -> Fallible<(BoxedStream<VideoDownloadEvent>, BoxedStream<DiagnosticEvent>)> {
+        let (video_download_events_tx, video_download_events_rx) = ::tokio::sync::mpsc::channel(self.event_buffer);
+        let (diagnostic_events_tx, diagnostic_events_rx) = ::tokio::sync::mpsc::channel(self.event_buffer);
+
+        let on_failure = {
+            let url = url.clone();
+            let video_download_events_tx = video_download_events_tx.clone();
+            let cancellation = self.cancellation.clone();
+
+            move |reason: MaybeOwnedString| async move {
+                if cancellation.is_cancelled() {
+                    return;
+                }
+
+                let event = VideoDownloadFailedEvent::builder()
+                    .url((*url).clone())
+                    .video_id(None)
+                    .reason(reason)
+                    .exit_status(None)
+                    .build();
+
+                video_download_events_tx.send(VideoDownloadEvent::Failed(event)).await.ok();
+            }
+        };
+
+        let task = ::tokio::spawn(async move {
+            let mut attempt = 1;
+
+            let limit_rate = self
+                .rate_limiter
+                .bytes_per_second(self.workers)
+                .map(|limit_rate| limit_rate.to_string());
+
+            let audio_format = self.profile.codec.ytdlp_audio_format()?;
+            let audio_quality = self.profile.bitrate.ytdlp_audio_quality();
+            let trim_filenames = self.profile.max_file_stem_length(&self.directory).to_string();
+            // Sources rarely come below the maximum sample rate, so that resampling to
+            // it is as good as capping
+            let postprocessor_args =
+                format!("ExtractAudio:-ar {} -ac {}", self.profile.max_sample_rate, self.profile.channels);
+
+            loop {
+                // The rate is waited for before a worker is taken, so that a throttled
+                // download does not hold a worker that others could use meanwhile
+                ::tokio::select! {
+                    _ = self.rate_limiter.acquire(1) => {},
+                    _ = self.cancellation.cancelled() => break,
+                }
+
+                let worker = ::tokio::select! {
+                    worker = self.download_pool.acquire(&queue) => worker?,
+                    _ = self.cancellation.cancelled() => break,
+                };
+
+                let folder_slot = self.profile.reserve_folder_slot(&self.directory).await?;
+
+                #[rustfmt::skip]
+                let args = [
+                    &*url,
+                    "--quiet",
+                    "--color", "no_color",
+                    "--paths", self.directory.to_str().ok()?,
+                    "--no-playlist",
+                    "--format", "bestaudio",
+                    "--extract-audio",
+                    "--audio-format", audio_format,
+                    "--audio-quality", &audio_quality,
+                    "--postprocessor-args", &postprocessor_args,
+                    "--output", "%(title)+U.%(ext)s",
+                    "--trim-filenames", &trim_filenames,
+                    "--write-thumbnail",
+                    "--convert-thumbnails", "jpg",
+                    "--newline",
+                    "--restrict-filenames",
+                    "--windows-filenames",
+                    "--abort-on-error",
+                    "--force-overwrites",
+                    "--progress",
+                    "--print", "before_dl:[video-started]%(.{id,original_url,title,album,artists,genres})j",
+                    "--print", "before_dl:[video-artifact]%(_filename)j",
+                    "--progress-template", "[video-downloading]{\"id\":%(info.id)j,\"progress\":%(progress.{eta,elapsed,downloaded_bytes,total_bytes,speed})j}",
+                    "--print", "after_move:[video-completed]%(.{id,original_url,title,album,artists,genres,filepath})j",
+                ].into_iter().chain(limit_rate.as_deref().into_iter().flat_map(|limit_rate| ["--limit-rate", limit_rate])).collect::<Vec<_>>();
+
+                let (stdout, stderr, status) = self.executor.execute("yt-dlp", &args)?;
+
+                let mut video_id = None;
+                let mut partial_artifact = None;
+                let mut errors = Vec::new();
+                let mut completed = false;
+
+                ::tokio::try_join!(
+                    async {
+                        let mut stdout = stdout;
+
+                        while let Some(line) = stdout.next().await {
+                            match YtdlpPayloadParser::parse::<::std::path::PathBuf>(&line, "[video-artifact]") {
+                                Ok(Some(path)) => partial_artifact = Some(path),
+                                Ok(None) => {},
+                                Err(error) =>
+                                    diagnostic_events_tx.send(MalformedLineReporter::report(&line, error)).await?,
+                            }
+
+                            let event = match VideoDownloadEvent::from_line(&line) {
+                                Ok(Some(event)) => event,
+                                Ok(None) => continue,
+                                Err(error) => {
+                                    diagnostic_events_tx.send(MalformedLineReporter::report(&line, error)).await?;
+
+                                    continue;
+                                },
+                            };
+
+                            match &event {
+                                VideoDownloadEvent::Started(event) => video_id = Some(event.video.id.clone()),
+                                VideoDownloadEvent::Completed(_) => completed = true,
+                                _ => {},
+                            }
+
+                            VideoDownloadEventSender::send(&video_download_events_tx, event).await?;
+                        }
+
+                        Fallible::Ok(())
+                    },
+                    async {
+                        let mut stderr = stderr;
+
+                        // Errors are withheld until we know whether they are going to be retried
+                        while let Some(line) = stderr.next().await {
+                            match DiagnosticEventParser::parse(line) {
+                                Some(event @ DiagnosticEvent { level: DiagnosticLevel::Error, .. }) =>
+                                    errors.push(event),
+                                Some(event) => diagnostic_events_tx.send(event).await?,
+                                None => {},
+                            }
+                        }
+
+                        Fallible::Ok(())
+                    },
+                )?;
+
+                let status = status.await;
+
+                if completed {
+                    folder_slot.keep();
+                }
+
+                // The worker is released during the backoff, so that others can make progress
+                ::core::mem::drop(worker);
+
+                if self.cancellation.is_cancelled() {
+                    if let Some(path) = partial_artifact {
+                        PartialArtifactsRemover::remove(&path, &self.profile).await?;
+                    }
+
+                    break;
+                }
+
+                let reason = errors
+                    .iter()
+                    .find(|event| self.retry_policy.is_retryable(event))
+                    .map(|event| event.message.clone());
+
+                match reason {
+                    Some(reason) if completed.not() && attempt < self.retry_policy.max_attempts => {
+                        let delay = self.retry_policy.delay(attempt);
+                        attempt += 1;
+
+                        let event = VideoDownloadRetryScheduledEvent::builder()
+                            .url((*url).clone())
+                            .video_id(video_id)
+                            .attempt(attempt)
+                            .max_attempts(self.retry_policy.max_attempts)
+                            .delay(delay)
+                            .reason(reason)
+                            .build();
+
+                        video_download_events_tx.send(VideoDownloadEvent::RetryScheduled(event)).await?;
+
+                        ::tokio::select! {
+                            _ = ::tokio::time::sleep(delay) => {},
+                            _ = self.cancellation.cancelled() => break,
+                        }
+                    },
+                    _ => {
+                        let failure = completed.not().then(|| {
+                            VideoDownloadFailedEvent::builder()
+                                .url((*url).clone())
+                                .video_id(video_id)
+                                .reason(FailureReason::describe(&errors, status))
+                                .exit_status(status)
+                                .build()
+                        });
+
+                        for event in errors {
+                            diagnostic_events_tx.send(event).await?;
+                        }
+
+                        if let Some(event) = failure {
+                            video_download_events_tx.send(VideoDownloadEvent::Failed(event)).await?;
+                        }
+
+                        break;
+                    },
+                }
+            }
 
             Ok::<_, ::anyhow::Error>(())
         });
 
+        TaskSupervisor::supervise(task, on_failure);
+
         Ok((
-            ::std::boxed::Box::pin(::tokio_stream::wrappers::UnboundedReceiverStream::new(video_download_events_rx)),
-            ::std::boxed::Box::pin(::tokio_stream::wrappers::UnboundedReceiverStream::new(diagnostic_events_rx)),
+            ::std::boxed::Box::pin(::tokio_stream::wrappers::ReceiverStream::new(video_download_events_rx)),
+            ::std::boxed::Box::pin(::tokio_stream::wrappers::ReceiverStream::new(diagnostic_events_rx)),
         ))
     }
 }
@@ -117,140 +540,198 @@ impl VideoDownloader for YtdlpDownloader {
 #[async_trait]
 impl PlaylistDownloader for YtdlpDownloader {
     async fn download(
-        self: ::std::sync::Arc<Self>, url: PlaylistUrl,
+        self: ::std::sync::Arc<Self>, url: PlaylistUrl, downloaded_videos: MaybeOwnedVec<ResolvedVideo>,
     ) -> Fallible<(BoxedStream<VideoDownloadEvent>, BoxedStream<PlaylistDownloadEvent>, BoxedStream<DiagnosticEvent>)>
     {
-        let (video_download_events_tx, video_download_events_rx) = ::tokio::sync::mpsc::unbounded_channel();
-        let (playlist_download_events_tx, playlist_download_events_rx) = ::tokio::sync::mpsc::unbounded_channel();
-        let (diagnostic_events_tx, diagnostic_events_rx) = ::tokio::sync::mpsc::unbounded_channel();
+        let (video_download_events_tx, video_download_events_rx) = ::tokio::sync::mpsc::channel(self.event_buffer);
+        let (playlist_download_events_tx, playlist_download_events_rx) =
+            ::tokio::sync::mpsc::channel(self.event_buffer);
+        let (diagnostic_events_tx, diagnostic_events_rx) = ::tokio::sync::mpsc::channel(self.event_buffer);
+
+        let on_failure = {
+            let url = url.clone();
+            let playlist_download_events_tx = playlist_download_events_tx.clone();
+            let cancellation = self.cancellation.clone();
+
+            move |reason: MaybeOwnedString| async move {
+                if cancellation.is_cancelled() {
+                    return;
+                }
+
+                let event = PlaylistDownloadFailedEvent::builder()
+                    .url((*url).clone())
+                    .playlist_id(None)
+                    .reason(reason)
+                    .exit_status(None)
+                    .build();
+
+                playlist_download_events_tx
+                    .send(PlaylistDownloadEvent::Failed(event))
+                    .await
+                    .ok();
+            }
+        };
+
+        let task = ::tokio::spawn(async move {
+            ::tokio::select! {
+                _ = self.rate_limiter.acquire(1) => {},
+                _ = self.cancellation.cancelled() => return Ok(()),
+            }
+
+            let worker = ::tokio::select! {
+                worker = self.resolution_pool.acquire(&url) => worker?,
+                _ = self.cancellation.cancelled() => return Ok(()),
+            };
 
-        ::tokio::spawn(async move {
             #[rustfmt::skip]
-            let (stdout, stderr) = TokioCommandExecutor::execute("yt-dlp", [
+            let (stdout, stderr, status) = self.executor.execute("yt-dlp", &[
                 &*url,
                 "--quiet",
                 "--color", "no_color",
                 "--flat-playlist",
                 "--yes-playlist",
-                "--print", "playlist:[playlist-started:metadata]%(id)s;%(original_url)s;%(title)s",
-                "--print", "video:[playlist-started:video]%(id)s;%(url)s"
+                "--print", "playlist:[playlist-started:metadata]%(.{id,original_url,title,thumbnails})j",
+                "--print", "video:[playlist-started:video]%(.{id,url,playlist_index})j"
             ])?;
 
-            let (playlist, _) = ::tokio::try_join!(
+            let mut errors = Vec::new();
+
+            let (event, _) = ::tokio::try_join!(
                 async {
-                    let event = PlaylistDownloadStartedEvent::from_lines(stdout).await.ok()?;
-                    let playlist = event.playlist.clone();
+                    let (event, malformed_line_events) = PlaylistDownloadStartedEvent::from_lines(stdout).await;
 
-                    playlist_download_events_tx.send(PlaylistDownloadEvent::Started(event))?;
+                    for event in malformed_line_events {
+                        diagnostic_events_tx.send(event).await?;
+                    }
 
-                    Ok(playlist)
+                    Ok(event)
                 },
                 async {
-                    stderr
-                        .filter_map(|line| async { DiagnosticEvent::from_line(line) })
-                        .map(Ok)
-                        .try_for_each(|event| async { diagnostic_events_tx.send(event) })
-                        .await
-                        .map_err(::anyhow::Error::from)
+                    let mut stderr = stderr;
+
+                    while let Some(line) = stderr.next().await {
+                        if let Some(event) = DiagnosticEventParser::parse(line) {
+                            if let DiagnosticLevel::Error = event.level {
+                                errors.push(event.clone());
+                            }
+
+                            diagnostic_events_tx.send(event).await?;
+                        }
+                    }
+
+                    Fallible::Ok(())
                 },
             )?;
 
+            let status = status.await;
+
+            ::core::mem::drop(worker);
+
+            // A listing that exited unsuccessfully may have been cut short, so that it is
+            // only announced once complete, since mirroring takes whatever it leaves out
+            // for removed
+            let playlist = match (event, status) {
+                (Some(event), Some(0)) => {
+                    let playlist = event.playlist.clone();
+
+                    playlist_download_events_tx.send(PlaylistDownloadEvent::Started(event)).await?;
+
+                    playlist
+                },
+                (event, status) => {
+                    if self.cancellation.is_cancelled().not() {
+                        let event = PlaylistDownloadFailedEvent::builder()
+                            .url((*url).clone())
+                            .playlist_id(event.map(|event| event.playlist.id))
+                            .reason(FailureReason::describe(&errors, status))
+                            .exit_status(status)
+                            .build();
+
+                        playlist_download_events_tx.send(PlaylistDownloadEvent::Failed(event)).await?;
+                    }
+
+                    return Ok(());
+                },
+            };
+
             let playlist = PartiallyResolvedPlaylistDeduplicator::deduplicate(playlist);
 
-            ::tracing::debug!("Downloaded playlist `{:?}`", playlist);
+            ::tracing::debug!("Downloading playlist `{:?}`", playlist);
+
+            let (skipped_videos, pending_videos) = DownloadedVideosPartitioner::partition(
+                playlist.videos.as_deref().unwrap_or_default(),
+                &downloaded_videos,
+            );
 
-            let completed_videos = ::std::sync::Arc::new(::std::sync::atomic::AtomicU64::new(0));
+            let mut completed_videos = 0;
+            let mut failed_videos = 0;
+            let skipped_videos_count = skipped_videos.len() as u64;
             let total_videos = playlist.videos.as_deref().map(|videos| videos.len() as u64).unwrap_or_default();
 
-            let videos = ::std::sync::Arc::new(::tokio::sync::Mutex::new(Vec::with_capacity(total_videos as usize)));
+            let mut videos = Vec::with_capacity(total_videos as usize);
+            videos.extend(skipped_videos);
 
-            let videos_completed_notify = ::std::sync::Arc::new(::tokio::sync::Notify::new());
+            if skipped_videos_count > 0 {
+                let event = PlaylistDownloadProgressUpdatedEvent::builder()
+                    .playlist_id(playlist.id.clone())
+                    .completed_videos(0)
+                    .failed_videos(0)
+                    .skipped_videos(skipped_videos_count)
+                    .total_videos(total_videos)
+                    .build();
 
-            ::tracing::debug!(
-                "Downloaded videos `{:?}` (`{}`/`{}`)",
-                videos,
-                completed_videos.load(::std::sync::atomic::Ordering::Relaxed),
-                total_videos
-            );
+                playlist_download_events_tx
+                    .send(PlaylistDownloadEvent::ProgressUpdated(event))
+                    .await?;
+            }
 
-            playlist.videos.as_deref().into_iter().flatten().cloned().for_each(|video| {
-                ::tokio::spawn({
-                    let this = ::std::sync::Arc::clone(&self);
-
-                    let video_download_events_tx = video_download_events_tx.clone();
-                    let playlist_download_events_tx = playlist_download_events_tx.clone();
-                    let diagnostic_events_tx = diagnostic_events_tx.clone();
-
-                    let playlist_id = playlist.id.clone();
-
-                    let completed_videos = ::std::sync::Arc::clone(&completed_videos);
-                    let videos = ::std::sync::Arc::clone(&videos);
-                    let videos_completed_notify = ::std::sync::Arc::clone(&videos_completed_notify);
-
-                    async move {
-                        let worker = this.worker_pool.acquire().await?;
-
-                        let (video_download_events, diagnostic_events) =
-                            VideoDownloader::download(::std::sync::Arc::clone(&this), video.url.clone().into()).await?;
-
-                        ::tokio::try_join!(
-                            async {
-                                video_download_events
-                                    .map(Ok)
-                                    .try_for_each(|event| async {
-                                        if let VideoDownloadEvent::Completed(ref event) = event {
-                                            completed_videos.fetch_add(1, ::std::sync::atomic::Ordering::Relaxed);
-                                            videos.lock().await.push(event.video.clone());
-
-                                            let event = PlaylistDownloadProgressUpdatedEvent::builder()
-                                                .playlist_id(playlist_id.clone())
-                                                .completed_videos(
-                                                    completed_videos.load(::std::sync::atomic::Ordering::Relaxed),
-                                                )
-                                                .total_videos(total_videos)
-                                                .build();
-
-                                            playlist_download_events_tx
-                                                .send(PlaylistDownloadEvent::ProgressUpdated(event))?;
-                                        }
+            let mut video_downloads = VideoDownloadsSupervisor::builder()
+                .downloader(::std::sync::Arc::clone(&self))
+                .video_download_events_tx(video_download_events_tx.clone())
+                .diagnostic_events_tx(diagnostic_events_tx.clone())
+                .build();
 
-                                        video_download_events_tx.send(event)?;
-
-                                        Ok(())
-                                    })
-                                    .await
-                            },
-                            async {
-                                diagnostic_events
-                                    .map(Ok)
-                                    .try_for_each(|event| async { diagnostic_events_tx.send(event) })
-                                    .await
-                                    .map_err(::anyhow::Error::from)
-                            },
-                        )?;
-
-                        ::tokio::time::sleep(this.per_worker_cooldown).await;
-                        ::core::mem::drop(worker);
-
-                        ::tracing::debug!(
-                            "Downloaded videos `{:?}` (`{}`/`{}`)",
-                            videos,
-                            completed_videos.load(::std::sync::atomic::Ordering::Relaxed),
-                            total_videos
-                        );
-
-                        if completed_videos.load(::std::sync::atomic::Ordering::Relaxed) == total_videos {
-                            videos_completed_notify.notify_one();
-                        }
+            pending_videos
+                .into_iter()
+                .for_each(|video| video_downloads.spawn(video, playlist.id.clone()));
+
+            loop {
+                let outcome = ::tokio::select! {
+                    outcome = video_downloads.join_next() => outcome?,
+                    _ = self.cancellation.cancelled() => return Ok(()),
+                };
+
+                match outcome {
+                    Some(VideoDownloadOutcome::Completed(video)) => {
+                        completed_videos += 1;
+                        videos.push(video);
+                    },
+                    Some(VideoDownloadOutcome::Failed) => failed_videos += 1,
+                    None => break,
+                }
+
+                let event = PlaylistDownloadProgressUpdatedEvent::builder()
+                    .playlist_id(playlist.id.clone())
+                    .completed_videos(completed_videos)
+                    .failed_videos(failed_videos)
+                    .skipped_videos(skipped_videos_count)
+                    .total_videos(total_videos)
+                    .build();
 
-                        Fallible::Ok(())
-                    }
-                });
-            });
+                playlist_download_events_tx
+                    .send(PlaylistDownloadEvent::ProgressUpdated(event))
+                    .await?;
+
+                ::tracing::debug!(
+                    "Downloaded videos `{:?}` (`{}`/`{}`)",
+                    videos,
+                    completed_videos + failed_videos + skipped_videos_count,
+                    total_videos
+                );
+            }
 
-            videos_completed_notify.notified().await;
+            ListingOrderRestorer::restore(&mut videos, playlist.videos.as_deref().unwrap_or_default());
 
-            let videos = ::std::mem::take(&mut *videos.lock().await);
             let videos = videos.is_empty().not().then_some(videos.into());
 
             let playlist = ResolvedPlaylist::builder()
@@ -258,20 +739,25 @@ impl PlaylistDownloader for YtdlpDownloader {
                 .url(playlist.url)
                 .metadata(playlist.metadata)
                 .videos(videos)
+                .listed_videos(total_videos)
                 .build();
 
             ::tracing::debug!("Downloaded playlist `{:?}`", playlist);
 
             let event = PlaylistDownloadCompletedEvent { playlist };
-            playlist_download_events_tx.send(PlaylistDownloadEvent::Completed(event))?;
+            playlist_download_events_tx
+                .send(PlaylistDownloadEvent::Completed(event))
+                .await?;
 
             Ok::<_, ::anyhow::Error>(())
         });
 
+        TaskSupervisor::supervise(task, on_failure);
+
         Ok((
-            ::std::boxed::Box::pin(::tokio_stream::wrappers::UnboundedReceiverStream::new(video_download_events_rx)),
-            ::std::boxed::Box::pin(::tokio_stream::wrappers::UnboundedReceiverStream::new(playlist_download_events_rx)),
-            ::std::boxed::Box::pin(::tokio_stream::wrappers::UnboundedReceiverStream::new(diagnostic_events_rx)),
+            ::std::boxed::Box::pin(::tokio_stream::wrappers::ReceiverStream::new(video_download_events_rx)),
+            ::std::boxed::Box::pin(::tokio_stream::wrappers::ReceiverStream::new(playlist_download_events_rx)),
+            ::std::boxed::Box::pin(::tokio_stream::wrappers::ReceiverStream::new(diagnostic_events_rx)),
         ))
     }
 }
@@ -279,292 +765,324 @@ impl PlaylistDownloader for YtdlpDownloader {
 #[async_trait]
 impl ChannelDownloader for YtdlpDownloader {
     async fn download(
-        self: ::std::sync::Arc<Self>, url: ChannelUrl,
+        self: ::std::sync::Arc<Self>, url: ChannelUrl, downloaded_videos: MaybeOwnedVec<ResolvedVideo>,
     ) -> Fallible<(
         BoxedStream<VideoDownloadEvent>,
         BoxedStream<PlaylistDownloadEvent>,
         BoxedStream<ChannelDownloadEvent>,
         BoxedStream<DiagnosticEvent>,
     )> {
-        let (video_download_events_tx, video_download_events_rx) = ::tokio::sync::mpsc::unbounded_channel();
-        let (playlist_download_events_tx, playlist_download_events_rx) = ::tokio::sync::mpsc::unbounded_channel();
-        let (channel_download_events_tx, channel_download_events_rx) = ::tokio::sync::mpsc::unbounded_channel();
-        let (diagnostic_events_tx, diagnostic_events_rx) = ::tokio::sync::mpsc::unbounded_channel();
+        let (video_download_events_tx, video_download_events_rx) = ::tokio::sync::mpsc::channel(self.event_buffer);
+        let (playlist_download_events_tx, playlist_download_events_rx) =
+            ::tokio::sync::mpsc::channel(self.event_buffer);
+        let (channel_download_events_tx, channel_download_events_rx) = ::tokio::sync::mpsc::channel(self.event_buffer);
+        let (diagnostic_events_tx, diagnostic_events_rx) = ::tokio::sync::mpsc::channel(self.event_buffer);
+
+        let on_failure = {
+            let url = url.clone();
+            let channel_download_events_tx = channel_download_events_tx.clone();
+            let cancellation = self.cancellation.clone();
+
+            move |reason: MaybeOwnedString| async move {
+                if cancellation.is_cancelled() {
+                    return;
+                }
+
+                let event = ChannelDownloadFailedEvent::builder()
+                    .url((*url).clone())
+                    .channel_id(None)
+                    .reason(reason)
+                    .exit_status(None)
+                    .build();
+
+                channel_download_events_tx.send(ChannelDownloadEvent::Failed(event)).await.ok();
+            }
+        };
+
+        let task = ::tokio::spawn(async move {
+            ::tokio::select! {
+                _ = self.rate_limiter.acquire(2) => {},
+                _ = self.cancellation.cancelled() => return Ok(()),
+            }
+
+            let worker = ::tokio::select! {
+                worker = self.resolution_pool.acquire(&url) => worker?,
+                _ = self.cancellation.cancelled() => return Ok(()),
+            };
 
-        ::tokio::spawn(async move {
             #[rustfmt::skip]
-            let (stdout, stderr) = TokioCommandExecutor::execute_all(&[
+            let (stdout, stderr, status) = self.executor.execute_all(&[
                 ("yt-dlp", &[
                     &format!("{}/videos", &*url) as &str,
                     "--quiet",
                     "--color", "no_color",
-                    "--print", "[channel-started:video]%(id)s;%(webpage_url)s;%(channel_id)s;%(channel_url)s;%(channel)s",
+                    "--print", "[channel-started:video]%(.{id,webpage_url,playlist_index,channel_id,channel_url,channel})j",
                 ]),
                 ("yt-dlp", &[
                     &format!("{}/playlists", &*url),
                     "--quiet",
                     "--color", "no_color",
                     "--flat-playlist",
-                    "--print", "[channel-started:playlist]%(id)s;%(url)s",
+                    "--print", "[channel-started:playlist]%(.{id,url})j",
                 ]),
             ])?;
 
+            let mut errors = Vec::new();
+
             let (channel, _) = ::tokio::try_join!(
                 async {
-                    let event = ChannelDownloadStartedEvent::from_lines(stdout).await.ok()?;
+                    let (event, malformed_line_events) = ChannelDownloadStartedEvent::from_lines(stdout).await;
+
+                    for event in malformed_line_events {
+                        diagnostic_events_tx.send(event).await?;
+                    }
+
+                    let Some(mut event) = event else {
+                        return Ok(None);
+                    };
+
+                    event.channel.url = (*url).clone();
+
                     let channel = event.channel.clone();
 
-                    channel_download_events_tx.send(ChannelDownloadEvent::Started(event))?;
+                    channel_download_events_tx.send(ChannelDownloadEvent::Started(event)).await?;
 
-                    Ok(channel)
+                    Ok(Some(channel))
                 },
                 async {
-                    stderr
-                        .filter_map(|line| async { DiagnosticEvent::from_line(line) })
-                        .map(Ok)
-                        .try_for_each(|event| async { diagnostic_events_tx.send(event) })
-                        .await
-                        .map_err(::anyhow::Error::from)
+                    let mut stderr = stderr;
+
+                    while let Some(line) = stderr.next().await {
+                        if let Some(event) = DiagnosticEventParser::parse(line) {
+                            if let DiagnosticLevel::Error = event.level {
+                                errors.push(event.clone());
+                            }
+
+                            diagnostic_events_tx.send(event).await?;
+                        }
+                    }
+
+                    Fallible::Ok(())
                 },
             )?;
 
+            let status = status.await;
+
+            ::core::mem::drop(worker);
+
+            // A listing that exited unsuccessfully may have been cut short, so that
+            // downloading what little it printed would pass for the whole channel
+            let channel = match (channel, status) {
+                (Some(channel), Some(0)) => channel,
+                (channel, status) => {
+                    if self.cancellation.is_cancelled().not() {
+                        let event = ChannelDownloadFailedEvent::builder()
+                            .url((*url).clone())
+                            .channel_id(channel.map(|channel| channel.id))
+                            .reason(FailureReason::describe(&errors, status))
+                            .exit_status(status)
+                            .build();
+
+                        channel_download_events_tx.send(ChannelDownloadEvent::Failed(event)).await?;
+                    }
+
+                    return Ok(());
+                },
+            };
+
             let channel = PartiallyResolvedChannelDeduplicator::deduplicate(channel);
 
             ::tracing::debug!("Downloading channel `{:?}`", channel);
 
-            let completed_videos = ::std::sync::Arc::new(::std::sync::atomic::AtomicU64::default());
+            let (skipped_videos, pending_videos) = DownloadedVideosPartitioner::partition(
+                channel.videos.as_deref().unwrap_or_default(),
+                &downloaded_videos,
+            );
+
+            let completed_videos = ::std::sync::atomic::AtomicU64::default();
+            let failed_videos = ::std::sync::atomic::AtomicU64::default();
+            let skipped_videos_count = skipped_videos.len() as u64;
             let total_videos = channel.videos.as_deref().map(|videos| videos.len() as u64).unwrap_or_default();
-            let completed_playlists = ::std::sync::Arc::new(::std::sync::atomic::AtomicU64::default());
+            let completed_playlists = ::std::sync::atomic::AtomicU64::default();
+            let failed_playlists = ::std::sync::atomic::AtomicU64::default();
             let total_playlists = channel
                 .playlists
                 .as_deref()
                 .map(|playlists| playlists.len() as u64)
                 .unwrap_or_default();
 
-            let videos = ::std::sync::Arc::new(::tokio::sync::Mutex::new(Vec::with_capacity(total_videos as usize)));
-            let playlists =
-                ::std::sync::Arc::new(::tokio::sync::Mutex::new(Vec::with_capacity(total_playlists as usize)));
+            let progress = || {
+                ChannelDownloadProgressUpdatedEvent::builder()
+                    .channel_id(channel.id.clone())
+                    .completed_videos(completed_videos.load(::std::sync::atomic::Ordering::Relaxed))
+                    .failed_videos(failed_videos.load(::std::sync::atomic::Ordering::Relaxed))
+                    .skipped_videos(skipped_videos_count)
+                    .total_videos(total_videos)
+                    .completed_playlists(completed_playlists.load(::std::sync::atomic::Ordering::Relaxed))
+                    .failed_playlists(failed_playlists.load(::std::sync::atomic::Ordering::Relaxed))
+                    .total_playlists(total_playlists)
+                    .build()
+            };
+
+            if skipped_videos_count > 0 {
+                channel_download_events_tx
+                    .send(ChannelDownloadEvent::ProgressUpdated(progress()))
+                    .await?;
+            }
+
+            let mut video_downloads = VideoDownloadsSupervisor::builder()
+                .downloader(::std::sync::Arc::clone(&self))
+                .video_download_events_tx(video_download_events_tx.clone())
+                .diagnostic_events_tx(diagnostic_events_tx.clone())
+                .build();
 
-            let videos_completed_notify = ::std::sync::Arc::new(::tokio::sync::Notify::new());
-            let playlists_completed_notify = ::std::sync::Arc::new(::tokio::sync::Notify::new());
+            pending_videos
+                .into_iter()
+                .for_each(|video| video_downloads.spawn(video, channel.id.clone()));
 
-            ::tracing::debug!(
-                "Downloaded videos `{:?}` (`{}`/`{}`)",
-                videos,
-                completed_videos.load(::std::sync::atomic::Ordering::Relaxed),
-                total_videos
-            );
-            ::tracing::debug!(
-                "Downloaded playlists `{:?}` (`{}`/`{}`)",
-                playlists,
-                completed_playlists.load(::std::sync::atomic::Ordering::Relaxed),
-                total_playlists
-            );
+            let mut playlist_downloads = ::tokio::task::JoinSet::new();
+            let mut playlists_by_task_ids = ::std::collections::HashMap::new();
 
-            ::tokio::try_join!(
-                async {
-                    channel.videos.as_deref().into_iter().flatten().cloned().for_each(|video| {
-                        ::tokio::spawn({
-                            let this = ::std::sync::Arc::clone(&self);
-
-                            let video_download_events_tx = video_download_events_tx.clone();
-                            let channel_download_events_tx = channel_download_events_tx.clone();
-                            let diagnostic_events_tx = diagnostic_events_tx.clone();
-
-                            let channel_id = channel.id.clone();
-
-                            let completed_videos = ::std::sync::Arc::clone(&completed_videos);
-                            let completed_playlists = ::std::sync::Arc::clone(&completed_playlists);
-
-                            let videos = ::std::sync::Arc::clone(&videos);
-                            let videos_completed_notify = ::std::sync::Arc::clone(&videos_completed_notify);
-
-                            async move {
-                                let worker = this.worker_pool.acquire().await?;
-
-                                let (video_download_events, diagnostic_events) =
-                                    VideoDownloader::download(::std::sync::Arc::clone(&this), video.url.clone().into())
-                                        .await?;
-
-                                ::tokio::try_join!(
-                                    async {
-                                        video_download_events
-                                            .map(Ok)
-                                            .try_for_each(|event| async {
-                                                if let VideoDownloadEvent::Completed(ref event) = event {
-                                                    completed_videos
-                                                        .fetch_add(1, ::std::sync::atomic::Ordering::Relaxed);
-                                                    videos.lock().await.push(event.video.clone());
-
-                                                    let event = ChannelDownloadProgressUpdatedEvent::builder()
-                                                        .channel_id(channel_id.clone())
-                                                        .completed_videos(
-                                                            completed_videos
-                                                                .load(::std::sync::atomic::Ordering::Relaxed),
-                                                        )
-                                                        .total_videos(total_videos)
-                                                        .completed_playlists(
-                                                            completed_playlists
-                                                                .load(::std::sync::atomic::Ordering::Relaxed),
-                                                        )
-                                                        .total_playlists(total_playlists)
-                                                        .build();
-
-                                                    channel_download_events_tx
-                                                        .send(ChannelDownloadEvent::ProgressUpdated(event))?;
-                                                }
-
-                                                video_download_events_tx.send(event)?;
-
-                                                Ok(())
-                                            })
-                                            .await
-                                    },
-                                    async {
-                                        diagnostic_events
-                                            .map(Ok)
-                                            .try_for_each(|event| async { diagnostic_events_tx.send(event) })
-                                            .await
-                                            .map_err(::anyhow::Error::from)
-                                    },
-                                )?;
-
-                                ::tokio::time::sleep(this.per_worker_cooldown).await;
-                                ::core::mem::drop(worker);
-
-                                ::tracing::debug!(
-                                    "Downloaded video `{:?}` (`{}`/`{}`)",
-                                    video,
-                                    completed_videos.load(::std::sync::atomic::Ordering::Relaxed),
-                                    total_videos
-                                );
-
-                                if completed_videos.load(::std::sync::atomic::Ordering::Relaxed) == total_videos {
-                                    videos_completed_notify.notify_one();
-                                }
-
-                                Ok::<_, ::anyhow::Error>(())
-                            }
-                        });
-                    });
+            channel
+                .playlists
+                .as_deref()
+                .into_iter()
+                .flatten()
+                .cloned()
+                .for_each(|playlist| {
+                    let task = playlist_downloads.spawn({
+                        let this = ::std::sync::Arc::clone(&self);
+
+                        let video_download_events_tx = video_download_events_tx.clone();
+                        let playlist_download_events_tx = playlist_download_events_tx.clone();
+                        let diagnostic_events_tx = diagnostic_events_tx.clone();
+
+                        let url = playlist.url.clone();
+                        let downloaded_videos = downloaded_videos.clone();
+
+                        async move {
+                            let (video_download_events, playlist_download_events, diagnostic_events) =
+                                PlaylistDownloader::download(this, url.into(), downloaded_videos).await?;
+
+                            let (_, playlist, _) = ::tokio::try_join!(
+                                async {
+                                    video_download_events
+                                        .map(Ok)
+                                        .try_for_each(|event| {
+                                            VideoDownloadEventSender::send(&video_download_events_tx, event)
+                                        })
+                                        .await
+                                },
+                                async {
+                                    let mut playlist_download_events = playlist_download_events;
+                                    let mut playlist = None;
+
+                                    while let Some(event) = playlist_download_events.next().await {
+                                        if let PlaylistDownloadEvent::Completed(ref event) = event {
+                                            playlist = Some(event.playlist.clone());
+                                        }
 
-                    Ok::<_, ::anyhow::Error>(())
-                },
-                async {
-                    channel
-                        .playlists
-                        .as_deref()
-                        .into_iter()
-                        .flatten()
-                        .cloned()
-                        .for_each(|playlist| {
-                            ::tokio::spawn({
-                                let this = ::std::sync::Arc::clone(&self);
-
-                                let video_download_events_tx = video_download_events_tx.clone();
-                                let playlist_download_events_tx = playlist_download_events_tx.clone();
-                                let channel_download_events_tx = channel_download_events_tx.clone();
-                                let diagnostic_events_tx = diagnostic_events_tx.clone();
-
-                                let channel_id = channel.id.clone();
-
-                                let completed_videos = ::std::sync::Arc::clone(&completed_videos);
-                                let completed_playlists = ::std::sync::Arc::clone(&completed_playlists);
-
-                                let playlists = ::std::sync::Arc::clone(&playlists);
-                                let playlists_completed_notify = ::std::sync::Arc::clone(&playlists_completed_notify);
-
-                                async move {
-                                    let worker = this.worker_pool.acquire().await?;
-
-                                    let (video_download_events, playlist_download_events, diagnostic_events) =
-                                        PlaylistDownloader::download(
-                                            ::std::sync::Arc::clone(&this),
-                                            playlist.url.clone().into(),
-                                        )
-                                        .await?;
-
-                                    ::tokio::try_join!(
-                                        async {
-                                            video_download_events
-                                                .map(Ok)
-                                                .try_for_each(|event| async { video_download_events_tx.send(event) })
-                                                .await
-                                                .map_err(::anyhow::Error::from)
-                                        },
-                                        async {
-                                            playlist_download_events
-                                                .map(Ok)
-                                                .try_for_each(|event| async {
-                                                    if let PlaylistDownloadEvent::Completed(ref event) = event {
-                                                        completed_playlists
-                                                            .fetch_add(1, ::std::sync::atomic::Ordering::Relaxed);
-                                                        playlists.lock().await.push(event.playlist.clone());
-
-                                                        let event = ChannelDownloadProgressUpdatedEvent::builder()
-                                                            .channel_id(channel_id.clone())
-                                                            .completed_videos(
-                                                                completed_videos
-                                                                    .load(::std::sync::atomic::Ordering::Relaxed),
-                                                            )
-                                                            .total_videos(total_videos)
-                                                            .completed_playlists(
-                                                                completed_playlists
-                                                                    .load(::std::sync::atomic::Ordering::Relaxed),
-                                                            )
-                                                            .total_playlists(total_playlists)
-                                                            .build();
-
-                                                        channel_download_events_tx
-                                                            .send(ChannelDownloadEvent::ProgressUpdated(event))?;
-                                                    }
-
-                                                    playlist_download_events_tx.send(event)?;
-
-                                                    Ok(())
-                                                })
-                                                .await
-                                        },
-                                        async {
-                                            diagnostic_events
-                                                .map(Ok)
-                                                .try_for_each(|event| async { diagnostic_events_tx.send(event) })
-                                                .await
-                                                .map_err(::anyhow::Error::from)
-                                        },
-                                    )?;
-
-                                    ::tokio::time::sleep(this.per_worker_cooldown).await;
-                                    ::core::mem::drop(worker);
-
-                                    ::tracing::debug!(
-                                        "Downloaded playlist `{:?}` (`{}`/`{}`)",
-                                        playlist,
-                                        completed_playlists.load(::std::sync::atomic::Ordering::Relaxed),
-                                        total_playlists
-                                    );
-
-                                    if completed_playlists.load(::std::sync::atomic::Ordering::Relaxed)
-                                        == total_playlists
-                                    {
-                                        playlists_completed_notify.notify_one();
+                                        playlist_download_events_tx.send(event).await?;
                                     }
 
-                                    Ok::<_, ::anyhow::Error>(())
-                                }
-                            });
-                        });
+                                    Fallible::Ok(playlist)
+                                },
+                                async {
+                                    diagnostic_events
+                                        .map(Ok)
+                                        .try_for_each(|event| async { diagnostic_events_tx.send(event).await })
+                                        .await
+                                        .map_err(::anyhow::Error::from)
+                                },
+                            )?;
+
+                            Fallible::Ok(playlist)
+                        }
+                    });
 
-                    Ok::<_, ::anyhow::Error>(())
-                },
-            )?;
+                    playlists_by_task_ids.insert(task.id(), playlist);
+                });
+
+            let downloads = async {
+                ::tokio::try_join!(
+                    async {
+                        let mut videos = Vec::with_capacity(total_videos as usize);
+                        videos.extend(skipped_videos);
+
+                        while let Some(outcome) = video_downloads.join_next().await? {
+                            match outcome {
+                                VideoDownloadOutcome::Completed(video) => {
+                                    completed_videos.fetch_add(1, ::std::sync::atomic::Ordering::Relaxed);
+                                    videos.push(video);
+                                },
+                                VideoDownloadOutcome::Failed => {
+                                    failed_videos.fetch_add(1, ::std::sync::atomic::Ordering::Relaxed);
+                                },
+                            }
 
-            ::tokio::join!(videos_completed_notify.notified(), playlists_completed_notify.notified(),);
+                            channel_download_events_tx
+                                .send(ChannelDownloadEvent::ProgressUpdated(progress()))
+                                .await?;
 
-            let videos = ::std::mem::take(&mut *videos.lock().await);
-            let videos = videos.is_empty().not().then_some(videos.into());
+                            ::tracing::debug!("Downloaded videos `{:?}` (`{:?}`)", videos, progress());
+                        }
+
+                        Fallible::Ok(videos)
+                    },
+                    async {
+                        let mut playlists = Vec::with_capacity(total_playlists as usize);
+
+                        while let Some(joined) = playlist_downloads.join_next_with_id().await {
+                            let (task_id, playlist) = match joined {
+                                Ok((task_id, playlist)) => (task_id, playlist),
+                                Err(error) => (error.id(), Err(error.into())),
+                            };
+
+                            let pending_playlist = playlists_by_task_ids.remove(&task_id).ok()?;
+
+                            match playlist {
+                                Ok(Some(playlist)) => {
+                                    completed_playlists.fetch_add(1, ::std::sync::atomic::Ordering::Relaxed);
+                                    playlists.push(playlist);
+                                },
+                                // The playlist reported its own failure
+                                Ok(None) => {
+                                    failed_playlists.fetch_add(1, ::std::sync::atomic::Ordering::Relaxed);
+                                },
+                                Err(error) => {
+                                    failed_playlists.fetch_add(1, ::std::sync::atomic::Ordering::Relaxed);
+
+                                    let event = PlaylistDownloadFailedEvent::builder()
+                                        .url(pending_playlist.url)
+                                        .playlist_id(Some(pending_playlist.id))
+                                        .reason(error.to_string().into())
+                                        .exit_status(None)
+                                        .build();
+
+                                    playlist_download_events_tx.send(PlaylistDownloadEvent::Failed(event)).await?;
+                                },
+                            }
+
+                            channel_download_events_tx
+                                .send(ChannelDownloadEvent::ProgressUpdated(progress()))
+                                .await?;
+
+                            ::tracing::debug!("Downloaded playlists `{:?}` (`{:?}`)", playlists, progress());
+                        }
+
+                        Fallible::Ok(playlists)
+                    },
+                )
+            };
+
+            let (mut videos, playlists) = ::tokio::select! {
+                downloads = downloads => downloads?,
+                _ = self.cancellation.cancelled() => return Ok(()),
+            };
+
+            ListingOrderRestorer::restore(&mut videos, channel.videos.as_deref().unwrap_or_default());
 
-            let playlists = ::std::mem::take(&mut *playlists.lock().await);
+            let videos = videos.is_empty().not().then_some(videos.into());
             let playlists = playlists.is_empty().not().then_some(playlists.into());
 
             let channel = ResolvedChannel::builder()
@@ -573,114 +1091,981 @@ impl ChannelDownloader for YtdlpDownloader {
                 .metadata(channel.metadata)
                 .videos(videos)
                 .playlists(playlists)
+                .listed_videos(total_videos)
                 .build();
 
             ::tracing::debug!("Downloaded channel `{:?}`", channel);
 
             let event = ChannelDownloadCompletedEvent { channel };
-            channel_download_events_tx.send(ChannelDownloadEvent::Completed(event))?;
+            channel_download_events_tx.send(ChannelDownloadEvent::Completed(event)).await?;
 
             Ok::<_, ::anyhow::Error>(())
         });
 
+        TaskSupervisor::supervise(task, on_failure);
+
         Ok((
-            ::std::boxed::Box::pin(::tokio_stream::wrappers::UnboundedReceiverStream::new(video_download_events_rx)),
-            ::std::boxed::Box::pin(::tokio_stream::wrappers::UnboundedReceiverStream::new(playlist_download_events_rx)),
-            ::std::boxed::Box::pin(::tokio_stream::wrappers::UnboundedReceiverStream::new(channel_download_events_rx)),
-            ::std::boxed::Box::pin(::tokio_stream::wrappers::UnboundedReceiverStream::new(diagnostic_events_rx)),
+            ::std::boxed::Box::pin(::tokio_stream::wrappers::ReceiverStream::new(video_download_events_rx)),
+            ::std::boxed::Box::pin(::tokio_stream::wrappers::ReceiverStream::new(playlist_download_events_rx)),
+            ::std::boxed::Box::pin(::tokio_stream::wrappers::ReceiverStream::new(channel_download_events_rx)),
+            ::std::boxed::Box::pin(::tokio_stream::wrappers::ReceiverStream::new(diagnostic_events_rx)),
         ))
     }
 }
 
-trait CommandExecutor {
-    fn execute<Program, Args>(
-        program: Program, args: Args,
-    ) -> Fallible<(BoxedStream<MaybeOwnedString>, BoxedStream<MaybeOwnedString>)>
-    where
-        Program: AsRef<::std::ffi::OsStr>,
-        Args: IntoIterator,
-        Args::Item: AsRef<::std::ffi::OsStr>;
-
-    fn execute_all<Program, Arg>(
-        commands: &[(Program, &[Arg])],
-    ) -> Fallible<(BoxedStream<MaybeOwnedString>, BoxedStream<MaybeOwnedString>)>
-    where
-        Program: AsRef<::std::ffi::OsStr>,
-        Arg: AsRef<::std::ffi::OsStr>,
-    {
-        let mut stdouts = Vec::with_capacity(commands.len());
-        let mut stderrs = Vec::with_capacity(commands.len());
-
-        commands
-            .iter()
-            .map(|(program, args)| (program, args.iter()))
-            .filter_map(|(program, args)| Self::execute(program, args).ok())
-            .for_each(|(stdout, stderr)| {
-                stdouts.push(stdout);
-                stderrs.push(stderr);
-            });
+#[async_trait]
+impl VideoResolver for YtdlpDownloader {
+    async fn resolve(
+        self: ::std::sync::Arc<Self>, url: VideoUrl,
+    ) -> Fallible<(PlannedVideo, BoxedStream<DiagnosticEvent>)> {
+        ::tokio::select! {
+            _ = self.rate_limiter.acquire(1) => {},
+            _ = self.cancellation.cancelled() => ::anyhow::bail!("Refused to resolve `{}` after cancellation", &*url),
+        }
 
-        Ok((
-            ::std::boxed::Box::pin(::futures::stream::select_all(stdouts)),
-            ::std::boxed::Box::pin(::futures::stream::select_all(stderrs)),
-        ))
+        let worker = ::tokio::select! {
+            worker = self.resolution_pool.acquire(&url) => worker?,
+            _ = self.cancellation.cancelled() => ::anyhow::bail!("Refused to resolve `{}` after cancellation", &*url),
+        };
+
+        let output = format!("%(title)+U.{}", self.profile.codec.extension());
+        let trim_filenames = self.profile.max_file_stem_length(&self.directory).to_string();
+
+        #[rustfmt::skip]
+        let (stdout, stderr, _) = self.executor.execute("yt-dlp", &[
+            &*url,
+            "--quiet",
+            "--color", "no_color",
+            "--paths", self.directory.to_str().ok()?,
+            "--no-playlist",
+            "--format", "bestaudio",
+            "--output", &output,
+            "--trim-filenames", &trim_filenames,
+            "--restrict-filenames",
+            "--windows-filenames",
+            "--print", "[video-planned]%(.{id,original_url,title,duration,filesize,filesize_approx,filename})j",
+        ])?;
+
+        let ((video, malformed_line_events), mut diagnostic_events) = ::tokio::join!(
+            async {
+                let mut stdout = stdout;
+                let mut video = None;
+                let mut malformed_line_events = Vec::new();
+
+                while let Some(line) = stdout.next().await {
+                    let parsed = PlannedVideo::from_line(&line);
+                    video = video.or(MalformedLineReporter::recover(&line, parsed, &mut malformed_line_events));
+                }
+
+                (video, malformed_line_events)
+            },
+            DiagnosticEventsCollector::collect(stderr),
+        );
+
+        diagnostic_events.extend(malformed_line_events);
+
+        ::core::mem::drop(worker);
+
+        ::tracing::debug!("Resolved video `{:?}`", video);
+
+        Ok((video.ok()?, ::std::boxed::Box::pin(::futures::stream::iter(diagnostic_events))))
     }
 }
 
-struct TokioCommandExecutor;
-
-impl CommandExecutor for TokioCommandExecutor {
-    fn execute<Program, Args>(
-        program: Program, args: Args,
-    ) -> Fallible<(BoxedStream<MaybeOwnedString>, BoxedStream<MaybeOwnedString>)>
-    where
-        Program: AsRef<::std::ffi::OsStr>,
-        Args: IntoIterator,
-        Args::Item: AsRef<::std::ffi::OsStr>,
+#[async_trait]
+impl PlaylistResolver for YtdlpDownloader {
+    async fn resolve(
+        self: ::std::sync::Arc<Self>, url: PlaylistUrl,
+    ) -> Fallible<(PlannedPlaylist, BoxedStream<DiagnosticEvent>)> {
+        ::tokio::select! {
+            _ = self.rate_limiter.acquire(1) => {},
+            _ = self.cancellation.cancelled() => ::anyhow::bail!("Refused to resolve `{}` after cancellation", &*url),
+        }
+
+        let worker = ::tokio::select! {
+            worker = self.resolution_pool.acquire(&url) => worker?,
+            _ = self.cancellation.cancelled() => ::anyhow::bail!("Refused to resolve `{}` after cancellation", &*url),
+        };
+
+        let output = format!("%(title)+U.{}", self.profile.codec.extension());
+        let trim_filenames = self.profile.max_file_stem_length(&self.directory).to_string();
+
+        #[rustfmt::skip]
+        let (stdout, stderr, _) = self.executor.execute("yt-dlp", &[
+            &*url,
+            "--quiet",
+            "--color", "no_color",
+            "--paths", self.directory.to_str().ok()?,
+            "--flat-playlist",
+            "--yes-playlist",
+            "--output", &output,
+            "--trim-filenames", &trim_filenames,
+            "--restrict-filenames",
+            "--windows-filenames",
+            "--print", "playlist:[playlist-planned:metadata]%(.{id,original_url,title})j",
+            "--print", "video:[playlist-planned:video]%(.{id,url,title,duration,filesize,filesize_approx,filename})j",
+        ])?;
+
+        let ((playlist, malformed_line_events), mut diagnostic_events) =
+            ::tokio::join!(PlannedPlaylist::from_lines(stdout), DiagnosticEventsCollector::collect(stderr),);
+
+        diagnostic_events.extend(malformed_line_events);
+
+        ::core::mem::drop(worker);
+
+        let playlist = PlannedPlaylistDeduplicator::deduplicate(playlist.ok()?);
+
+        ::tracing::debug!("Resolved playlist `{:?}`", playlist);
+
+        Ok((playlist, ::std::boxed::Box::pin(::futures::stream::iter(diagnostic_events))))
+    }
+}
+
+#[async_trait]
+impl ChannelResolver for YtdlpDownloader {
+    async fn resolve(
+        self: ::std::sync::Arc<Self>, url: ChannelUrl,
+    ) -> Fallible<(PlannedChannel, BoxedStream<DiagnosticEvent>)> {
+        ::tokio::select! {
+            _ = self.rate_limiter.acquire(2) => {},
+            _ = self.cancellation.cancelled() => ::anyhow::bail!("Refused to resolve `{}` after cancellation", &*url),
+        }
+
+        let worker = ::tokio::select! {
+            worker = self.resolution_pool.acquire(&url) => worker?,
+            _ = self.cancellation.cancelled() => ::anyhow::bail!("Refused to resolve `{}` after cancellation", &*url),
+        };
+
+        let output = format!("%(title)+U.{}", self.profile.codec.extension());
+        let trim_filenames = self.profile.max_file_stem_length(&self.directory).to_string();
+
+        #[rustfmt::skip]
+        let (stdout, stderr, _) = self.executor.execute_all(&[
+            ("yt-dlp", &[
+                &format!("{}/videos", &*url) as &str,
+                "--quiet",
+                "--color", "no_color",
+                "--paths", self.directory.to_str().ok()?,
+                "--flat-playlist",
+                "--output", &output,
+                "--trim-filenames", &trim_filenames,
+                "--restrict-filenames",
+                "--windows-filenames",
+                "--print", "playlist:[channel-planned:metadata]%(.{id,webpage_url,title,channel_id,channel_url,channel})j",
+                "--print", "video:[channel-planned:video]%(.{id,url,title,duration,filesize,filesize_approx,filename})j",
+            ]),
+            ("yt-dlp", &[
+                &format!("{}/playlists", &*url),
+                "--quiet",
+                "--color", "no_color",
+                "--flat-playlist",
+                "--print", "[channel-planned:playlist]%(.{id,url})j",
+            ]),
+        ])?;
+
+        let ((channel, malformed_line_events), mut diagnostic_events) =
+            ::tokio::join!(PlannedChannel::from_lines(stdout), DiagnosticEventsCollector::collect(stderr),);
+
+        diagnostic_events.extend(malformed_line_events);
+
+        // The worker is released before resolving the playlists, which need workers of
+        // their own
+        ::core::mem::drop(worker);
+
+        let mut channel = PlannedChannelDeduplicator::deduplicate(channel.ok()?);
+        channel.url = (*url).clone();
+
+        let playlists = ::futures::stream::iter(channel.playlists.as_deref().unwrap_or_default().to_vec())
+            .map(|playlist| {
+                let this = ::std::sync::Arc::clone(&self);
+
+                async move {
+                    let (playlist, diagnostic_events) =
+                        PlaylistResolver::resolve(this, playlist.url.clone().into()).await?;
+
+                    Fallible::Ok((playlist, diagnostic_events.collect::<Vec<_>>().await))
+                }
+            })
+            .buffered(self.resolvers as usize)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let playlists = playlists
+            .into_iter()
+            .map(|(playlist, playlist_diagnostic_events)| {
+                diagnostic_events.extend(playlist_diagnostic_events);
+
+                playlist
+            })
+            .collect::<Vec<_>>();
+
+        channel.playlists = playlists.is_empty().not().then(|| playlists.into());
+
+        ::tracing::debug!("Resolved channel `{:?}`", channel);
+
+        Ok((channel, ::std::boxed::Box::pin(::futures::stream::iter(diagnostic_events))))
+    }
+}
+
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct HttpFeedDownloader {
+    directory: MaybeOwnedPath,
+
+    profile: DeviceProfile,
+
+    workers: u64,
+
+    #[builder(default)]
+    rate_limiter: ::std::sync::Arc<RateLimiter>,
+
+    #[builder(default)]
+    cancellation: ::tokio_util::sync::CancellationToken,
+
+    // Transcodes the episodes that do not come in the profile's codec
+    executor: ::std::sync::Arc<dyn CommandExecutor>,
+
+    #[builder(default = 256_usize)]
+    event_buffer: usize,
+
+    #[builder(skip)]
+    client: ::reqwest::Client,
+
+    #[builder(skip)]
+    reserved_file_names: ::tokio::sync::Mutex<::std::collections::HashSet<::std::string::String>>,
+
+    #[builder(skip = ::std::sync::Arc::new(WorkerPool::new(workers)))]
+    download_pool: ::std::sync::Arc<WorkerPool>,
+}
+
+#[async_trait]
+impl FeedDownloader for HttpFeedDownloader {
+    async fn download(
+        self: ::std::sync::Arc<Self>, url: FeedUrl, downloaded_videos: MaybeOwnedVec<ResolvedVideo>,
+    ) -> Fallible<(BoxedStream<VideoDownloadEvent>, BoxedStream<PlaylistDownloadEvent>, BoxedStream<DiagnosticEvent>)>
     {
-        use ::tokio::io::AsyncBufReadExt as _;
+        let (video_download_events_tx, video_download_events_rx) = ::tokio::sync::mpsc::channel(self.event_buffer);
+        let (playlist_download_events_tx, playlist_download_events_rx) =
+            ::tokio::sync::mpsc::channel(self.event_buffer);
+        let (diagnostic_events_tx, diagnostic_events_rx) = ::tokio::sync::mpsc::channel(self.event_buffer);
+
+        let on_failure = {
+            let url = url.clone();
+            let playlist_download_events_tx = playlist_download_events_tx.clone();
+            let cancellation = self.cancellation.clone();
+
+            move |reason: MaybeOwnedString| async move {
+                if cancellation.is_cancelled() {
+                    return;
+                }
+
+                let event = PlaylistDownloadFailedEvent::builder()
+                    .url((*url).clone())
+                    .playlist_id(None)
+                    .reason(reason)
+                    .exit_status(None)
+                    .build();
 
-        let (stdout_tx, stdout_rx) = ::tokio::sync::mpsc::unbounded_channel();
-        let (stderr_tx, stderr_rx) = ::tokio::sync::mpsc::unbounded_channel();
+                playlist_download_events_tx
+                    .send(PlaylistDownloadEvent::Failed(event))
+                    .await
+                    .ok();
+            }
+        };
+
+        let task = ::tokio::spawn(async move {
+            let feed = ::tokio::select! {
+                feed = self.fetch(&url) => feed?,
+                _ = self.cancellation.cancelled() => return Ok(()),
+            };
+
+            let videos = feed
+                .episodes
+                .iter()
+                .map(|episode| {
+                    UnresolvedVideo::builder()
+                        .id(episode.video.id.clone())
+                        .url(episode.video.url.clone())
+                        .build()
+                })
+                .collect::<Vec<_>>();
+
+            // Feeds have no id of their own, so that their url stands in for it
+            let playlist = PartiallyResolvedPlaylist::builder()
+                .id((*url).clone())
+                .url((*url).clone())
+                .metadata(feed.metadata)
+                .videos(videos.is_empty().not().then(|| videos.into()))
+                .build();
 
-        let mut process = ::tokio::process::Command::new(program)
-            .args(args)
-            .stdout(::std::process::Stdio::piped())
-            .stderr(::std::process::Stdio::piped())
-            .spawn()?;
+            ::tracing::debug!("Downloading feed `{:?}`", playlist);
 
-        let stdout = process.stdout.take().ok()?;
-        let stderr = process.stderr.take().ok()?;
+            let event = PlaylistDownloadStartedEvent::builder().playlist(playlist.clone()).build();
+            playlist_download_events_tx.send(PlaylistDownloadEvent::Started(event)).await?;
 
-        ::tokio::spawn(async move {
-            let lines = ::tokio::io::BufReader::new(stdout).lines();
-
-            ::tokio_stream::wrappers::LinesStream::new(lines)
-                .filter_map(|line| async move { line.ok() })
-                .map(|line| line.to_owned().into())
-                .map(Ok)
-                .try_for_each(|line| async { stdout_tx.send(line) })
-                .await
+            let (skipped_videos, pending_videos) = DownloadedVideosPartitioner::partition(
+                playlist.videos.as_deref().unwrap_or_default(),
+                &downloaded_videos,
+            );
+
+            let mut completed_videos = 0;
+            let mut failed_videos = 0;
+            let skipped_videos_count = skipped_videos.len() as u64;
+            let total_videos = playlist.videos.as_deref().map(|videos| videos.len() as u64).unwrap_or_default();
+
+            let mut videos = Vec::with_capacity(total_videos as usize);
+            videos.extend(skipped_videos);
+
+            if skipped_videos_count > 0 {
+                let event = PlaylistDownloadProgressUpdatedEvent::builder()
+                    .playlist_id(playlist.id.clone())
+                    .completed_videos(0)
+                    .failed_videos(0)
+                    .skipped_videos(skipped_videos_count)
+                    .total_videos(total_videos)
+                    .build();
+
+                playlist_download_events_tx
+                    .send(PlaylistDownloadEvent::ProgressUpdated(event))
+                    .await?;
+            }
+
+            let episodes = feed
+                .episodes
+                .into_iter()
+                .map(|episode| (episode.video.id.clone(), episode))
+                .collect();
+
+            let downloader = FeedEpisodesDownloader::builder()
+                .downloader(::std::sync::Arc::clone(&self))
+                .episodes(episodes)
+                .build();
+
+            let mut video_downloads = VideoDownloadsSupervisor::builder()
+                .downloader(::std::sync::Arc::new(downloader))
+                .video_download_events_tx(video_download_events_tx.clone())
+                .diagnostic_events_tx(diagnostic_events_tx.clone())
+                .build();
+
+            pending_videos
+                .into_iter()
+                .for_each(|video| video_downloads.spawn(video, playlist.id.clone()));
+
+            loop {
+                let outcome = ::tokio::select! {
+                    outcome = video_downloads.join_next() => outcome?,
+                    _ = self.cancellation.cancelled() => return Ok(()),
+                };
+
+                match outcome {
+                    Some(VideoDownloadOutcome::Completed(video)) => {
+                        completed_videos += 1;
+                        videos.push(video);
+                    },
+                    Some(VideoDownloadOutcome::Failed) => failed_videos += 1,
+                    None => break,
+                }
+
+                let event = PlaylistDownloadProgressUpdatedEvent::builder()
+                    .playlist_id(playlist.id.clone())
+                    .completed_videos(completed_videos)
+                    .failed_videos(failed_videos)
+                    .skipped_videos(skipped_videos_count)
+                    .total_videos(total_videos)
+                    .build();
+
+                playlist_download_events_tx
+                    .send(PlaylistDownloadEvent::ProgressUpdated(event))
+                    .await?;
+            }
+
+            ListingOrderRestorer::restore(&mut videos, playlist.videos.as_deref().unwrap_or_default());
+
+            let videos = videos.is_empty().not().then_some(videos.into());
+
+            let playlist = ResolvedPlaylist::builder()
+                .id(playlist.id)
+                .url(playlist.url)
+                .metadata(playlist.metadata)
+                .videos(videos)
+                .listed_videos(total_videos)
+                .build();
+
+            ::tracing::debug!("Downloaded feed `{:?}`", playlist);
+
+            let event = PlaylistDownloadCompletedEvent { playlist };
+            playlist_download_events_tx
+                .send(PlaylistDownloadEvent::Completed(event))
+                .await?;
+
+            Ok::<_, ::anyhow::Error>(())
         });
 
-        ::tokio::spawn(async move {
-            let lines = ::tokio::io::BufReader::new(stderr).lines();
-
-            ::tokio_stream::wrappers::LinesStream::new(lines)
-                .filter_map(|line| async move { line.ok() })
-                .map(|line| line.to_owned().into())
-                .map(Ok)
-                .try_for_each(|line| async { stderr_tx.send(line) })
-                .await
+        TaskSupervisor::supervise(task, on_failure);
+
+        Ok((
+            ::std::boxed::Box::pin(::tokio_stream::wrappers::ReceiverStream::new(video_download_events_rx)),
+            ::std::boxed::Box::pin(::tokio_stream::wrappers::ReceiverStream::new(playlist_download_events_rx)),
+            ::std::boxed::Box::pin(::tokio_stream::wrappers::ReceiverStream::new(diagnostic_events_rx)),
+        ))
+    }
+}
+
+#[async_trait]
+impl FeedResolver for HttpFeedDownloader {
+    async fn resolve(
+        self: ::std::sync::Arc<Self>, url: FeedUrl,
+    ) -> Fallible<(PlannedPlaylist, BoxedStream<DiagnosticEvent>)> {
+        let feed = self.fetch(&url).await?;
+
+        let videos = feed
+            .episodes
+            .into_iter()
+            .map(|episode| {
+                PlannedVideo::builder()
+                    .id(episode.video.id)
+                    .url(episode.video.url)
+                    .metadata(episode.video.metadata)
+                    .path(Some(self.directory.join(self.file_name(&episode.stem, 1)).into()))
+                    .duration(episode.duration)
+                    .estimated_bytes(episode.length)
+                    .build()
+            })
+            .collect::<Vec<_>>();
+
+        let playlist = PlannedPlaylist::builder()
+            .id((*url).clone())
+            .url((*url).clone())
+            .metadata(feed.metadata)
+            .videos(videos.is_empty().not().then(|| videos.into()))
+            .build();
+
+        ::tracing::debug!("Resolved feed `{:?}`", playlist);
+
+        Ok((playlist, ::std::boxed::Box::pin(::futures::stream::empty())))
+    }
+}
+
+impl HttpFeedDownloader {
+    // Progress is reported at most this often, so that small chunks do not flood
+    // the view
+    const PROGRESS_INTERVAL: ::std::time::Duration = ::std::time::Duration::from_millis(250);
+    const USER_AGENT: &'static str = concat!("walkman/", env!("CARGO_PKG_VERSION"));
+
+    async fn fetch(&self, url: &FeedUrl) -> Fallible<FeedDocument> {
+        ::tokio::select! {
+            _ = self.rate_limiter.acquire(1) => {},
+            _ = self.cancellation.cancelled() => ::anyhow::bail!("Refused to fetch `{}` after cancellation", &***url),
+        }
+
+        let document = self
+            .client
+            .get(&***url)
+            .header(::reqwest::header::USER_AGENT, Self::USER_AGENT)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        FeedDocumentParser::parse(&document)
+    }
+
+    // Episodes are named after the profile's codec, which they are transcoded to
+    // whenever they come in another
+    fn file_name(&self, stem: &str, attempt: u64) -> ::std::string::String {
+        let suffix = match attempt {
+            1 => ::std::string::String::new(),
+            _ => format!("_{}", attempt),
+        };
+
+        let max_stem_length = self.profile.max_file_stem_length(&self.directory) as usize;
+        let stem = MediaFileNamer::truncate(stem, max_stem_length.saturating_sub(suffix.len()).max(1));
+
+        format!("{}{}.{}", stem, suffix, self.profile.codec.extension())
+    }
+
+    // Taken names get a numbered suffix, so that episodes of the same title never
+    // overwrite each other or the files already there
+    async fn reserve_path(&self, episode: &FeedEpisode) -> Fallible<::std::path::PathBuf> {
+        let mut reserved_file_names = self.reserved_file_names.lock().await;
+
+        for attempt in 1_u64.. {
+            let file_name = self.file_name(&episode.stem, attempt);
+            let path = self.directory.join(&file_name);
+
+            if reserved_file_names.contains(&file_name).not() && ::tokio::fs::try_exists(&path).await?.not() {
+                reserved_file_names.insert(file_name);
+
+                return Ok(path);
+            }
+        }
+
+        unreachable!()
+    }
+
+    // Sources already in the profile's codec are kept as they are, since encoding
+    // them again would only lose quality
+    async fn transcode(&self, episode: &FeedEpisode, path: &::std::path::Path) -> Fallible<()> {
+        if *episode.extension == *self.profile.codec.extension() {
+            return Ok(());
+        }
+
+        let transcoded_path = path.with_file_name(format!(
+            "{}.{}",
+            path.file_name().ok()?.to_string_lossy(),
+            self.profile.codec.extension()
+        ));
+
+        let [bitrate_option, bitrate] = self.profile.bitrate.ffmpeg_args();
+        let sample_rate = self.profile.max_sample_rate.to_string();
+        let channels = self.profile.channels.to_string();
+
+        #[rustfmt::skip]
+        let args = [
+            "-hide_banner", "-nostdin", "-y",
+            "-i", path.to_str().ok()?,
+            "-map", "0:a:0",
+            "-c:a", self.profile.codec.ffmpeg_encoder(),
+            &bitrate_option, &bitrate,
+            "-ar", &sample_rate,
+            "-ac", &channels,
+            "-f", self.profile.codec.ffmpeg_muxer(), transcoded_path.to_str().ok()?,
+        ];
+
+        CommandRunner::run(&*self.executor, "ffmpeg", &args).await?;
+
+        ::tokio::fs::rename(&transcoded_path, path).await?;
+
+        Ok(())
+    }
+
+    fn download_episode(
+        self: ::std::sync::Arc<Self>, episode: FeedEpisode, queue: MaybeOwnedString,
+    ) -> Fallible<(BoxedStream<VideoDownloadEvent>, BoxedStream<DiagnosticEvent>)> {
+        let (video_download_events_tx, video_download_events_rx) = ::tokio::sync::mpsc::channel(self.event_buffer);
+
+        let on_failure = {
+            let video = episode.video.clone();
+            let video_download_events_tx = video_download_events_tx.clone();
+            let cancellation = self.cancellation.clone();
+
+            move |reason: MaybeOwnedString| async move {
+                if cancellation.is_cancelled() {
+                    return;
+                }
+
+                let event = VideoDownloadFailedEvent::builder()
+                    .url(video.url)
+                    .video_id(Some(video.id))
+                    .reason(reason)
+                    .exit_status(None)
+                    .build();
+
+                video_download_events_tx.send(VideoDownloadEvent::Failed(event)).await.ok();
+            }
+        };
+
+        let task = ::tokio::spawn(async move {
+            ::tokio::select! {
+                _ = self.rate_limiter.acquire(1) => {},
+                _ = self.cancellation.cancelled() => return Ok(()),
+            }
+
+            let worker = ::tokio::select! {
+                worker = self.download_pool.acquire(&queue) => worker?,
+                _ = self.cancellation.cancelled() => return Ok(()),
+            };
+
+            let event = VideoDownloadStartedEvent::builder().video(episode.video.clone()).build();
+            video_download_events_tx.send(VideoDownloadEvent::Started(event)).await?;
+
+            ::tokio::fs::create_dir_all(&self.directory).await?;
+
+            let folder_slot = self.profile.reserve_folder_slot(&self.directory).await?;
+
+            let path = self.reserve_path(&episode).await?;
+            let partial_path = path.with_file_name(format!("{}.part", path.file_name().ok()?.to_string_lossy()));
+
+            let transferred = async {
+                self.transfer()
+                    .episode(&episode)
+                    .path(&partial_path)
+                    .video_download_events_tx(&video_download_events_tx)
+                    .call()
+                    .await?;
+
+                self.transcode(&episode, &partial_path).await
+            };
+
+            let transferred = ::tokio::select! {
+                transferred = transferred => transferred,
+                _ = self.cancellation.cancelled() => {
+                    PartialArtifactsRemover::remove(&path, &self.profile).await.ok();
+
+                    return Ok(());
+                },
+            };
+
+            ::core::mem::drop(worker);
+
+            if let Err(error) = transferred {
+                PartialArtifactsRemover::remove(&path, &self.profile).await.ok();
+
+                let event = VideoDownloadFailedEvent::builder()
+                    .url(episode.video.url)
+                    .video_id(Some(episode.video.id))
+                    .reason(error.to_string().into())
+                    .exit_status(None)
+                    .build();
+
+                video_download_events_tx.send(VideoDownloadEvent::Failed(event)).await?;
+
+                return Ok(());
+            }
+
+            ::tokio::fs::rename(&partial_path, &path).await?;
+
+            folder_slot.keep();
+
+            let video = ResolvedVideo::builder()
+                .id(episode.video.id)
+                .url(episode.video.url)
+                .metadata(episode.video.metadata)
+                .path(path.into())
+                .build();
+
+            let event = VideoDownloadCompletedEvent::builder().video(video).build();
+            video_download_events_tx.send(VideoDownloadEvent::Completed(event)).await?;
+
+            Ok::<_, ::anyhow::Error>(())
         });
 
+        TaskSupervisor::supervise(task, on_failure);
+
         Ok((
-            ::std::boxed::Box::pin(::tokio_stream::wrappers::UnboundedReceiverStream::new(stdout_rx)),
-            ::std::boxed::Box::pin(::tokio_stream::wrappers::UnboundedReceiverStream::new(stderr_rx)),
+            ::std::boxed::Box::pin(::tokio_stream::wrappers::ReceiverStream::new(video_download_events_rx)),
+            ::std::boxed::Box::pin(::futures::stream::empty()),
         ))
     }
 }
 
+#[::bon::bon]
+impl HttpFeedDownloader {
+    #[builder]
+    async fn transfer(
+        &self, episode: &FeedEpisode, path: &::std::path::Path,
+        video_download_events_tx: &::tokio::sync::mpsc::Sender<VideoDownloadEvent>,
+    ) -> Fallible<()> {
+        use ::tokio::io::AsyncWriteExt as _;
+
+        let response = self
+            .client
+            .get(&*episode.video.url)
+            .header(::reqwest::header::USER_AGENT, Self::USER_AGENT)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let total_bytes = response.content_length().or(episode.length);
+        let limit_rate = self.rate_limiter.bytes_per_second(self.workers);
+
+        let mut file = ::tokio::fs::File::create(path).await?;
+        let mut chunks = response.bytes_stream();
+
+        let started_at = ::tokio::time::Instant::now();
+        let mut reported_at = started_at;
+        let mut downloaded_bytes = 0;
+
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk?;
+
+            file.write_all(&chunk).await?;
+            downloaded_bytes += chunk.len() as u64;
+
+            // Whatever got ahead of the limit is slept off, so that the rate evens out over
+            // the transfer
+            if let Some(limit_rate) = limit_rate {
+                let expected = ::std::time::Duration::from_secs_f64(downloaded_bytes as f64 / limit_rate as f64);
+                ::tokio::time::sleep(expected.saturating_sub(started_at.elapsed())).await;
+            }
+
+            let Some(total_bytes) = total_bytes else {
+                continue;
+            };
+
+            if reported_at.elapsed() < Self::PROGRESS_INTERVAL && downloaded_bytes < total_bytes {
+                continue;
+            }
+
+            reported_at = ::tokio::time::Instant::now();
+
+            let elapsed = started_at.elapsed();
+            let bytes_per_second = (downloaded_bytes as f64 / elapsed.as_secs_f64().max(f64::EPSILON)) as u64;
+            let eta = ::std::time::Duration::from_secs_f64(
+                total_bytes.saturating_sub(downloaded_bytes) as f64 / bytes_per_second.max(1) as f64,
+            );
+
+            let event = VideoDownloadProgressUpdatedEvent::builder()
+                .video_id(episode.video.id.clone())
+                .eta(eta)
+                .elapsed(elapsed)
+                .downloaded_bytes(downloaded_bytes)
+                .total_bytes(total_bytes.max(downloaded_bytes))
+                .bytes_per_second(bytes_per_second)
+                .build();
+
+            VideoDownloadEventSender::send(video_download_events_tx, VideoDownloadEvent::ProgressUpdated(event))
+                .await?;
+        }
+
+        file.flush().await?;
+
+        Ok(())
+    }
+}
+
+#[derive(::bon::Builder)]
+struct FeedEpisodesDownloader {
+    downloader: ::std::sync::Arc<HttpFeedDownloader>,
+
+    episodes: ::std::collections::HashMap<MaybeOwnedString, FeedEpisode>,
+}
+
+impl SupervisedVideoDownloader for FeedEpisodesDownloader {
+    fn start(
+        self: ::std::sync::Arc<Self>, video: &UnresolvedVideo, queue: MaybeOwnedString,
+    ) -> Fallible<(BoxedStream<VideoDownloadEvent>, BoxedStream<DiagnosticEvent>)> {
+        let episode = self.episodes.get(&video.id).cloned().ok()?;
+
+        ::std::sync::Arc::clone(&self.downloader).download_episode(episode, queue)
+    }
+}
+
+#[derive(::bon::Builder)]
+struct VideoDownloadsSupervisor<Downloader> {
+    downloader: ::std::sync::Arc<Downloader>,
+
+    video_download_events_tx: ::tokio::sync::mpsc::Sender<VideoDownloadEvent>,
+    diagnostic_events_tx: ::tokio::sync::mpsc::Sender<DiagnosticEvent>,
+
+    #[builder(skip)]
+    tasks: ::tokio::task::JoinSet<Fallible<Option<ResolvedVideo>>>,
+    #[builder(skip)]
+    videos_by_task_ids: ::std::collections::HashMap<::tokio::task::Id, UnresolvedVideo>,
+}
+
+enum VideoDownloadOutcome {
+    Completed(ResolvedVideo),
+    Failed,
+}
+
+trait SupervisedVideoDownloader: ::core::marker::Send + ::core::marker::Sync + 'static {
+    fn start(
+        self: ::std::sync::Arc<Self>, video: &UnresolvedVideo, queue: MaybeOwnedString,
+    ) -> Fallible<(BoxedStream<VideoDownloadEvent>, BoxedStream<DiagnosticEvent>)>;
+}
+
+impl SupervisedVideoDownloader for YtdlpDownloader {
+    fn start(
+        self: ::std::sync::Arc<Self>, video: &UnresolvedVideo, queue: MaybeOwnedString,
+    ) -> Fallible<(BoxedStream<VideoDownloadEvent>, BoxedStream<DiagnosticEvent>)> {
+        self.download_video(video.url.clone().into(), queue)
+    }
+}
+
+impl<Downloader> VideoDownloadsSupervisor<Downloader>
+where
+    Downloader: SupervisedVideoDownloader,
+{
+    fn spawn(&mut self, video: UnresolvedVideo, queue: MaybeOwnedString) {
+        let task = self.tasks.spawn({
+            let downloader = ::std::sync::Arc::clone(&self.downloader);
+
+            let video_download_events_tx = self.video_download_events_tx.clone();
+            let diagnostic_events_tx = self.diagnostic_events_tx.clone();
+
+            let started = downloader.start(&video, queue);
+
+            async move {
+                let (video_download_events, diagnostic_events) = started?;
+
+                let (video, _) = ::tokio::try_join!(
+                    async {
+                        let mut video_download_events = video_download_events;
+                        let mut video = None;
+
+                        while let Some(event) = video_download_events.next().await {
+                            if let VideoDownloadEvent::Completed(ref event) = event {
+                                video = Some(event.video.clone());
+                            }
+
+                            VideoDownloadEventSender::send(&video_download_events_tx, event).await?;
+                        }
+
+                        Fallible::Ok(video)
+                    },
+                    async {
+                        diagnostic_events
+                            .map(Ok)
+                            .try_for_each(|event| async { diagnostic_events_tx.send(event).await })
+                            .await
+                            .map_err(::anyhow::Error::from)
+                    },
+                )?;
+
+                Fallible::Ok(video)
+            }
+        });
+
+        self.videos_by_task_ids.insert(task.id(), video);
+    }
+
+    async fn join_next(&mut self) -> Fallible<Option<VideoDownloadOutcome>> {
+        let Some(joined) = self.tasks.join_next_with_id().await else {
+            return Ok(None);
+        };
+
+        let (task_id, video) = match joined {
+            Ok((task_id, video)) => (task_id, video),
+            Err(error) => (error.id(), Err(error.into())),
+        };
+
+        let pending_video = self.videos_by_task_ids.remove(&task_id).ok()?;
+
+        match video {
+            Ok(Some(video)) => Ok(Some(VideoDownloadOutcome::Completed(video))),
+            // The download reported its own failure
+            Ok(None) => Ok(Some(VideoDownloadOutcome::Failed)),
+            Err(error) => {
+                ::tracing::error!("Failed to download video `{:?}` `{}`", pending_video, error);
+
+                let event = VideoDownloadFailedEvent::builder()
+                    .url(pending_video.url)
+                    .video_id(Some(pending_video.id))
+                    .reason(error.to_string().into())
+                    .exit_status(None)
+                    .build();
+
+                self.video_download_events_tx.send(VideoDownloadEvent::Failed(event)).await?;
+
+                Ok(Some(VideoDownloadOutcome::Failed))
+            },
+        }
+    }
+}
+
+pub(crate) struct TaskSupervisor;
+
+impl TaskSupervisor {
+    // Errors and panics would otherwise be dropped along with the handle, leaving
+    // whoever waits for the task's events hanging
+    pub(crate) fn supervise<Failure, Fut>(task: ::tokio::task::JoinHandle<Fallible<()>>, on_failure: Failure)
+    where
+        Failure: FnOnce(MaybeOwnedString) -> Fut + ::core::marker::Send + 'static,
+        Fut: ::std::future::Future<Output = ()> + ::core::marker::Send,
+    {
+        ::tokio::spawn(async move {
+            let reason = match task.await {
+                Ok(Ok(())) => return,
+                Ok(Err(error)) => error.to_string(),
+                Err(error) => error.to_string(),
+            };
+
+            ::tracing::error!("Supervised task failed `{}`", reason);
+
+            on_failure(reason.into()).await;
+        });
+    }
+}
+
+pub(crate) struct VideoDownloadEventSender;
+
+impl VideoDownloadEventSender {
+    // Progress is superseded by the next update anyway, so that it is dropped
+    // rather than waited for when the consumer falls behind
+    pub(crate) async fn send(
+        tx: &::tokio::sync::mpsc::Sender<VideoDownloadEvent>, event: VideoDownloadEvent,
+    ) -> Fallible<()> {
+        if let VideoDownloadEvent::ProgressUpdated(_) = event {
+            return match tx.try_send(event) {
+                Ok(()) | Err(::tokio::sync::mpsc::error::TrySendError::Full(_)) => Ok(()),
+                Err(error) => Err(error.into()),
+            };
+        }
+
+        tx.send(event).await?;
+
+        Ok(())
+    }
+}
+
+struct FailureReason;
+
+impl FailureReason {
+    fn describe(errors: &[DiagnosticEvent], status: Option<i32>) -> MaybeOwnedString {
+        match (errors.first(), status) {
+            (Some(error), _) => error.message.clone(),
+            (None, Some(0)) => "yt-dlp exited without reporting a result".into(),
+            (None, Some(_)) => "yt-dlp exited unsuccessfully".into(),
+            (None, None) => "yt-dlp was terminated".into(),
+        }
+    }
+}
+
+struct PartialArtifactsRemover;
+
+impl PartialArtifactsRemover {
+    // A file by the name of the download is only partial while it is not yet in
+    // the profile's codec, so that finished downloads are never taken for one
+    async fn remove(path: &::std::path::Path, profile: &DeviceProfile) -> Fallible<()> {
+        let (Some(directory), Some(name), Some(stem)) = (
+            path.parent(),
+            path.file_name().and_then(::std::ffi::OsStr::to_str),
+            path.file_stem().and_then(::std::ffi::OsStr::to_str),
+        ) else {
+            return Ok(());
+        };
+
+        let mut entries = ::tokio::fs::read_dir(directory).await?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let entry_name = entry.file_name();
+            let Some(entry_name) = entry_name.to_str() else {
+                continue;
+            };
+
+            let is_partial = (entry_name == name
+                && path.extension().is_some_and(|extension| extension != profile.codec.extension()))
+                || entry_name.starts_with(&format!("{}.part", name))
+                || entry_name == format!("{}.ytdl", name)
+                || entry_name.starts_with(&format!("{}.temp.", stem));
+
+            if is_partial {
+                ::tracing::debug!("Removing partial artifact `{}`", entry.path().display());
+
+                ::tokio::fs::remove_file(entry.path()).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct DiagnosticEventsCollector;
+
+impl DiagnosticEventsCollector {
+    async fn collect(mut lines: BoxedStream<MaybeOwnedString>) -> Vec<DiagnosticEvent> {
+        let mut events = Vec::new();
+
+        while let Some(line) = lines.next().await {
+            events.extend(DiagnosticEventParser::parse(line));
+        }
+
+        events
+    }
+}
+
 trait Deduplicator<Artifact> {
     fn deduplicate(artifact: Artifact) -> Artifact;
 }
@@ -705,11 +2090,61 @@ impl Deduplicator<PartiallyResolvedPlaylist> for PartiallyResolvedPlaylistDedupl
     }
 }
 
-struct PartiallyResolvedChannelDeduplicator;
+struct PartiallyResolvedChannelDeduplicator;
+
+impl Deduplicator<PartiallyResolvedChannel> for PartiallyResolvedChannelDeduplicator {
+    fn deduplicate(channel: PartiallyResolvedChannel) -> PartiallyResolvedChannel {
+        PartiallyResolvedChannel {
+            videos: channel.videos.map(|videos| {
+                videos
+                    .iter()
+                    .cloned()
+                    .map(|video| (video.id.clone(), video))
+                    .collect::<::indexmap::IndexMap<_, _>>()
+                    .into_values()
+                    .collect::<Vec<_>>()
+                    .into()
+            }),
+            playlists: channel.playlists.map(|playlists| {
+                playlists
+                    .iter()
+                    .cloned()
+                    .map(|playlist| (playlist.id.clone(), playlist))
+                    .collect::<::indexmap::IndexMap<_, _>>()
+                    .into_values()
+                    .collect::<Vec<_>>()
+                    .into()
+            }),
+            ..channel
+        }
+    }
+}
+
+struct PlannedPlaylistDeduplicator;
+
+impl Deduplicator<PlannedPlaylist> for PlannedPlaylistDeduplicator {
+    fn deduplicate(playlist: PlannedPlaylist) -> PlannedPlaylist {
+        PlannedPlaylist {
+            videos: playlist.videos.map(|videos| {
+                videos
+                    .iter()
+                    .cloned()
+                    .map(|video| (video.id.clone(), video))
+                    .collect::<::indexmap::IndexMap<_, _>>()
+                    .into_values()
+                    .collect::<Vec<_>>()
+                    .into()
+            }),
+            ..playlist
+        }
+    }
+}
+
+struct PlannedChannelDeduplicator;
 
-impl Deduplicator<PartiallyResolvedChannel> for PartiallyResolvedChannelDeduplicator {
-    fn deduplicate(channel: PartiallyResolvedChannel) -> PartiallyResolvedChannel {
-        PartiallyResolvedChannel {
+impl Deduplicator<PlannedChannel> for PlannedChannelDeduplicator {
+    fn deduplicate(channel: PlannedChannel) -> PlannedChannel {
+        PlannedChannel {
             videos: channel.videos.map(|videos| {
                 videos
                     .iter()
@@ -735,15 +2170,62 @@ impl Deduplicator<PartiallyResolvedChannel> for PartiallyResolvedChannelDeduplic
     }
 }
 
+pub(crate) struct DownloadedVideosPartitioner;
+
+impl DownloadedVideosPartitioner {
+    pub(crate) fn partition(
+        videos: &[UnresolvedVideo], downloaded_videos: &[ResolvedVideo],
+    ) -> (Vec<ResolvedVideo>, Vec<UnresolvedVideo>) {
+        let downloaded_videos = downloaded_videos
+            .iter()
+            .map(|video| (&video.id, video))
+            .collect::<::std::collections::HashMap<_, _>>();
+
+        let mut skipped_videos = Vec::new();
+        let mut pending_videos = Vec::new();
+
+        videos.iter().for_each(|video| match downloaded_videos.get(&video.id) {
+            Some(downloaded_video) => skipped_videos.push((*downloaded_video).clone()),
+            None => pending_videos.push(video.clone()),
+        });
+
+        (skipped_videos, pending_videos)
+    }
+}
+
+struct ListingOrderRestorer;
+
+impl ListingOrderRestorer {
+    // Videos come back in the order they finished downloading, so that they are
+    // put back where they were listed, and at the end when they no longer are.
+    // Each one keeps its index, so that tracks are numbered by the listing even
+    // when some of them were skipped or failed
+    fn restore(videos: &mut [ResolvedVideo], listed_videos: &[UnresolvedVideo]) {
+        let indices = listed_videos
+            .iter()
+            .enumerate()
+            .map(|(position, video)| (&video.id, video.index.unwrap_or(position as u64 + 1)))
+            .collect::<::std::collections::HashMap<_, _>>();
+
+        for video in videos.iter_mut() {
+            video.index = indices.get(&video.id).copied();
+        }
+
+        videos.sort_by_key(|video| video.index.unwrap_or(u64::MAX));
+    }
+}
+
+// Nothing when the line is meant for something else, and an error when it is
+// ours but could not be parsed
 trait FromYtdlpLine: ::core::marker::Send + ::core::marker::Sync {
-    fn from_line<Line>(line: Line) -> Option<Self>
+    fn from_line<Line>(line: Line) -> Fallible<Option<Self>>
     where
         Line: AsRef<str>,
         Self: Sized;
 }
 
 impl FromYtdlpLine for VideoDownloadEvent {
-    fn from_line<Line>(line: Line) -> Option<Self>
+    fn from_line<Line>(line: Line) -> Fallible<Option<Self>>
     where
         Line: AsRef<str>,
         Self: Sized,
@@ -752,134 +2234,123 @@ impl FromYtdlpLine for VideoDownloadEvent {
 
         let line = line.as_ref();
 
-        VideoDownloadProgressUpdatedEvent::from_line(line)
+        Ok(VideoDownloadProgressUpdatedEvent::from_line(line)?
             .map(Self::ProgressUpdated)
-            .or(VideoDownloadStartedEvent::from_line(line).map(Self::Started))
-            .or(VideoDownloadCompletedEvent::from_line(line).map(Self::Completed))
+            .or(VideoDownloadStartedEvent::from_line(line)?.map(Self::Started))
+            .or(VideoDownloadCompletedEvent::from_line(line)?.map(Self::Completed)))
     }
 }
 
 impl FromYtdlpLine for VideoDownloadStartedEvent {
-    fn from_line<Line>(line: Line) -> Option<Self>
+    fn from_line<Line>(line: Line) -> Fallible<Option<Self>>
     where
         Line: AsRef<str>,
         Self: Sized,
     {
-        let attrs = line.as_ref().strip_prefix("[video-started]")?.split(';');
-        let [id, url, title, album, artists, genres] = YtdlpAttributes::parse(attrs)?.into();
+        let Some(video) = YtdlpPayloadParser::parse::<YtdlpVideo>(line.as_ref(), "[video-started]")? else {
+            return Ok(None);
+        };
 
         ::tracing::debug!("Parsed line `{}` as `VideoDownloadStartedEvent`", line.as_ref());
 
-        Some(
+        Ok(Some(
             Self::builder()
                 .video(
                     PartiallyResolvedVideo::builder()
-                        .id(id.singlevalued()?)
-                        .url(url.singlevalued()?)
-                        .metadata(
-                            VideoMetadata::builder()
-                                .title(title.singlevalued())
-                                .album(album.singlevalued())
-                                .artists(artists.multivalued())
-                                .genres(genres.multivalued())
-                                .build(),
-                        )
+                        .id(video.id)
+                        .url(video.url)
+                        .metadata(video.metadata.into())
                         .build(),
                 )
                 .build(),
-        )
+        ))
     }
 }
 
 impl FromYtdlpLine for VideoDownloadProgressUpdatedEvent {
-    fn from_line<Line>(line: Line) -> Option<Self>
+    fn from_line<Line>(line: Line) -> Fallible<Option<Self>>
     where
         Line: AsRef<str>,
         Self: Sized,
     {
-        let attrs = line.as_ref().strip_prefix("[video-downloading]")?.split(';');
-        let [id, eta, elapsed, downloaded_bytes, total_bytes, bytes_per_second] = YtdlpAttributes::parse(attrs)?.into();
+        let Some(YtdlpProgress { id, progress }) =
+            YtdlpPayloadParser::parse::<YtdlpProgress>(line.as_ref(), "[video-downloading]")?
+        else {
+            return Ok(None);
+        };
+
+        // yt-dlp leaves out whatever it does not know yet, which is not worth a
+        // diagnostic
+        let (Some(eta), Some(elapsed), Some(downloaded_bytes), Some(total_bytes), Some(bytes_per_second)) =
+            (progress.eta, progress.elapsed, progress.downloaded_bytes, progress.total_bytes, progress.speed)
+        else {
+            return Ok(None);
+        };
 
         ::tracing::debug!("Parsed line `{}` as `VideoDownloadProgressUpdatedEvent`", line.as_ref());
 
-        Some(
+        Ok(Some(
             Self::builder()
-                .video_id(id.singlevalued()?)
-                .eta(::std::time::Duration::from_secs(eta.singlevalued()?.parse().ok()?))
-                .elapsed(::std::time::Duration::try_from_secs_f64(elapsed.singlevalued()?.parse().ok()?).ok()?)
-                .downloaded_bytes(downloaded_bytes.singlevalued()?.parse().ok()?)
-                .total_bytes(total_bytes.singlevalued()?.parse().ok()?)
-                .bytes_per_second(bytes_per_second.singlevalued()?.parse::<f64>().ok()?.floor() as u64)
+                .video_id(id)
+                .eta(::std::time::Duration::try_from_secs_f64(eta)?)
+                .elapsed(::std::time::Duration::try_from_secs_f64(elapsed)?)
+                .downloaded_bytes(downloaded_bytes.floor() as u64)
+                .total_bytes(total_bytes.floor() as u64)
+                .bytes_per_second(bytes_per_second.floor() as u64)
                 .build(),
-        )
+        ))
     }
 }
 
 impl FromYtdlpLine for VideoDownloadCompletedEvent {
-    fn from_line<Line>(line: Line) -> Option<Self>
+    fn from_line<Line>(line: Line) -> Fallible<Option<Self>>
     where
         Line: AsRef<str>,
         Self: Sized,
     {
-        let attrs = line.as_ref().strip_prefix("[video-completed]")?.split(';');
-        let [id, url, title, album, artists, genres, path] = YtdlpAttributes::parse(attrs)?.into();
+        let Some(YtdlpDownloadedVideo { video, filepath }) =
+            YtdlpPayloadParser::parse::<YtdlpDownloadedVideo>(line.as_ref(), "[video-completed]")?
+        else {
+            return Ok(None);
+        };
 
         ::tracing::debug!("Parsed line `{}` as `VideoDownloadCompletedEvent`", line.as_ref());
 
-        Some(
+        Ok(Some(
             Self::builder()
                 .video(
                     ResolvedVideo::builder()
-                        .id(id.singlevalued()?)
-                        .url(url.singlevalued()?)
-                        .metadata(
-                            VideoMetadata::builder()
-                                .title(title.singlevalued())
-                                .album(album.singlevalued())
-                                .artists(artists.multivalued())
-                                .genres(genres.multivalued())
-                                .build(),
-                        )
-                        .path(match path.singlevalued()? {
-                            MaybeOwnedString::Borrowed(path) => MaybeOwnedPath::Borrowed(path.as_ref()),
-                            MaybeOwnedString::Owned(path) => MaybeOwnedPath::Owned(path.into()),
-                        })
+                        .id(video.id)
+                        .url(video.url)
+                        .metadata(video.metadata.into())
+                        .path(filepath.into())
                         .build(),
                 )
                 .build(),
-        )
+        ))
     }
 }
 
-impl FromYtdlpLine for DiagnosticEvent {
-    fn from_line<Line>(line: Line) -> Option<Self>
+impl FromYtdlpLine for PlannedVideo {
+    fn from_line<Line>(line: Line) -> Fallible<Option<Self>>
     where
         Line: AsRef<str>,
         Self: Sized,
     {
-        ::tracing::debug!("Parsing line `{}` as `DiagnosticEvent`", line.as_ref());
-
-        let attrs = line.as_ref().splitn(2, ':');
-        let [level, message] = YtdlpAttributes::parse(attrs)?.into();
+        let Some(video) = YtdlpPayloadParser::parse::<YtdlpPlannedVideo>(line.as_ref(), "[video-planned]")? else {
+            return Ok(None);
+        };
 
-        ::tracing::debug!("Parsed line `{}` as `DiagnosticEvent`", line.as_ref());
+        ::tracing::debug!("Parsed line `{}` as `PlannedVideo`", line.as_ref());
 
-        Some(
-            Self::builder()
-                .level(match level.singlevalued()?.as_ref() {
-                    "WARNING" => DiagnosticLevel::Warning,
-                    "ERROR" => DiagnosticLevel::Error,
-                    _ => return None,
-                })
-                .message(message.singlevalued()?)
-                .build(),
-        )
+        Ok(Some(video.try_into()?))
     }
 }
 
+// Lines that could not be parsed are handed back as diagnostics alongside
 #[async_trait]
 trait FromYtdlpLines: ::core::marker::Send + ::core::marker::Sync {
-    async fn from_lines<Lines, Line>(lines: Lines) -> Option<Self>
+    async fn from_lines<Lines, Line>(lines: Lines) -> (Option<Self>, Vec<DiagnosticEvent>)
     where
         Lines: ::futures::Stream<Item = Line> + ::core::marker::Send,
         Line: AsRef<str>,
@@ -888,54 +2359,67 @@ trait FromYtdlpLines: ::core::marker::Send + ::core::marker::Sync {
 
 #[async_trait]
 impl FromYtdlpLines for PlaylistDownloadStartedEvent {
-    async fn from_lines<Lines, Line>(lines: Lines) -> Option<Self>
+    async fn from_lines<Lines, Line>(lines: Lines) -> (Option<Self>, Vec<DiagnosticEvent>)
     where
         Lines: ::futures::Stream<Item = Line> + ::core::marker::Send,
         Line: AsRef<str>,
         Self: Sized,
     {
-        let (mut playlist_id, mut playlist_url, mut playlist_title) = (None, None, None);
+        let mut metadata = None;
         let mut videos = Vec::new();
+        let mut diagnostic_events = Vec::new();
 
         ::futures::pin_mut!(lines);
 
         while let Some(line) = lines.next().await {
-            ::tracing::debug!("Parsing line `{}` as `PlaylistDownloadStartedEvent`", line.as_ref());
+            let line = line.as_ref();
 
-            if let Some(line) = line.as_ref().strip_prefix("[playlist-started:video]") {
-                let attrs = line.split(';');
-                let [id, url] = YtdlpAttributes::parse(attrs)?.into();
-
-                let video = UnresolvedVideo::builder()
-                    .id(id.singlevalued()?)
-                    .url(url.singlevalued()?)
-                    .build();
+            ::tracing::debug!("Parsing line `{}` as `PlaylistDownloadStartedEvent`", line);
 
-                videos.push(video);
-            } else if let Some(line) = line.as_ref().strip_prefix("[playlist-started:metadata]") {
-                let attrs = line.split(';');
-                let [id, url, title] = YtdlpAttributes::parse(attrs)?.into();
+            let parsed = YtdlpPayloadParser::parse::<YtdlpVideo>(line, "[playlist-started:video]");
 
-                playlist_id = id.singlevalued();
-                playlist_url = url.singlevalued();
-                playlist_title = title.singlevalued();
+            if let Some(video) = MalformedLineReporter::recover(line, parsed, &mut diagnostic_events) {
+                videos.push(
+                    UnresolvedVideo::builder()
+                        .id(video.id)
+                        .url(video.url)
+                        .maybe_index(video.playlist_index)
+                        .build(),
+                );
             }
+
+            let parsed = YtdlpPayloadParser::parse::<YtdlpCollection>(line, "[playlist-started:metadata]");
+            metadata = MalformedLineReporter::recover(line, parsed, &mut diagnostic_events).or(metadata);
         }
 
-        let playlist = PartiallyResolvedPlaylist::builder()
-            .id(playlist_id?)
-            .url(playlist_url?)
-            .metadata(PlaylistMetadata::builder().title(playlist_title).build())
-            .videos(videos.is_empty().not().then(|| videos.into()))
-            .build();
+        let event = metadata.map(|playlist| {
+            let thumbnail_url = playlist
+                .thumbnails
+                .and_then(|thumbnails| thumbnails.into_iter().last())
+                .map(|thumbnail| thumbnail.url);
+
+            Self {
+                playlist: PartiallyResolvedPlaylist::builder()
+                    .id(playlist.id)
+                    .url(playlist.url)
+                    .metadata(
+                        PlaylistMetadata::builder()
+                            .title(playlist.title)
+                            .maybe_thumbnail_url(thumbnail_url)
+                            .build(),
+                    )
+                    .videos(videos.is_empty().not().then(|| videos.into()))
+                    .build(),
+            }
+        });
 
-        Some(Self { playlist })
+        (event, diagnostic_events)
     }
 }
 
 #[async_trait]
 impl FromYtdlpLines for ChannelDownloadStartedEvent {
-    async fn from_lines<Lines, Line>(lines: Lines) -> Option<Self>
+    async fn from_lines<Lines, Line>(lines: Lines) -> (Option<Self>, Vec<DiagnosticEvent>)
     where
         Lines: ::futures::Stream<Item = Line> + ::core::marker::Send,
         Line: AsRef<str>,
@@ -944,91 +2428,654 @@ impl FromYtdlpLines for ChannelDownloadStartedEvent {
         let (mut channel_id, mut channel_url, mut channel_title) = (None, None, None);
         let mut videos = Vec::new();
         let mut playlists = Vec::new();
+        let mut diagnostic_events = Vec::new();
 
         ::futures::pin_mut!(lines);
 
         while let Some(line) = lines.next().await {
-            ::tracing::debug!("Parsing line `{}` as `ChannelDownloadStartedEvent`", line.as_ref());
+            let line = line.as_ref();
 
-            if let Some(line) = line.as_ref().strip_prefix("[channel-started:video]") {
-                let attrs = line.split(';');
-                let [video_id, video_url, channel_id_, channel_url_, channel_title_] =
-                    YtdlpAttributes::parse(attrs)?.into();
+            ::tracing::debug!("Parsing line `{}` as `ChannelDownloadStartedEvent`", line);
 
-                let video = UnresolvedVideo::builder()
-                    .id(video_id.singlevalued()?)
-                    .url(video_url.singlevalued()?)
-                    .build();
+            let parsed = YtdlpPayloadParser::parse::<YtdlpChannelVideo>(line, "[channel-started:video]");
+
+            if let Some(YtdlpChannelVideo { video, channel }) =
+                MalformedLineReporter::recover(line, parsed, &mut diagnostic_events)
+            {
+                videos.push(
+                    UnresolvedVideo::builder()
+                        .id(video.id)
+                        .url(video.url)
+                        .maybe_index(video.playlist_index)
+                        .build(),
+                );
+
+                channel_id = channel.channel_id.or(channel_id);
+                channel_url = channel.channel_url.or(channel_url);
+                channel_title = channel.channel.or(channel_title);
+            }
+
+            let parsed = YtdlpPayloadParser::parse::<YtdlpCollection>(line, "[channel-started:playlist]");
+
+            if let Some(playlist) = MalformedLineReporter::recover(line, parsed, &mut diagnostic_events) {
+                playlists.push(UnresolvedPlaylist::builder().id(playlist.id).url(playlist.url).build());
+            }
+        }
+
+        let event = channel_id.zip(channel_url).map(|(channel_id, channel_url)| Self {
+            channel: PartiallyResolvedChannel::builder()
+                .id(channel_id)
+                .url(channel_url)
+                .metadata(ChannelMetadata::builder().title(channel_title).build())
+                .videos(videos.is_empty().not().then(|| videos.into()))
+                .playlists(playlists.is_empty().not().then(|| playlists.into()))
+                .build(),
+        });
+
+        (event, diagnostic_events)
+    }
+}
+
+#[async_trait]
+impl FromYtdlpLines for PlannedPlaylist {
+    async fn from_lines<Lines, Line>(lines: Lines) -> (Option<Self>, Vec<DiagnosticEvent>)
+    where
+        Lines: ::futures::Stream<Item = Line> + ::core::marker::Send,
+        Line: AsRef<str>,
+        Self: Sized,
+    {
+        let mut metadata = None;
+        let mut videos = Vec::new();
+        let mut diagnostic_events = Vec::new();
+
+        ::futures::pin_mut!(lines);
+
+        while let Some(line) = lines.next().await {
+            let line = line.as_ref();
+
+            ::tracing::debug!("Parsing line `{}` as `PlannedPlaylist`", line);
+
+            let parsed = YtdlpPayloadParser::parse::<YtdlpPlannedVideo>(line, "[playlist-planned:video]")
+                .and_then(|video| video.map(PlannedVideo::try_from).transpose());
+            videos.extend(MalformedLineReporter::recover(line, parsed, &mut diagnostic_events));
+
+            let parsed = YtdlpPayloadParser::parse::<YtdlpCollection>(line, "[playlist-planned:metadata]");
+            metadata = MalformedLineReporter::recover(line, parsed, &mut diagnostic_events).or(metadata);
+        }
+
+        let playlist = metadata.map(|playlist| {
+            Self::builder()
+                .id(playlist.id)
+                .url(playlist.url)
+                .metadata(PlaylistMetadata::builder().title(playlist.title).build())
+                .videos(videos.is_empty().not().then(|| videos.into()))
+                .build()
+        });
+
+        (playlist, diagnostic_events)
+    }
+}
+
+#[async_trait]
+impl FromYtdlpLines for PlannedChannel {
+    async fn from_lines<Lines, Line>(lines: Lines) -> (Option<Self>, Vec<DiagnosticEvent>)
+    where
+        Lines: ::futures::Stream<Item = Line> + ::core::marker::Send,
+        Line: AsRef<str>,
+        Self: Sized,
+    {
+        let mut metadata = None;
+        let mut videos = Vec::new();
+        let mut playlists = Vec::new();
+        let mut diagnostic_events = Vec::new();
+
+        ::futures::pin_mut!(lines);
 
-                videos.push(video);
+        while let Some(line) = lines.next().await {
+            let line = line.as_ref();
+
+            ::tracing::debug!("Parsing line `{}` as `PlannedChannel`", line);
 
-                channel_id = channel_id_.singlevalued();
-                channel_url = channel_url_.singlevalued();
-                channel_title = channel_title_.singlevalued();
-            } else if let Some(line) = line.as_ref().strip_prefix("[channel-started:playlist]") {
-                let attrs = line.split(';');
-                let [id, url] = YtdlpAttributes::parse(attrs)?.into();
+            let parsed = YtdlpPayloadParser::parse::<YtdlpPlannedVideo>(line, "[channel-planned:video]")
+                .and_then(|video| video.map(PlannedVideo::try_from).transpose());
+            videos.extend(MalformedLineReporter::recover(line, parsed, &mut diagnostic_events));
 
-                let playlist = UnresolvedPlaylist::builder()
-                    .id(id.singlevalued()?)
-                    .url(url.singlevalued()?)
+            let parsed = YtdlpPayloadParser::parse::<YtdlpCollection>(line, "[channel-planned:playlist]");
+
+            if let Some(playlist) = MalformedLineReporter::recover(line, parsed, &mut diagnostic_events) {
+                let playlist = PlannedPlaylist::builder()
+                    .id(playlist.id)
+                    .url(playlist.url)
+                    .metadata(PlaylistMetadata::builder().title(None).build())
+                    .videos(None)
                     .build();
 
                 playlists.push(playlist);
             }
+
+            let parsed = YtdlpPayloadParser::parse::<YtdlpCollection>(line, "[channel-planned:metadata]");
+            metadata = MalformedLineReporter::recover(line, parsed, &mut diagnostic_events).or(metadata);
         }
 
-        let channel = PartiallyResolvedChannel::builder()
-            .id(channel_id?)
-            .url(channel_url?)
-            .metadata(ChannelMetadata::builder().title(channel_title).build())
-            .videos(videos.is_empty().not().then(|| videos.into()))
-            .playlists(playlists.is_empty().not().then(|| playlists.into()))
-            .build();
+        // Channel tabs describe themselves either as the channel or as the tab
+        let channel = metadata.map(|channel| {
+            Self::builder()
+                .id(channel.channel.channel_id.unwrap_or(channel.id))
+                .url(channel.channel.channel_url.unwrap_or(channel.url))
+                .metadata(
+                    ChannelMetadata::builder()
+                        .title(channel.channel.channel.or(channel.title))
+                        .build(),
+                )
+                .videos(videos.is_empty().not().then(|| videos.into()))
+                .playlists(playlists.is_empty().not().then(|| playlists.into()))
+                .build()
+        });
+
+        (channel, diagnostic_events)
+    }
+}
+
+struct DiagnosticEventParser;
+
+impl DiagnosticEventParser {
+    fn parse<Line>(line: Line) -> Option<DiagnosticEvent>
+    where
+        Line: AsRef<str>,
+    {
+        ::tracing::debug!("Parsing line `{}` as `DiagnosticEvent`", line.as_ref());
+
+        let (level, message) = line.as_ref().split_once(':')?;
+
+        let level = match level.trim() {
+            "WARNING" => DiagnosticLevel::Warning,
+            "ERROR" => DiagnosticLevel::Error,
+            _ => return None,
+        };
+
+        ::tracing::debug!("Parsed line `{}` as `DiagnosticEvent`", line.as_ref());
+
+        Some(
+            DiagnosticEvent::builder()
+                .level(level)
+                .message(message.trim().to_owned().into())
+                .build(),
+        )
+    }
+}
+
+struct MalformedLineReporter;
+
+impl MalformedLineReporter {
+    fn report(line: &str, error: ::anyhow::Error) -> DiagnosticEvent {
+        ::tracing::warn!("Failed to parse line `{}` `{}`", line, error);
+
+        DiagnosticEvent::builder()
+            .level(DiagnosticLevel::Warning)
+            .message(format!("Skipped malformed yt-dlp output `{}`: {}", line.trim(), error).into())
+            .build()
+    }
+
+    fn recover<Payload>(
+        line: &str, parsed: Fallible<Option<Payload>>, diagnostic_events: &mut Vec<DiagnosticEvent>,
+    ) -> Option<Payload> {
+        parsed.unwrap_or_else(|error| {
+            diagnostic_events.push(Self::report(line, error));
+
+            None
+        })
+    }
+}
+
+struct YtdlpPayloadParser;
+
+impl YtdlpPayloadParser {
+    fn parse<Payload>(line: &str, prefix: &str) -> Fallible<Option<Payload>>
+    where
+        Payload: ::serde::de::DeserializeOwned,
+    {
+        let Some(payload) = line.strip_prefix(prefix) else {
+            return Ok(None);
+        };
 
-        Some(Self { channel })
+        Ok(Some(::serde_json::from_str(payload.trim())?))
     }
 }
 
+// Fields yt-dlp does not know are left out of the printed JSON, or come out as
+// `null`, either of which ends up as `None`
 #[derive(Debug, Clone)]
-struct YtdlpAttribute<'a>(&'a str);
+#[derive(::serde::Deserialize)]
+struct YtdlpVideo {
+    id: MaybeOwnedString,
 
-impl<'a> YtdlpAttribute<'a> {
-    fn singlevalued(self) -> Option<MaybeOwnedString> {
-        match self.0.trim() {
-            "NA" => None,
-            attr => Some(attr.to_owned().into()),
-        }
+    #[serde(alias = "original_url", alias = "webpage_url")]
+    url: MaybeOwnedString,
+
+    playlist_index: Option<u64>,
+
+    #[serde(flatten)]
+    metadata: YtdlpVideoMetadata,
+}
+
+#[derive(Debug, Clone)]
+#[derive(::serde::Deserialize)]
+struct YtdlpVideoMetadata {
+    title: Option<MaybeOwnedString>,
+    album: Option<MaybeOwnedString>,
+    artists: Option<Vec<MaybeOwnedString>>,
+    genres: Option<Vec<MaybeOwnedString>>,
+}
+
+impl From<YtdlpVideoMetadata> for VideoMetadata {
+    fn from(metadata: YtdlpVideoMetadata) -> Self {
+        VideoMetadata::builder()
+            .title(metadata.title)
+            .album(metadata.album)
+            .artists(metadata.artists.map(Into::into))
+            .genres(metadata.genres.map(Into::into))
+            .build()
     }
+}
 
-    fn multivalued(self) -> Option<MaybeOwnedVec<MaybeOwnedString>> {
-        let attrs = self
-            .0
-            .split(',')
-            .map(YtdlpAttribute)
-            .filter_map(Self::singlevalued)
-            .collect::<Vec<_>>();
+#[derive(Debug, Clone)]
+#[derive(::serde::Deserialize)]
+struct YtdlpDownloadedVideo {
+    #[serde(flatten)]
+    video: YtdlpVideo,
 
-        Some(attrs.into())
+    filepath: ::std::path::PathBuf,
+}
+
+#[derive(Debug, Clone)]
+#[derive(::serde::Deserialize)]
+struct YtdlpPlannedVideo {
+    #[serde(flatten)]
+    video: YtdlpVideo,
+
+    duration: Option<f64>,
+    filesize: Option<f64>,
+    filesize_approx: Option<f64>,
+    filename: Option<::std::path::PathBuf>,
+}
+
+impl TryFrom<YtdlpPlannedVideo> for PlannedVideo {
+    type Error = ::anyhow::Error;
+
+    fn try_from(planned_video: YtdlpPlannedVideo) -> Fallible<Self> {
+        let YtdlpPlannedVideo {
+            video,
+            duration,
+            filesize,
+            filesize_approx,
+            filename,
+        } = planned_video;
+
+        Ok(PlannedVideo::builder()
+            .id(video.id)
+            .url(video.url)
+            .metadata(
+                VideoMetadata::builder()
+                    .title(video.metadata.title)
+                    .album(None)
+                    .artists(None)
+                    .genres(None)
+                    .build(),
+            )
+            .path(filename.map(Into::into))
+            .duration(duration.map(::std::time::Duration::try_from_secs_f64).transpose()?)
+            .estimated_bytes(
+                filesize
+                    .or(filesize_approx)
+                    .map(|estimated_bytes| estimated_bytes.floor() as u64),
+            )
+            .build())
     }
 }
 
 #[derive(Debug, Clone)]
-struct YtdlpAttributes<'a, const N: usize>([YtdlpAttribute<'a>; N]);
+#[derive(::serde::Deserialize)]
+struct YtdlpChannel {
+    channel_id: Option<MaybeOwnedString>,
+    channel_url: Option<MaybeOwnedString>,
+    channel: Option<MaybeOwnedString>,
+}
+
+#[derive(Debug, Clone)]
+#[derive(::serde::Deserialize)]
+struct YtdlpChannelVideo {
+    #[serde(flatten)]
+    video: YtdlpVideo,
+
+    #[serde(flatten)]
+    channel: YtdlpChannel,
+}
+
+#[derive(Debug, Clone)]
+#[derive(::serde::Deserialize)]
+struct YtdlpCollection {
+    id: MaybeOwnedString,
+
+    #[serde(alias = "original_url", alias = "webpage_url")]
+    url: MaybeOwnedString,
+
+    title: Option<MaybeOwnedString>,
+
+    // yt-dlp lists the thumbnails from the worst to the best
+    thumbnails: Option<Vec<YtdlpThumbnail>>,
+
+    #[serde(flatten)]
+    channel: YtdlpChannel,
+}
+
+#[derive(Debug, Clone)]
+#[derive(::serde::Deserialize)]
+struct YtdlpThumbnail {
+    url: MaybeOwnedString,
+}
+
+#[derive(Debug, Clone)]
+#[derive(::serde::Deserialize)]
+struct YtdlpProgress {
+    id: MaybeOwnedString,
+    progress: YtdlpProgressStatus,
+}
+
+#[derive(Debug, Clone)]
+#[derive(::serde::Deserialize)]
+struct YtdlpProgressStatus {
+    eta: Option<f64>,
+    elapsed: Option<f64>,
+    downloaded_bytes: Option<f64>,
+    total_bytes: Option<f64>,
+    speed: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+struct FeedDocument {
+    metadata: PlaylistMetadata,
+    episodes: Vec<FeedEpisode>,
+}
+
+#[derive(Debug, Clone)]
+struct FeedEpisode {
+    video: PartiallyResolvedVideo,
+
+    stem: MaybeOwnedString,
+    // Of the enclosure as published, before any transcoding
+    extension: MaybeOwnedString,
+
+    duration: Option<::std::time::Duration>,
+    length: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct FeedFields {
+    id: Option<::std::string::String>,
+    url: Option<::std::string::String>,
+    title: Option<::std::string::String>,
+    author: Option<::std::string::String>,
+    duration: Option<::std::string::String>,
+    length: Option<u64>,
+    media_type: Option<::std::string::String>,
+}
+
+struct FeedDocumentParser;
+
+impl FeedDocumentParser {
+    // Both RSS `<channel>`/`<item>` and Atom `<feed>`/`<entry>` documents, keeping
+    // only the entries with an audio enclosure
+    fn parse(document: &str) -> Fallible<FeedDocument> {
+        use ::quick_xml::events::Event;
+
+        let mut reader = ::quick_xml::Reader::from_str(document);
+
+        let mut elements: Vec<::std::string::String> = Vec::new();
+        let mut text = ::std::string::String::new();
 
-impl<'a, const N: usize> From<YtdlpAttributes<'a, N>> for [YtdlpAttribute<'a>; N] {
-    fn from(outer: YtdlpAttributes<'a, N>) -> Self {
-        outer.0
+        let mut feed = FeedFields::default();
+        let mut entry: Option<FeedFields> = None;
+        let mut entries = Vec::new();
+
+        loop {
+            match reader.read_event()? {
+                Event::Start(element) => {
+                    let name = element.local_name().as_ref().to_owned();
+
+                    if matches!(name.as_str(), "item" | "entry") {
+                        entry = Some(FeedFields::default());
+                    }
+
+                    if let Some(entry) = entry.as_mut() {
+                        Self::enclosure(&element, entry)?;
+                    }
+
+                    elements.push(name);
+                    text.clear();
+                },
+                Event::Empty(element) =>
+                    if let Some(entry) = entry.as_mut() {
+                        Self::enclosure(&element, entry)?;
+                    },
+                Event::Text(content) => text.push_str(&content.xml_content(::quick_xml::XmlVersion::default())),
+                Event::CData(content) => text.push_str(&content.xml_content(::quick_xml::XmlVersion::default())),
+                Event::GeneralRef(reference) => match reference.resolve_char_ref()? {
+                    Some(character) => text.push(character),
+                    None => text.push_str(
+                        ::quick_xml::escape::resolve_predefined_entity(
+                            &reference.xml_content(::quick_xml::XmlVersion::default()),
+                        )
+                        .unwrap_or_default(),
+                    ),
+                },
+                Event::End(_) => {
+                    let name = elements.pop().unwrap_or_default();
+                    let parent = elements.last().map(::std::string::String::as_str).unwrap_or_default();
+
+                    let value = text.trim().to_owned();
+                    text.clear();
+
+                    if matches!(name.as_str(), "item" | "entry") {
+                        entries.extend(entry.take());
+
+                        continue;
+                    }
+
+                    let fields = entry.as_mut().unwrap_or(&mut feed);
+
+                    let field = match (name.as_str(), parent) {
+                        ("title", "channel" | "feed" | "item" | "entry") => &mut fields.title,
+                        ("guid" | "id", "item" | "entry") => &mut fields.id,
+                        ("author" | "creator", "channel" | "feed" | "item" | "entry") | ("name", "author") =>
+                            &mut fields.author,
+                        ("duration", "item" | "entry") => &mut fields.duration,
+                        _ => continue,
+                    };
+
+                    if field.is_none() && value.is_empty().not() {
+                        *field = Some(value);
+                    }
+                },
+                Event::Eof => break,
+                _ => {},
+            }
+        }
+
+        let episodes = entries
+            .into_iter()
+            .filter_map(|entry| entry.into_episode(&feed))
+            .map(|episode| (episode.video.id.clone(), episode))
+            .collect::<::indexmap::IndexMap<_, _>>()
+            .into_values()
+            .collect();
+
+        let metadata = PlaylistMetadata::builder().title(feed.title.map(Into::into)).build();
+
+        Ok(FeedDocument { metadata, episodes })
+    }
+
+    fn enclosure(element: &::quick_xml::events::BytesStart<'_>, entry: &mut FeedFields) -> Fallible<()> {
+        if entry.url.is_some() {
+            return Ok(());
+        }
+
+        let url = match element.local_name().as_ref() {
+            "enclosure" => Self::attribute(element, "url")?,
+            "link" if Self::attribute(element, "rel")?.as_deref() == Some("enclosure") =>
+                Self::attribute(element, "href")?,
+            _ => return Ok(()),
+        };
+
+        // Video enclosures are left for a later audio one, since only audio ends up
+        // on the device
+        let media_type = Self::attribute(element, "type")?;
+
+        if media_type
+            .as_deref()
+            .is_some_and(|media_type| media_type.starts_with("audio/").not())
+        {
+            return Ok(());
+        }
+
+        entry.url = url;
+        entry.length = Self::attribute(element, "length")?
+            .and_then(|length| length.trim().parse().ok())
+            .filter(|length| *length > 0);
+        entry.media_type = media_type;
+
+        Ok(())
+    }
+
+    fn attribute(element: &::quick_xml::events::BytesStart<'_>, name: &str) -> Fallible<Option<::std::string::String>> {
+        let Some(attribute) = element.try_get_attribute(name)? else {
+            return Ok(None);
+        };
+
+        let value = attribute.normalized_value(::quick_xml::XmlVersion::default())?;
+
+        Ok(Some(value.into_owned()))
     }
 }
 
-impl<'a, const N: usize> YtdlpAttributes<'a, N> {
-    fn parse<Attrs>(attrs: Attrs) -> Option<Self>
-    where
-        Attrs: Iterator<Item = &'a str>,
-    {
-        let attrs = attrs.map(YtdlpAttribute).collect::<Vec<_>>().try_into().ok()?;
+impl FeedFields {
+    fn into_episode(self, feed: &FeedFields) -> Option<FeedEpisode> {
+        let url = self.url?;
+        let id = self.id.unwrap_or_else(|| url.clone());
+
+        let stem = MediaFileNamer::stem([self.title.as_deref(), Some(&id)]);
+        let extension = MediaFileNamer::extension(&url, self.media_type.as_deref());
+
+        let metadata = VideoMetadata::builder()
+            .title(self.title.map(Into::into))
+            .album(feed.title.clone().map(Into::into))
+            .artists(
+                self.author
+                    .or_else(|| feed.author.clone())
+                    .map(|author| vec![author.into()].into()),
+            )
+            .genres(None)
+            .build();
+
+        let video = PartiallyResolvedVideo::builder()
+            .id(id.into())
+            .url(url.into())
+            .metadata(metadata)
+            .build();
+
+        Some(FeedEpisode {
+            video,
+            stem: stem.into(),
+            extension: extension.into(),
+            duration: self.duration.as_deref().and_then(FeedDurationParser::parse),
+            length: self.length,
+        })
+    }
+}
+
+pub(crate) struct MediaFileNamer;
+
+impl MediaFileNamer {
+    // Restricted the same way yt-dlp's `--restrict-filenames` is, so that episodes
+    // sit well next to videos
+    const MAX_LENGTH: usize = 128;
+
+    fn stem<'a>(candidates: impl IntoIterator<Item = Option<&'a str>>) -> ::std::string::String {
+        candidates
+            .into_iter()
+            .flatten()
+            .map(Self::restrict)
+            .find(|stem| stem.is_empty().not())
+            .unwrap_or_else(|| "episode".to_owned())
+    }
+
+    // Capped for callers that know nothing of the device's limits
+    pub(crate) fn sanitize(name: &str) -> ::std::string::String {
+        Self::truncate(&Self::restrict(name), Self::MAX_LENGTH)
+    }
+
+    fn restrict(name: &str) -> ::std::string::String {
+        let name = name
+            .chars()
+            .map(|character| match character {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '.' => character,
+                _ => '_',
+            })
+            .collect::<::std::string::String>();
+
+        name.split('_')
+            .filter(|part| part.is_empty().not())
+            .collect::<Vec<_>>()
+            .join("_")
+            .trim_matches('.')
+            .to_owned()
+    }
+
+    // Restricted names are ASCII, so that characters and bytes count the same
+    pub(crate) fn truncate(name: &str, max_length: usize) -> ::std::string::String {
+        name.chars()
+            .take(max_length)
+            .collect::<::std::string::String>()
+            .trim_end_matches(['.', '_'])
+            .to_owned()
+    }
+
+    fn extension(url: &str, media_type: Option<&str>) -> ::std::string::String {
+        let path = url.split(['?', '#']).next().unwrap_or_default();
+
+        let extension = path
+            .rsplit('/')
+            .next()
+            .and_then(|name| name.rsplit_once('.'))
+            .map(|(_, extension)| extension)
+            .filter(|extension| {
+                (1..=4).contains(&extension.len())
+                    && extension.chars().all(|character| character.is_ascii_alphanumeric())
+            });
+
+        match (extension, media_type) {
+            (Some(extension), _) => extension.to_ascii_lowercase(),
+            (None, Some("audio/mp4" | "audio/x-m4a" | "audio/aac")) => "m4a".to_owned(),
+            (None, Some("audio/ogg")) => "ogg".to_owned(),
+            (None, _) => "mp3".to_owned(),
+        }
+    }
+}
+
+struct FeedDurationParser;
+
+impl FeedDurationParser {
+    // Either plain seconds or `[HH:]MM:SS`
+    fn parse(duration: &str) -> Option<::std::time::Duration> {
+        let parts = duration.trim().split(':').collect::<Vec<_>>();
+
+        if parts.len() > 3 {
+            return None;
+        }
+
+        let seconds = parts
+            .iter()
+            .try_fold(0_f64, |seconds, part| Some(seconds * 60.0 + part.trim().parse::<f64>().ok()?))?;
 
-        Some(Self(attrs))
+        (seconds.is_finite() && seconds >= 0.0).then(|| ::std::time::Duration::from_secs_f64(seconds))
     }
 }
@@ -0,0 +1,415 @@
+use ::futures::prelude::*;
+use ::std::ops::Not;
+
+use crate::utils::aliases::BoxedFuture;
+use crate::utils::aliases::BoxedStream;
+use crate::utils::aliases::Fallible;
+use crate::utils::aliases::MaybeOwnedPath;
+use crate::utils::aliases::MaybeOwnedString;
+use crate::utils::extensions::OptionExt;
+
+// Standard output, standard error and the exit status, which is missing when
+// the process did not exit on its own
+pub type CommandOutput = (BoxedStream<MaybeOwnedString>, BoxedStream<MaybeOwnedString>, BoxedFuture<Option<i32>>);
+
+pub trait CommandExecutor: ::core::marker::Send + ::core::marker::Sync {
+    fn execute(&self, program: &str, args: &[&str]) -> Fallible<CommandOutput>;
+
+    fn execute_all(&self, commands: &[(&str, &[&str])]) -> Fallible<CommandOutput> {
+        let mut stdouts = Vec::with_capacity(commands.len());
+        let mut stderrs = Vec::with_capacity(commands.len());
+        let mut statuses = Vec::with_capacity(commands.len());
+
+        // A command that could not be spawned fails them all, so that a partial
+        // output never passes for a complete one
+        commands
+            .iter()
+            .map(|(program, args)| self.execute(program, args))
+            .collect::<Fallible<Vec<_>>>()?
+            .into_iter()
+            .for_each(|(stdout, stderr, status)| {
+                stdouts.push(stdout);
+                stderrs.push(stderr);
+                statuses.push(status);
+            });
+
+        // The first unsuccessful command speaks for all of them
+        let status = ::futures::future::join_all(statuses)
+            .map(|statuses| statuses.into_iter().find(|status| *status != Some(0)).unwrap_or(Some(0)));
+
+        Ok((
+            ::std::boxed::Box::pin(::futures::stream::select_all(stdouts)),
+            ::std::boxed::Box::pin(::futures::stream::select_all(stderrs)),
+            ::std::boxed::Box::pin(status),
+        ))
+    }
+}
+
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct TokioCommandExecutor {
+    #[builder(default)]
+    cancellation: ::tokio_util::sync::CancellationToken,
+
+    #[builder(default)]
+    process_groups: ::std::sync::Arc<LiveProcessGroups>,
+}
+
+impl TokioCommandExecutor {
+    const LINE_BUFFER: usize = 64;
+}
+
+impl CommandExecutor for TokioCommandExecutor {
+    fn execute(&self, program: &str, args: &[&str]) -> Fallible<CommandOutput> {
+        use ::tokio::io::AsyncBufReadExt as _;
+
+        if self.cancellation.is_cancelled() {
+            ::anyhow::bail!("Refused to execute `{}` after cancellation", program);
+        }
+
+        let (stdout_tx, stdout_rx) = ::tokio::sync::mpsc::channel(Self::LINE_BUFFER);
+        let (stderr_tx, stderr_rx) = ::tokio::sync::mpsc::channel(Self::LINE_BUFFER);
+        let (status_tx, status_rx) = ::tokio::sync::oneshot::channel();
+
+        let mut command = ::tokio::process::Command::new(program);
+        command
+            .args(args)
+            .stdout(::std::process::Stdio::piped())
+            .stderr(::std::process::Stdio::piped())
+            .kill_on_drop(true);
+
+        // Keeps children out of the terminal's foreground process group, so that only
+        // we get to tear them down
+        #[cfg(unix)]
+        command.process_group(0);
+
+        let mut process = command.spawn()?;
+
+        let stdout = process.stdout.take().ok()?;
+        let stderr = process.stderr.take().ok()?;
+
+        let pid = process.id();
+        self.process_groups.insert(pid);
+
+        ::tokio::spawn({
+            let cancellation = self.cancellation.clone();
+            let process_groups = ::std::sync::Arc::clone(&self.process_groups);
+
+            async move {
+                let status = ::tokio::select! {
+                    status = process.wait() => Some(status),
+                    _ = cancellation.cancelled() => None,
+                };
+
+                let status = match status {
+                    Some(status) => status.map(|status| status.code()).map_err(Into::into),
+                    None => ProcessTreeTerminator::terminate(&mut process).await.map(|()| None),
+                };
+
+                process_groups.remove(pid);
+
+                let status = status?;
+
+                // Nobody may be waiting for the exit status
+                status_tx.send(status).ok();
+
+                Fallible::Ok(())
+            }
+        });
+
+        ::tokio::spawn(async move {
+            let lines = ::tokio::io::BufReader::new(stdout).lines();
+
+            ::tokio_stream::wrappers::LinesStream::new(lines)
+                .filter_map(|line| async move { line.ok() })
+                .map(|line| line.to_owned().into())
+                .map(Ok)
+                .try_for_each(|line| async { stdout_tx.send(line).await })
+                .await
+        });
+
+        ::tokio::spawn(async move {
+            let lines = ::tokio::io::BufReader::new(stderr).lines();
+
+            ::tokio_stream::wrappers::LinesStream::new(lines)
+                .filter_map(|line| async move { line.ok() })
+                .map(|line| line.to_owned().into())
+                .map(Ok)
+                .try_for_each(|line| async { stderr_tx.send(line).await })
+                .await
+        });
+
+        Ok((
+            ::std::boxed::Box::pin(::tokio_stream::wrappers::ReceiverStream::new(stdout_rx)),
+            ::std::boxed::Box::pin(::tokio_stream::wrappers::ReceiverStream::new(stderr_rx)),
+            ::std::boxed::Box::pin(status_rx.map(|status| status.ok().flatten())),
+        ))
+    }
+}
+
+// Passes commands through to another executor and appends what they printed to
+// a fixture file, one JSON document per line
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct RecordingCommandExecutor {
+    executor: ::std::sync::Arc<dyn CommandExecutor>,
+
+    path: MaybeOwnedPath,
+
+    #[builder(skip)]
+    appending: ::std::sync::Arc<::tokio::sync::Mutex<()>>,
+}
+
+impl RecordingCommandExecutor {
+    const LINE_BUFFER: usize = 64;
+
+    async fn forward(
+        lines: BoxedStream<MaybeOwnedString>, tx: ::tokio::sync::mpsc::Sender<MaybeOwnedString>,
+    ) -> Vec<MaybeOwnedString> {
+        let mut recorded_lines = Vec::new();
+
+        ::futures::pin_mut!(lines);
+
+        while let Some(line) = lines.next().await {
+            // Lines are recorded even when nobody is listening anymore
+            tx.send(line.clone()).await.ok();
+            recorded_lines.push(line);
+        }
+
+        recorded_lines
+    }
+}
+
+impl CommandExecutor for RecordingCommandExecutor {
+    fn execute(&self, program: &str, args: &[&str]) -> Fallible<CommandOutput> {
+        let (stdout, stderr, status) = self.executor.execute(program, args)?;
+
+        let (stdout_tx, stdout_rx) = ::tokio::sync::mpsc::channel(Self::LINE_BUFFER);
+        let (stderr_tx, stderr_rx) = ::tokio::sync::mpsc::channel(Self::LINE_BUFFER);
+        let (status_tx, status_rx) = ::tokio::sync::oneshot::channel();
+
+        let program = MaybeOwnedString::from(program.to_owned());
+        let args = args
+            .iter()
+            .map(|arg| MaybeOwnedString::from((*arg).to_owned()))
+            .collect::<Vec<_>>();
+
+        let path = self.path.clone();
+        let appending = ::std::sync::Arc::clone(&self.appending);
+
+        ::tokio::spawn(async move {
+            let (stdout, stderr, status) =
+                ::tokio::join!(Self::forward(stdout, stdout_tx), Self::forward(stderr, stderr_tx), status);
+
+            let fixture = CommandFixture::builder()
+                .program(program)
+                .args(args)
+                .stdout(stdout)
+                .stderr(stderr)
+                .status(status)
+                .build();
+
+            // The fixture is complete before the exit status is handed over
+            if let Err(error) = fixture.append(&path, &appending).await {
+                ::tracing::error!("Failed to record command `{:?}` `{}`", fixture, error);
+            }
+
+            status_tx.send(status).ok();
+        });
+
+        Ok((
+            ::std::boxed::Box::pin(::tokio_stream::wrappers::ReceiverStream::new(stdout_rx)),
+            ::std::boxed::Box::pin(::tokio_stream::wrappers::ReceiverStream::new(stderr_rx)),
+            ::std::boxed::Box::pin(status_rx.map(|status| status.ok().flatten())),
+        ))
+    }
+}
+
+// Plays back the fixtures written by `RecordingCommandExecutor`, where
+// identical commands are answered in the order they were recorded
+#[derive(::bon::Builder)]
+#[builder(on(_, into), finish_fn(name = _build, vis = "pub(self)"))]
+pub struct ReplayCommandExecutor {
+    #[builder(getter(vis = "pub(self)"))]
+    path: MaybeOwnedPath,
+
+    #[builder(default)]
+    cancellation: ::tokio_util::sync::CancellationToken,
+
+    #[builder(skip)]
+    fixtures: ::std::sync::Mutex<Vec<Option<CommandFixture>>>,
+}
+
+impl<BuilderState> ReplayCommandExecutorBuilder<BuilderState>
+where
+    BuilderState: replay_command_executor_builder::IsComplete,
+{
+    pub async fn build(self) -> Fallible<ReplayCommandExecutor>
+    where
+        BuilderState::Path: replay_command_executor_builder::IsSet,
+    {
+        let fixtures = CommandFixture::load(self.get_path()).await?;
+
+        let output = self._build();
+
+        *output.fixtures.lock().unwrap_or_else(::std::sync::PoisonError::into_inner) =
+            fixtures.into_iter().map(Some).collect();
+
+        Ok(output)
+    }
+}
+
+impl CommandExecutor for ReplayCommandExecutor {
+    fn execute(&self, program: &str, args: &[&str]) -> Fallible<CommandOutput> {
+        if self.cancellation.is_cancelled() {
+            ::anyhow::bail!("Refused to execute `{}` after cancellation", program);
+        }
+
+        let fixture = self
+            .fixtures
+            .lock()
+            .unwrap_or_else(::std::sync::PoisonError::into_inner)
+            .iter_mut()
+            .find_map(|fixture| fixture.take_if(|fixture| fixture.matches(program, args)));
+
+        let Some(fixture) = fixture else {
+            ::anyhow::bail!("No fixture in `{}` for `{} {}`", self.path.display(), program, args.join(" "));
+        };
+
+        Ok((
+            ::std::boxed::Box::pin(::futures::stream::iter(fixture.stdout)),
+            ::std::boxed::Box::pin(::futures::stream::iter(fixture.stderr)),
+            ::std::boxed::Box::pin(::futures::future::ready(fixture.status)),
+        ))
+    }
+}
+
+#[derive(Debug, Clone)]
+#[derive(::bon::Builder)]
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+struct CommandFixture {
+    program: MaybeOwnedString,
+    args: Vec<MaybeOwnedString>,
+
+    stdout: Vec<MaybeOwnedString>,
+    stderr: Vec<MaybeOwnedString>,
+
+    #[builder(required)]
+    status: Option<i32>,
+}
+
+impl CommandFixture {
+    // Their values follow from where the commands run, so that fixtures recorded
+    // in one directory are replayed in any other
+    const LOCATION_DEPENDENT_OPTIONS: &[&str] = &["--paths", "--trim-filenames"];
+
+    fn matches(&self, program: &str, args: &[&str]) -> bool {
+        *self.program == *program
+            && Self::normalize(self.args.iter().map(|arg| &**arg)).eq(Self::normalize(args.iter().copied()))
+    }
+
+    fn normalize<'a>(args: impl Iterator<Item = &'a str>) -> impl Iterator<Item = Option<&'a str>> {
+        args.scan(false, |location_dependent, arg| {
+            let normalized = location_dependent.not().then_some(arg);
+            *location_dependent = Self::LOCATION_DEPENDENT_OPTIONS.contains(&arg);
+
+            Some(normalized)
+        })
+    }
+
+    async fn load(path: &::std::path::Path) -> Fallible<Vec<Self>> {
+        let fixtures = ::tokio::fs::read_to_string(path).await?;
+
+        fixtures
+            .lines()
+            .filter(|line| line.trim().is_empty().not())
+            .map(|line| Ok(::serde_json::from_str(line)?))
+            .collect()
+    }
+
+    async fn append(&self, path: &::std::path::Path, appending: &::tokio::sync::Mutex<()>) -> Fallible<()> {
+        use ::tokio::io::AsyncWriteExt as _;
+
+        let mut line = ::serde_json::to_string(self)?;
+        line.push('\n');
+
+        let _appending = appending.lock().await;
+
+        ::tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await?
+            .write_all(line.as_bytes())
+            .await?;
+
+        Ok(())
+    }
+}
+
+// Every child still running, each leading a process group of its own, so that a
+// forced exit can take them down without waiting on any of them
+#[derive(Default)]
+pub struct LiveProcessGroups(::std::sync::Mutex<::std::collections::HashSet<u32>>);
+
+impl LiveProcessGroups {
+    pub fn terminate(&self) {
+        for pid in self.lock().drain() {
+            ProcessTreeTerminator::kill(pid).ok();
+        }
+    }
+
+    fn insert(&self, pid: Option<u32>) {
+        if let Some(pid) = pid {
+            self.lock().insert(pid);
+        }
+    }
+
+    fn remove(&self, pid: Option<u32>) {
+        if let Some(pid) = pid {
+            self.lock().remove(&pid);
+        }
+    }
+
+    fn lock(&self) -> ::std::sync::MutexGuard<'_, ::std::collections::HashSet<u32>> {
+        self.0.lock().unwrap_or_else(::std::sync::PoisonError::into_inner)
+    }
+}
+
+struct ProcessTreeTerminator;
+
+impl ProcessTreeTerminator {
+    async fn terminate(process: &mut ::tokio::process::Child) -> Fallible<()> {
+        let Some(pid) = process.id() else {
+            return Ok(());
+        };
+
+        #[cfg(any(unix, windows))]
+        Self::kill(pid)?;
+
+        #[cfg(not(any(unix, windows)))]
+        process.start_kill()?;
+
+        process.wait().await?;
+
+        Ok(())
+    }
+
+    fn kill(pid: u32) -> Fallible<()> {
+        ::tracing::debug!("Terminating process tree `{}`", pid);
+
+        #[cfg(unix)]
+        if unsafe { ::libc::killpg(pid as ::libc::pid_t, ::libc::SIGKILL) } != 0 {
+            return Err(::std::io::Error::last_os_error().into());
+        }
+
+        #[cfg(windows)]
+        ::std::process::Command::new("taskkill")
+            .args(["/T", "/F", "/PID", &pid.to_string()])
+            .stdout(::std::process::Stdio::null())
+            .stderr(::std::process::Stdio::null())
+            .status()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,271 @@
+use ::async_trait::async_trait;
+use ::std::ops::Not;
+use ::use_cases::gateways::Exporter;
+use ::use_cases::gateways::Importer;
+use ::use_cases::models::descriptors::SubscriptionKind;
+use ::use_cases::models::descriptors::SubscriptionUrls;
+
+use crate::utils::aliases::Fallible;
+use crate::utils::aliases::MaybeOwnedString;
+
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct OpmlFormat {
+    #[builder(default = "walkman subscriptions")]
+    title: MaybeOwnedString,
+}
+
+#[async_trait]
+impl Exporter<SubscriptionUrls> for OpmlFormat {
+    async fn export(
+        self: ::std::sync::Arc<Self>, subscription_urls: &SubscriptionUrls, path: &::std::path::Path,
+    ) -> Fallible<()> {
+        use ::quick_xml::events::BytesDecl;
+        use ::quick_xml::events::BytesText;
+        use ::quick_xml::events::Event;
+
+        let mut writer = ::quick_xml::Writer::new_with_indent(Vec::new(), b' ', 2);
+
+        writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+        writer
+            .create_element("opml")
+            .with_attribute(("version", "2.0"))
+            .write_inner_content(|writer| {
+                writer.create_element("head").write_inner_content(|writer| {
+                    writer.create_element("title").write_text_content(BytesText::new(&self.title))?;
+
+                    Ok(())
+                })?;
+
+                writer.create_element("body").write_inner_content(|writer| {
+                    for kind in FormattedSection::SECTIONS {
+                        writer
+                            .create_element("outline")
+                            .with_attribute(("text", FormattedSection(kind).as_str()))
+                            .write_inner_content(|writer| {
+                                for url in SectionUrls(subscription_urls, kind).iter() {
+                                    // Feeds are written as `rss` outlines, so that podcast apps can import them too
+                                    let attributes = match kind {
+                                        SubscriptionKind::Feed => [("type", "rss"), ("text", url), ("xmlUrl", url)],
+                                        _ => [("type", "link"), ("text", url), ("url", url)],
+                                    };
+
+                                    writer.create_element("outline").with_attributes(attributes).write_empty()?;
+                                }
+
+                                Ok(())
+                            })?;
+                    }
+
+                    Ok(())
+                })?;
+
+                Ok(())
+            })?;
+
+        let mut document = writer.into_inner();
+        document.push(b'\n');
+
+        ::tokio::fs::write(path, document).await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Importer<SubscriptionUrls> for OpmlFormat {
+    async fn import(self: ::std::sync::Arc<Self>, path: &::std::path::Path) -> Fallible<SubscriptionUrls> {
+        use ::quick_xml::events::Event;
+
+        let document = ::tokio::fs::read_to_string(path).await?;
+        let mut reader = ::quick_xml::Reader::from_str(&document);
+
+        let mut subscription_urls = SubscriptionUrlsAccumulator::default();
+        let mut kinds: Vec<Option<SubscriptionKind>> = Vec::new();
+
+        loop {
+            match reader.read_event()? {
+                Event::Start(element) if element.local_name().as_ref() == "outline" => {
+                    let kind = Self::kind(&element)?.or(kinds.last().copied().flatten());
+
+                    if let Some(url) = Self::url(&element)? {
+                        subscription_urls.push(kind, url)?;
+                    }
+
+                    kinds.push(kind);
+                },
+                Event::Empty(element) if element.local_name().as_ref() == "outline" => {
+                    let kind = Self::kind(&element)?.or(kinds.last().copied().flatten());
+
+                    if let Some(url) = Self::url(&element)? {
+                        subscription_urls.push(kind, url)?;
+                    }
+                },
+                Event::End(element) if element.local_name().as_ref() == "outline" => {
+                    kinds.pop();
+                },
+                Event::Eof => break,
+                _ => {},
+            }
+        }
+
+        Ok(subscription_urls.into())
+    }
+}
+
+impl OpmlFormat {
+    fn kind(element: &::quick_xml::events::BytesStart<'_>) -> Fallible<Option<SubscriptionKind>> {
+        if let Some(attribute) = element.try_get_attribute("type")? {
+            let value = attribute.normalized_value(::quick_xml::XmlVersion::default())?;
+
+            if value.eq_ignore_ascii_case("rss") {
+                return Ok(Some(SubscriptionKind::Feed));
+            }
+        }
+
+        for name in ["text", "title"] {
+            if let Some(attribute) = element.try_get_attribute(name)? {
+                let value = attribute.normalized_value(::quick_xml::XmlVersion::default())?;
+
+                if let Some(kind) = FormattedSection::parse(&value) {
+                    return Ok(Some(kind));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn url(element: &::quick_xml::events::BytesStart<'_>) -> Fallible<Option<MaybeOwnedString>> {
+        for name in ["url", "htmlUrl", "xmlUrl"] {
+            if let Some(attribute) = element.try_get_attribute(name)? {
+                let value = attribute.normalized_value(::quick_xml::XmlVersion::default())?;
+
+                return Ok(Some(value.into_owned().into()));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+pub struct PlainTextFormat;
+
+#[async_trait]
+impl Exporter<SubscriptionUrls> for PlainTextFormat {
+    async fn export(
+        self: ::std::sync::Arc<Self>, subscription_urls: &SubscriptionUrls, path: &::std::path::Path,
+    ) -> Fallible<()> {
+        let document = FormattedSection::SECTIONS
+            .into_iter()
+            .map(|kind| {
+                ::std::iter::once(format!("# {}", FormattedSection(kind).as_str()))
+                    .chain(SectionUrls(subscription_urls, kind).iter().map(ToString::to_string))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        ::tokio::fs::write(path, document + "\n").await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Importer<SubscriptionUrls> for PlainTextFormat {
+    async fn import(self: ::std::sync::Arc<Self>, path: &::std::path::Path) -> Fallible<SubscriptionUrls> {
+        let document = ::tokio::fs::read_to_string(path).await?;
+
+        let mut subscription_urls = SubscriptionUrlsAccumulator::default();
+        let mut kind = None;
+
+        for line in document.lines().map(str::trim).filter(|line| line.is_empty().not()) {
+            match line.strip_prefix('#') {
+                Some(comment) =>
+                    if let Some(section) = FormattedSection::parse(comment) {
+                        kind = Some(section);
+                    },
+                None => subscription_urls.push(kind, line.to_owned().into())?,
+            }
+        }
+
+        Ok(subscription_urls.into())
+    }
+}
+
+#[derive(Default)]
+struct SubscriptionUrlsAccumulator {
+    video_urls: Vec<MaybeOwnedString>,
+    playlist_urls: Vec<MaybeOwnedString>,
+    channel_urls: Vec<MaybeOwnedString>,
+    feed_urls: Vec<MaybeOwnedString>,
+}
+
+impl SubscriptionUrlsAccumulator {
+    fn push(&mut self, kind: Option<SubscriptionKind>, url: MaybeOwnedString) -> Fallible<()> {
+        match kind {
+            Some(SubscriptionKind::Video) => self.video_urls.push(url),
+            Some(SubscriptionKind::Playlist) => self.playlist_urls.push(url),
+            Some(SubscriptionKind::Channel) => self.channel_urls.push(url),
+            Some(SubscriptionKind::Feed) => self.feed_urls.push(url),
+            None =>
+                ::anyhow::bail!("`{}` does not belong to any of the videos, playlists, channels or feeds sections", url),
+        }
+
+        Ok(())
+    }
+}
+
+impl From<SubscriptionUrlsAccumulator> for SubscriptionUrls {
+    fn from(accumulator: SubscriptionUrlsAccumulator) -> Self {
+        Self::builder()
+            .video_urls(accumulator.video_urls)
+            .playlist_urls(accumulator.playlist_urls)
+            .channel_urls(accumulator.channel_urls)
+            .feed_urls(accumulator.feed_urls)
+            .build()
+    }
+}
+
+struct SectionUrls<'a>(&'a SubscriptionUrls, SubscriptionKind);
+
+impl SectionUrls<'_> {
+    fn iter(&self) -> impl Iterator<Item = &str> {
+        let urls = match self.1 {
+            SubscriptionKind::Video => &self.0.video_urls,
+            SubscriptionKind::Playlist => &self.0.playlist_urls,
+            SubscriptionKind::Channel => &self.0.channel_urls,
+            SubscriptionKind::Feed => &self.0.feed_urls,
+        };
+
+        urls.iter().map(|url| &**url)
+    }
+}
+
+struct FormattedSection(SubscriptionKind);
+
+impl FormattedSection {
+    const SECTIONS: [SubscriptionKind; 4] =
+        [SubscriptionKind::Video, SubscriptionKind::Playlist, SubscriptionKind::Channel, SubscriptionKind::Feed];
+
+    fn as_str(&self) -> &'static str {
+        match self.0 {
+            SubscriptionKind::Video => "videos",
+            SubscriptionKind::Playlist => "playlists",
+            SubscriptionKind::Channel => "channels",
+            SubscriptionKind::Feed => "feeds",
+        }
+    }
+
+    fn parse(section: &str) -> Option<SubscriptionKind> {
+        match section.trim().to_lowercase().as_str() {
+            "videos" => Some(SubscriptionKind::Video),
+            "playlists" => Some(SubscriptionKind::Playlist),
+            "channels" => Some(SubscriptionKind::Channel),
+            "feeds" => Some(SubscriptionKind::Feed),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,583 @@
+use ::async_trait::async_trait;
+use ::std::ops::Not;
+use ::use_cases::gateways::MediaImporter;
+use ::use_cases::models::descriptors::PartiallyResolvedPlaylist;
+use ::use_cases::models::descriptors::PartiallyResolvedVideo;
+use ::use_cases::models::descriptors::PlaylistMetadata;
+use ::use_cases::models::descriptors::ResolvedPlaylist;
+use ::use_cases::models::descriptors::ResolvedVideo;
+use ::use_cases::models::descriptors::UnresolvedVideo;
+use ::use_cases::models::descriptors::VideoMetadata;
+use ::use_cases::models::events::DiagnosticEvent;
+use ::use_cases::models::events::DiagnosticLevel;
+use ::use_cases::models::events::PlaylistDownloadCompletedEvent;
+use ::use_cases::models::events::PlaylistDownloadEvent;
+use ::use_cases::models::events::PlaylistDownloadFailedEvent;
+use ::use_cases::models::events::PlaylistDownloadProgressUpdatedEvent;
+use ::use_cases::models::events::PlaylistDownloadStartedEvent;
+use ::use_cases::models::events::VideoDownloadCompletedEvent;
+use ::use_cases::models::events::VideoDownloadEvent;
+use ::use_cases::models::events::VideoDownloadFailedEvent;
+use ::use_cases::models::events::VideoDownloadProgressUpdatedEvent;
+use ::use_cases::models::events::VideoDownloadStartedEvent;
+
+use crate::gateways::downloaders::DownloadedVideosPartitioner;
+use crate::gateways::downloaders::MediaFileNamer;
+use crate::gateways::downloaders::TaskSupervisor;
+use crate::gateways::downloaders::VideoDownloadEventSender;
+use crate::gateways::profiles::DeviceProfile;
+use crate::utils::aliases::BoxedStream;
+use crate::utils::aliases::Fallible;
+use crate::utils::aliases::MaybeOwnedPath;
+use crate::utils::aliases::MaybeOwnedString;
+use crate::utils::aliases::MaybeOwnedVec;
+use crate::utils::extensions::OptionExt;
+
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct FilesystemMediaImporter {
+    directory: MaybeOwnedPath,
+
+    profile: DeviceProfile,
+
+    transfer_policy: TransferPolicy,
+
+    #[builder(default)]
+    cancellation: ::tokio_util::sync::CancellationToken,
+
+    #[builder(default = 256_usize)]
+    event_buffer: usize,
+}
+
+pub enum TransferPolicy {
+    CopyFiles,
+    MoveFiles,
+}
+
+#[async_trait]
+impl MediaImporter for FilesystemMediaImporter {
+    async fn import(
+        self: ::std::sync::Arc<Self>, path: MaybeOwnedPath, imported_videos: MaybeOwnedVec<ResolvedVideo>,
+    ) -> Fallible<(BoxedStream<VideoDownloadEvent>, BoxedStream<PlaylistDownloadEvent>, BoxedStream<DiagnosticEvent>)>
+    {
+        if ::tokio::fs::metadata(&path).await.is_ok_and(|metadata| metadata.is_dir()).not() {
+            ::anyhow::bail!("`{}` is not a folder", path.display());
+        }
+
+        ::tokio::fs::create_dir_all(&self.directory).await?;
+
+        let path = ::tokio::fs::canonicalize(&path).await?;
+        let directory = ::tokio::fs::canonicalize(&self.directory).await?;
+
+        let (video_download_events_tx, video_download_events_rx) = ::tokio::sync::mpsc::channel(self.event_buffer);
+        let (playlist_download_events_tx, playlist_download_events_rx) =
+            ::tokio::sync::mpsc::channel(self.event_buffer);
+        let (diagnostic_events_tx, diagnostic_events_rx) = ::tokio::sync::mpsc::channel(self.event_buffer);
+
+        // Folders have no id of their own, so that their url stands in for it
+        let url = LocalUrl::of(&path);
+
+        let on_failure = {
+            let url = url.clone();
+            let playlist_download_events_tx = playlist_download_events_tx.clone();
+            let cancellation = self.cancellation.clone();
+
+            move |reason: MaybeOwnedString| async move {
+                if cancellation.is_cancelled() {
+                    return;
+                }
+
+                let event = PlaylistDownloadFailedEvent::builder()
+                    .url(url)
+                    .playlist_id(None)
+                    .reason(reason)
+                    .exit_status(None)
+                    .build();
+
+                playlist_download_events_tx
+                    .send(PlaylistDownloadEvent::Failed(event))
+                    .await
+                    .ok();
+            }
+        };
+
+        let task = ::tokio::spawn(async move {
+            let sources = ::tokio::select! {
+                sources = LocalTrackScanner::scan(&path, &directory, self.profile.codec.extension()) => sources?,
+                _ = self.cancellation.cancelled() => return Ok(()),
+            };
+
+            if sources.is_empty() {
+                let event = DiagnosticEvent::builder()
+                    .level(DiagnosticLevel::Warning)
+                    .message(
+                        format!("No `{}` files found in `{}`", self.profile.codec.extension(), path.display()).into(),
+                    )
+                    .build();
+
+                diagnostic_events_tx.send(event).await?;
+            }
+
+            let mut tracks = ::std::collections::HashMap::with_capacity(sources.len());
+            let mut reserved_file_names = ::std::collections::HashSet::new();
+            let mut videos = Vec::with_capacity(sources.len());
+
+            for source in sources {
+                let (metadata, warning) = LocalTrackTagReader::read(&source).await?;
+
+                if let Some(warning) = warning {
+                    diagnostic_events_tx.send(warning).await?;
+                }
+
+                let track_url = LocalUrl::of(&source);
+
+                // Files imported before keep their ids, so that they are recognized and skipped
+                if let Some(imported_video) = imported_videos.iter().find(|video| video.url == track_url) {
+                    videos.push(UnresolvedVideo::builder().id(imported_video.id.clone()).url(track_url).build());
+
+                    continue;
+                }
+
+                let destination = match source.parent() == Some(&*directory) {
+                    true => source.clone(),
+                    false =>
+                        LocalTrackFileNamer::name()
+                            .directory(&directory)
+                            .profile(&self.profile)
+                            .maybe_title(metadata.title.as_deref())
+                            .source(&source)
+                            .reserved_file_names(&mut reserved_file_names)
+                            .call()
+                            .await?,
+                };
+
+                let id: MaybeOwnedString = format!("local:{}", destination.file_name().ok()?.to_string_lossy()).into();
+
+                videos.push(UnresolvedVideo::builder().id(id.clone()).url(track_url.clone()).build());
+
+                let video = PartiallyResolvedVideo::builder()
+                    .id(id.clone())
+                    .url(track_url)
+                    .metadata(metadata)
+                    .build();
+
+                tracks.insert(id, LocalTrack { video, source, destination });
+            }
+
+            let title = path.file_name().map(|name| name.to_string_lossy().into_owned().into());
+
+            let playlist = PartiallyResolvedPlaylist::builder()
+                .id(url.clone())
+                .url(url)
+                .metadata(PlaylistMetadata::builder().title(title).build())
+                .videos(videos.is_empty().not().then(|| videos.into()))
+                .build();
+
+            ::tracing::debug!("Importing folder `{:?}`", playlist);
+
+            let event = PlaylistDownloadStartedEvent::builder().playlist(playlist.clone()).build();
+            playlist_download_events_tx.send(PlaylistDownloadEvent::Started(event)).await?;
+
+            let (skipped_videos, pending_videos) = DownloadedVideosPartitioner::partition(
+                playlist.videos.as_deref().unwrap_or_default(),
+                &imported_videos,
+            );
+
+            let mut completed_videos = 0;
+            let mut failed_videos = 0;
+            let skipped_videos_count = skipped_videos.len() as u64;
+            let total_videos = playlist.videos.as_deref().map(|videos| videos.len() as u64).unwrap_or_default();
+
+            let mut videos = Vec::with_capacity(total_videos as usize);
+            videos.extend(skipped_videos);
+
+            if skipped_videos_count > 0 {
+                let event = PlaylistDownloadProgressUpdatedEvent::builder()
+                    .playlist_id(playlist.id.clone())
+                    .completed_videos(0)
+                    .failed_videos(0)
+                    .skipped_videos(skipped_videos_count)
+                    .total_videos(total_videos)
+                    .build();
+
+                playlist_download_events_tx
+                    .send(PlaylistDownloadEvent::ProgressUpdated(event))
+                    .await?;
+            }
+
+            // Tracks are transferred one at a time, so that the disk is not thrashed
+            for video in pending_videos {
+                if self.cancellation.is_cancelled() {
+                    return Ok(());
+                }
+
+                let track = tracks.remove(&video.id).ok()?;
+                let imported = self.import_track(track, &video_download_events_tx).await?;
+
+                if self.cancellation.is_cancelled() {
+                    return Ok(());
+                }
+
+                match imported {
+                    Some(video) => {
+                        completed_videos += 1;
+                        videos.push(video);
+                    },
+                    None => failed_videos += 1,
+                }
+
+                let event = PlaylistDownloadProgressUpdatedEvent::builder()
+                    .playlist_id(playlist.id.clone())
+                    .completed_videos(completed_videos)
+                    .failed_videos(failed_videos)
+                    .skipped_videos(skipped_videos_count)
+                    .total_videos(total_videos)
+                    .build();
+
+                playlist_download_events_tx
+                    .send(PlaylistDownloadEvent::ProgressUpdated(event))
+                    .await?;
+            }
+
+            let videos = videos.is_empty().not().then_some(videos.into());
+
+            let playlist = ResolvedPlaylist::builder()
+                .id(playlist.id)
+                .url(playlist.url)
+                .metadata(playlist.metadata)
+                .videos(videos)
+                .listed_videos(total_videos)
+                .build();
+
+            ::tracing::debug!("Imported folder `{:?}`", playlist);
+
+            let event = PlaylistDownloadCompletedEvent { playlist };
+            playlist_download_events_tx
+                .send(PlaylistDownloadEvent::Completed(event))
+                .await?;
+
+            Ok::<_, ::anyhow::Error>(())
+        });
+
+        TaskSupervisor::supervise(task, on_failure);
+
+        Ok((
+            ::std::boxed::Box::pin(::tokio_stream::wrappers::ReceiverStream::new(video_download_events_rx)),
+            ::std::boxed::Box::pin(::tokio_stream::wrappers::ReceiverStream::new(playlist_download_events_rx)),
+            ::std::boxed::Box::pin(::tokio_stream::wrappers::ReceiverStream::new(diagnostic_events_rx)),
+        ))
+    }
+}
+
+impl FilesystemMediaImporter {
+    const CHUNK_SIZE: usize = 1 << 20;
+    // Progress is reported at most this often, so that small chunks do not flood
+    // the view
+    const PROGRESS_INTERVAL: ::std::time::Duration = ::std::time::Duration::from_millis(250);
+
+    async fn import_track(
+        &self, track: LocalTrack, video_download_events_tx: &::tokio::sync::mpsc::Sender<VideoDownloadEvent>,
+    ) -> Fallible<Option<ResolvedVideo>> {
+        let event = VideoDownloadStartedEvent::builder().video(track.video.clone()).build();
+        video_download_events_tx.send(VideoDownloadEvent::Started(event)).await?;
+
+        // Files already in the library are counted there, so that only the ones
+        // brought in take a slot
+        let folder_slot = match (track.source == track.destination, track.destination.parent()) {
+            (false, Some(folder)) => Some(self.profile.reserve_folder_slot(folder).await?),
+            _ => None,
+        };
+
+        let transferred = match (track.source == track.destination, &self.transfer_policy) {
+            (true, _) => Ok(()),
+            (false, TransferPolicy::CopyFiles) => self.copy(&track, video_download_events_tx).await,
+            // Renaming fails across filesystems, so that a copy is made and the original
+            // removed instead
+            (false, TransferPolicy::MoveFiles) => match ::tokio::fs::rename(&track.source, &track.destination).await {
+                Ok(()) => Ok(()),
+                Err(_) => match self.copy(&track, video_download_events_tx).await {
+                    Ok(()) => ::tokio::fs::remove_file(&track.source).await.map_err(Into::into),
+                    Err(error) => Err(error),
+                },
+            },
+        };
+
+        if let Err(error) = transferred {
+            if self.cancellation.is_cancelled() {
+                return Ok(None);
+            }
+
+            let event = VideoDownloadFailedEvent::builder()
+                .url(track.video.url)
+                .video_id(Some(track.video.id))
+                .reason(error.to_string().into())
+                .exit_status(None)
+                .build();
+
+            video_download_events_tx.send(VideoDownloadEvent::Failed(event)).await?;
+
+            return Ok(None);
+        }
+
+        if let Some(folder_slot) = folder_slot {
+            folder_slot.keep();
+        }
+
+        let video = ResolvedVideo::builder()
+            .id(track.video.id)
+            .url(track.video.url)
+            .metadata(track.video.metadata)
+            .path(track.destination.into())
+            .build();
+
+        let event = VideoDownloadCompletedEvent::builder().video(video.clone()).build();
+        video_download_events_tx.send(VideoDownloadEvent::Completed(event)).await?;
+
+        Ok(Some(video))
+    }
+
+    async fn copy(
+        &self, track: &LocalTrack, video_download_events_tx: &::tokio::sync::mpsc::Sender<VideoDownloadEvent>,
+    ) -> Fallible<()> {
+        let partial_path = track.partial_path()?;
+
+        let copied = ::tokio::select! {
+            copied = self.copy_chunks(track, video_download_events_tx) => copied,
+            _ = self.cancellation.cancelled() => Err(::anyhow::anyhow!("Cancelled")),
+        };
+
+        match copied {
+            Ok(()) => Ok(::tokio::fs::rename(&partial_path, &track.destination).await?),
+            Err(error) => {
+                ::tokio::fs::remove_file(&partial_path).await.ok();
+
+                Err(error)
+            },
+        }
+    }
+
+    async fn copy_chunks(
+        &self, track: &LocalTrack, video_download_events_tx: &::tokio::sync::mpsc::Sender<VideoDownloadEvent>,
+    ) -> Fallible<()> {
+        use ::tokio::io::AsyncReadExt as _;
+        use ::tokio::io::AsyncWriteExt as _;
+
+        let mut source = ::tokio::fs::File::open(&track.source).await?;
+        let mut file = ::tokio::fs::File::create(track.partial_path()?).await?;
+
+        let total_bytes = source.metadata().await?.len();
+
+        let started_at = ::tokio::time::Instant::now();
+        let mut reported_at = started_at;
+        let mut copied_bytes = 0;
+
+        let mut chunk = vec![0; Self::CHUNK_SIZE];
+
+        loop {
+            let length = source.read(&mut chunk).await?;
+
+            if length == 0 {
+                break;
+            }
+
+            file.write_all(&chunk[..length]).await?;
+            copied_bytes += length as u64;
+
+            if reported_at.elapsed() < Self::PROGRESS_INTERVAL && copied_bytes < total_bytes {
+                continue;
+            }
+
+            reported_at = ::tokio::time::Instant::now();
+
+            let elapsed = started_at.elapsed();
+            let bytes_per_second = (copied_bytes as f64 / elapsed.as_secs_f64().max(f64::EPSILON)) as u64;
+            let eta = ::std::time::Duration::from_secs_f64(
+                total_bytes.saturating_sub(copied_bytes) as f64 / bytes_per_second.max(1) as f64,
+            );
+
+            let event = VideoDownloadProgressUpdatedEvent::builder()
+                .video_id(track.video.id.clone())
+                .eta(eta)
+                .elapsed(elapsed)
+                .downloaded_bytes(copied_bytes)
+                .total_bytes(total_bytes.max(copied_bytes))
+                .bytes_per_second(bytes_per_second)
+                .build();
+
+            VideoDownloadEventSender::send(video_download_events_tx, VideoDownloadEvent::ProgressUpdated(event))
+                .await?;
+        }
+
+        file.flush().await?;
+
+        Ok(())
+    }
+}
+
+struct LocalTrack {
+    video: PartiallyResolvedVideo,
+
+    source: ::std::path::PathBuf,
+    destination: ::std::path::PathBuf,
+}
+
+impl LocalTrack {
+    fn partial_path(&self) -> Fallible<::std::path::PathBuf> {
+        let file_name = self.destination.file_name().ok()?.to_string_lossy();
+
+        Ok(self.destination.with_file_name(format!("{}.part", file_name)))
+    }
+}
+
+struct LocalTrackScanner;
+
+impl LocalTrackScanner {
+    // Only files in the profile's codec are picked up, so that nothing is brought
+    // in that the device cannot play. The library itself is left out when nested
+    // in the folder, so that its files are not imported into themselves
+    async fn scan(
+        path: &::std::path::Path, directory: &::std::path::Path, extension: &str,
+    ) -> Fallible<Vec<::std::path::PathBuf>> {
+        let mut sources = Vec::new();
+        let mut folders = vec![path.to_path_buf()];
+
+        while let Some(folder) = folders.pop() {
+            let mut entries = ::tokio::fs::read_dir(&folder).await?;
+
+            while let Some(entry) = entries.next_entry().await? {
+                let entry_path = entry.path();
+                let file_type = entry.file_type().await?;
+
+                if file_type.is_dir() {
+                    if entry_path != directory {
+                        folders.push(entry_path);
+                    }
+
+                    continue;
+                }
+
+                let entry_extension = entry_path
+                    .extension()
+                    .and_then(::std::ffi::OsStr::to_str)
+                    .map(str::to_ascii_lowercase);
+
+                if file_type.is_file() && entry_extension.is_some_and(|entry_extension| entry_extension == extension) {
+                    sources.push(entry_path);
+                }
+            }
+        }
+
+        sources.sort();
+
+        Ok(sources)
+    }
+}
+
+struct LocalTrackTagReader;
+
+impl LocalTrackTagReader {
+    // Unreadable tags are reported rather than fatal, so that the file is still
+    // imported under its file name
+    async fn read(path: &::std::path::Path) -> Fallible<(VideoMetadata, Option<DiagnosticEvent>)> {
+        let tag = ::tokio::task::spawn_blocking({
+            let path = path.to_path_buf();
+            move || ::id3::Tag::read_from_path(path)
+        })
+        .await?;
+
+        let (tag, warning) = match tag {
+            Ok(tag) => (Some(tag), None),
+            Err(error) if matches!(error.kind, ::id3::ErrorKind::NoTag) => (None, None),
+            Err(error) => {
+                let event = DiagnosticEvent::builder()
+                    .level(DiagnosticLevel::Warning)
+                    .message(format!("Ignored unreadable ID3 tag of `{}`: {}", path.display(), error).into())
+                    .build();
+
+                (None, Some(event))
+            },
+        };
+
+        let metadata = Self::metadata(tag.as_ref(), path);
+
+        Ok((metadata, warning))
+    }
+
+    fn metadata(tag: Option<&::id3::Tag>, path: &::std::path::Path) -> VideoMetadata {
+        use ::id3::TagLike as _;
+
+        let title = tag
+            .and_then(|tag| tag.title())
+            .map(str::to_owned)
+            .or_else(|| path.file_stem().map(|stem| stem.to_string_lossy().into_owned()));
+        let album = tag.and_then(|tag| tag.album()).map(str::to_owned);
+        let artists = tag.and_then(|tag| tag.artists()).map(Self::collect);
+        let genres = tag.and_then(|tag| tag.genres()).map(Self::collect);
+
+        VideoMetadata::builder()
+            .title(title.map(Into::into))
+            .album(album.map(Into::into))
+            .artists(artists)
+            .genres(genres)
+            .build()
+    }
+
+    fn collect(values: Vec<&str>) -> MaybeOwnedVec<MaybeOwnedString> {
+        values
+            .into_iter()
+            .map(|value| MaybeOwnedString::from(value.to_owned()))
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+struct LocalTrackFileNamer;
+
+#[::bon::bon]
+impl LocalTrackFileNamer {
+    // Taken names get a numbered suffix, so that downloaded and imported files
+    // never overwrite each other. Names are cut to what the profile allows,
+    // suffix included
+    #[builder]
+    async fn name(
+        directory: &::std::path::Path, profile: &DeviceProfile, title: Option<&str>, source: &::std::path::Path,
+        reserved_file_names: &mut ::std::collections::HashSet<::std::string::String>,
+    ) -> Fallible<::std::path::PathBuf> {
+        let stem = [title, source.file_stem().and_then(::std::ffi::OsStr::to_str)]
+            .into_iter()
+            .flatten()
+            .map(MediaFileNamer::sanitize)
+            .find(|stem| stem.is_empty().not())
+            .unwrap_or_else(|| "track".to_owned());
+
+        let max_stem_length = profile.max_file_stem_length(directory) as usize;
+
+        for attempt in 1_u64.. {
+            let suffix = match attempt {
+                1 => ::std::string::String::new(),
+                _ => format!("_{}", attempt),
+            };
+
+            let stem = MediaFileNamer::truncate(&stem, max_stem_length.saturating_sub(suffix.len()).max(1));
+            let file_name = format!("{}{}.{}", stem, suffix, profile.codec.extension());
+
+            let path = directory.join(&file_name);
+
+            if reserved_file_names.contains(&file_name).not() && ::tokio::fs::try_exists(&path).await?.not() {
+                reserved_file_names.insert(file_name);
+
+                return Ok(path);
+            }
+        }
+
+        unreachable!()
+    }
+}
+
+struct LocalUrl;
+
+impl LocalUrl {
+    fn of(path: &::std::path::Path) -> MaybeOwnedString {
+        format!("file://{}", path.display()).into()
+    }
+}
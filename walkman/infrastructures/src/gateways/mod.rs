@@ -1,3 +1,8 @@
+pub mod discarders;
 pub mod downloaders;
+pub mod executors;
+pub mod formats;
+pub mod importers;
 pub mod postprocessors;
+pub mod profiles;
 pub mod repositories;
@@ -1,17 +1,31 @@
 use ::async_trait::async_trait;
+use ::futures::prelude::*;
 use ::rayon::prelude::*;
+use ::std::ops::Not;
 use ::use_cases::gateways::PostProcessor;
 use ::use_cases::models::descriptors::ResolvedChannel;
 use ::use_cases::models::descriptors::ResolvedPlaylist;
 use ::use_cases::models::descriptors::ResolvedVideo;
+use ::use_cases::models::descriptors::VideoMetadata;
 
+use crate::gateways::downloaders::MediaFileNamer;
+use crate::gateways::downloaders::RateLimiter;
+use crate::gateways::executors::CommandExecutor;
+use crate::gateways::profiles::AudioCodec;
+use crate::gateways::profiles::DeviceProfile;
 use crate::utils::aliases::Fallible;
+use crate::utils::aliases::MaybeOwnedString;
+use crate::utils::aliases::MaybeOwnedVec;
+use crate::utils::extensions::OptionExt;
 
 #[derive(::bon::Builder)]
 #[builder(on(_, into))]
 pub struct Id3MetadataWriter {
     album_naming_policy: AlbumNamingPolicy,
     artists_naming_policy: ArtistsNamingPolicy,
+    upload_numbering_policy: UploadNumberingPolicy,
+    tracks_per_disc: Option<u64>,
+    profile: DeviceProfile,
 }
 
 pub enum AlbumNamingPolicy {
@@ -25,45 +39,76 @@ pub enum ArtistsNamingPolicy {
     UseBothVideoArtistsAndChannelTitle,
 }
 
+pub enum UploadNumberingPolicy {
+    NumberUploadsChronologically,
+    LeaveUploadsUnnumbered,
+}
+
 #[async_trait]
 impl PostProcessor<ResolvedVideo> for Id3MetadataWriter {
-    async fn process(self: ::std::sync::Arc<Self>, video: &ResolvedVideo) -> Fallible<()> {
+    async fn process(self: ::std::sync::Arc<Self>, video: &mut ResolvedVideo) -> Fallible<()> {
         self.write().video(video).call()
     }
 }
 
 #[async_trait]
 impl PostProcessor<ResolvedPlaylist> for Id3MetadataWriter {
-    async fn process(self: ::std::sync::Arc<Self>, playlist: &ResolvedPlaylist) -> Fallible<()> {
-        playlist
-            .videos
-            .as_deref()
-            .into_par_iter()
-            .flatten()
-            .try_for_each(|video| ::std::sync::Arc::clone(&self).write().video(video).playlist(playlist).call())
+    async fn process(self: ::std::sync::Arc<Self>, playlist: &mut ResolvedPlaylist) -> Fallible<()> {
+        let videos = playlist.videos.as_deref().unwrap_or_default();
+        let total = playlist.listed_videos.unwrap_or(videos.len() as u64);
+
+        videos.par_iter().enumerate().try_for_each(|(position, video)| {
+            let index = video.index.unwrap_or(position as u64 + 1);
+
+            ::std::sync::Arc::clone(&self)
+                .write()
+                .video(video)
+                .playlist(playlist)
+                .track(TrackPosition::in_playlist(index, total))
+                .call()
+        })
     }
 }
 
 #[async_trait]
 impl PostProcessor<ResolvedChannel> for Id3MetadataWriter {
-    async fn process(self: ::std::sync::Arc<Self>, channel: &ResolvedChannel) -> Fallible<()> {
+    async fn process(self: ::std::sync::Arc<Self>, channel: &mut ResolvedChannel) -> Fallible<()> {
         ::tokio::try_join!(
             async {
-                channel
-                    .videos
-                    .as_deref()
-                    .into_par_iter()
-                    .flatten()
-                    .try_for_each(|video| ::std::sync::Arc::clone(&self).write().video(video).channel(channel).call())
+                let videos = channel.videos.as_deref().unwrap_or_default();
+                let total = channel.listed_videos.unwrap_or(videos.len() as u64);
+
+                videos.par_iter().enumerate().try_for_each(|(position, video)| {
+                    let index = video.index.unwrap_or(position as u64 + 1);
+
+                    let track = match self.upload_numbering_policy {
+                        UploadNumberingPolicy::NumberUploadsChronologically =>
+                            Some(TrackPosition::in_uploads(index, total)),
+                        UploadNumberingPolicy::LeaveUploadsUnnumbered => None,
+                    };
+
+                    ::std::sync::Arc::clone(&self)
+                        .write()
+                        .video(video)
+                        .channel(channel)
+                        .maybe_track(track)
+                        .call()
+                })
             },
             async {
                 channel.playlists.as_deref().into_par_iter().flatten().try_for_each(|playlist| {
-                    playlist.videos.as_deref().into_par_iter().flatten().try_for_each(|video| {
+                    let videos = playlist.videos.as_deref().unwrap_or_default();
+                    let total = playlist.listed_videos.unwrap_or(videos.len() as u64);
+
+                    videos.par_iter().enumerate().try_for_each(|(position, video)| {
+                        let index = video.index.unwrap_or(position as u64 + 1);
+
                         ::std::sync::Arc::clone(&self)
                             .write()
                             .video(video)
                             .playlist(playlist)
                             .channel(channel)
+                            .track(TrackPosition::in_playlist(index, total))
                             .call()
                     })
                 })
@@ -79,19 +124,57 @@ impl Id3MetadataWriter {
     #[builder]
     fn write(
         self: ::std::sync::Arc<Self>, video: &ResolvedVideo, playlist: Option<&ResolvedPlaylist>,
-        channel: Option<&ResolvedChannel>,
+        channel: Option<&ResolvedChannel>, track: Option<TrackPosition>,
     ) -> Fallible<()> {
         use ::id3::TagLike as _;
 
+        if self.profile.codec.carries_id3_tags().not() {
+            ::tracing::debug!("Skipped tagging `{}`, which carries no ID3 tag", video.path.display());
+
+            return Ok(());
+        }
+
+        if SplitVideoDetector::is_split(&video.path) {
+            ::tracing::debug!("Skipped tagging `{}`, which was split into tracks", video.path.display());
+
+            return Ok(());
+        }
+
         let mut tag = ::id3::Tag::new();
 
-        if let Some(title) = video.metadata.title.as_deref() {
+        // Extended texts, pictures, lyrics and chapters are the records of other
+        // post-processors, so that they survive the rest of the tag being written
+        // afresh
+        if let Ok(existing) = ::id3::Tag::read_from_path(&video.path) {
+            existing
+                .frames()
+                .filter(|frame| {
+                    matches!(
+                        frame.content(),
+                        ::id3::Content::ExtendedText(_)
+                            | ::id3::Content::Picture(_)
+                            | ::id3::Content::Lyrics(_)
+                            | ::id3::Content::SynchronisedLyrics(_)
+                            | ::id3::Content::Chapter(_)
+                            | ::id3::Content::TableOfContents(_)
+                    )
+                })
+                .cloned()
+                .for_each(|frame| {
+                    tag.add_frame(frame);
+                });
+        }
+
+        let metadata = &video.metadata;
+        let channel_title = channel.and_then(|channel| channel.metadata.title.as_deref());
+
+        if let Some(title) = metadata.title.as_deref() {
             tag.set_title(title)
         }
 
         match self.album_naming_policy {
             AlbumNamingPolicy::UseVideoAlbum =>
-                if let Some(album) = video.metadata.album.as_deref() {
+                if let Some(album) = metadata.album.as_deref() {
                     tag.set_album(album)
                 },
             AlbumNamingPolicy::UsePlaylistTitle => {
@@ -103,20 +186,28 @@ impl Id3MetadataWriter {
 
         match self.artists_naming_policy {
             ArtistsNamingPolicy::UseOnlyVideoArtists => {
-                if let Some(artists) = video.metadata.artists.as_deref().map(|artists| artists.join(", ")) {
+                if let Some(artists) = metadata.artists.as_deref().map(|artists| artists.join(", ")) {
                     tag.set_artist(artists)
                 }
             },
-            ArtistsNamingPolicy::UseOnlyChannelTitle => {
-                if let Some(title) = channel.and_then(|channel| channel.metadata.title.as_deref()) {
+            ArtistsNamingPolicy::UseOnlyChannelTitle =>
+                if let Some(title) = channel_title {
                     tag.set_artist(title)
-                }
-            },
+                },
             ArtistsNamingPolicy::UseBothVideoArtistsAndChannelTitle => {
-                match (
-                    video.metadata.artists.as_deref().map(|artists| artists.join(", ")),
-                    channel.and_then(|channel| channel.metadata.title.as_deref()),
-                ) {
+                let artists = metadata.artists.as_deref();
+
+                // A channel named after one of the artists adds nothing, so that it is
+                // left out rather than repeated
+                let channel_title = channel_title.filter(|title| {
+                    artists
+                        .unwrap_or_default()
+                        .iter()
+                        .any(|artist| TitleParser::is_same_name(artist, title))
+                        .not()
+                });
+
+                match (artists.map(|artists| artists.join(", ")), channel_title) {
                     (Some(artists), Some(title)) => tag.set_artist(format!("{}, {}", artists, title)),
                     (Some(artists), None) => tag.set_artist(artists),
                     (None, Some(title)) => tag.set_artist(title),
@@ -125,12 +216,1972 @@ impl Id3MetadataWriter {
             },
         }
 
-        if let Some(genres) = video.metadata.genres.as_deref() {
+        if let Some(genres) = metadata.genres.as_deref() {
             tag.set_genre(genres.join(", "))
         }
 
-        tag.write_to_path(&video.path, ::id3::Version::Id3v23)?;
+        if let Some(track) = track {
+            self.number(&mut tag, track);
+        }
+
+        Id3TagWriter::write(&tag, &video.path, &self.profile)
+    }
+
+    // Playlists longer than a disc are numbered disc by disc, so that every
+    // number stays within what the device sorts
+    fn number(&self, tag: &mut ::id3::Tag, track: TrackPosition) {
+        use ::id3::TagLike as _;
+
+        let tracks_per_disc = self.tracks_per_disc.filter(|tracks_per_disc| track.total > *tracks_per_disc);
+
+        let Some(tracks_per_disc) = tracks_per_disc else {
+            tag.set_track(track.number as u32);
+            tag.set_total_tracks(track.total as u32);
+
+            return;
+        };
+
+        let disc = (track.number - 1) / tracks_per_disc;
+
+        tag.set_track(((track.number - 1) % tracks_per_disc + 1) as u32);
+        tag.set_total_tracks((track.total - disc * tracks_per_disc).min(tracks_per_disc) as u32);
+        tag.set_disc((disc + 1) as u32);
+        tag.set_total_discs(track.total.div_ceil(tracks_per_disc) as u32);
+    }
+}
+
+#[derive(Clone, Copy)]
+struct TrackPosition {
+    // Counted from one
+    number: u64,
+    total: u64,
+}
+
+impl TrackPosition {
+    // Numbered by where the video was listed rather than by what downloaded, so
+    // that a skipped or failed video leaves a gap instead of shifting the rest
+    fn in_playlist(index: u64, total: u64) -> Self {
+        Self { number: index, total: total.max(index) }
+    }
+
+    // Channels list their newest uploads first, so that the numbers run the other
+    // way around
+    fn in_uploads(index: u64, total: u64) -> Self {
+        let total = total.max(index);
+
+        Self { number: total - index + 1, total }
+    }
+}
+
+// Either `(feat. Name)` anywhere, or `ft. Name` up to the end
+static FEATURING_PATTERN: ::std::sync::LazyLock<::regex::Regex> = ::std::sync::LazyLock::new(|| {
+    ::regex::Regex::new(
+        r"(?i)\s*(?:[(\[]\s*(?:feat\.?|ft\.?|featuring)\s+([^)\]]+)[)\]]|\b(?:feat\.?|ft\.?|featuring)\s+(.+)$)",
+    )
+    .unwrap()
+});
+// Brackets holding `(Official Music Video)`, `[4K]`, `(Lyrics)` and alike, or a
+// trailing `| Official Audio`
+static NOISE_PATTERN: ::std::sync::LazyLock<::regex::Regex> = ::std::sync::LazyLock::new(|| {
+    ::regex::Regex::new(r"(?i)\s*(?:[(\[【][^)\]】]*\b(?:official|lyrics?|audio|video|visuali[sz]er|mv|pv|hd|hq|4k|8k|\d{3,4}p)\b[^)\]】]*[)\]】]|[|｜][^|｜]*\b(?:official|lyrics?|audio|video|visuali[sz]er|mv)\b[^|｜]*$)").unwrap()
+});
+static SEPARATOR_PATTERN: ::std::sync::LazyLock<::regex::Regex> =
+    ::std::sync::LazyLock::new(|| ::regex::Regex::new(r"(?i)\s*(?:,|&|\band\b)\s*").unwrap());
+
+// Goes ahead of every other post-processor, so that the cleaned titles and
+// artists are what gets tagged, split and recorded
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct TitleParser {
+    // Tried before the built-in rules, each naming a `title` and maybe an
+    // `artist` group
+    #[builder(default)]
+    rules: MaybeOwnedVec<::regex::Regex>,
+}
+
+#[async_trait]
+impl PostProcessor<ResolvedVideo> for TitleParser {
+    async fn process(self: ::std::sync::Arc<Self>, video: &mut ResolvedVideo) -> Fallible<()> {
+        video.metadata = self.parse(&video.metadata);
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PostProcessor<ResolvedPlaylist> for TitleParser {
+    async fn process(self: ::std::sync::Arc<Self>, playlist: &mut ResolvedPlaylist) -> Fallible<()> {
+        for video in playlist.videos.iter_mut().flat_map(|videos| videos.to_mut().iter_mut()) {
+            video.metadata = self.parse(&video.metadata);
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PostProcessor<ResolvedChannel> for TitleParser {
+    async fn process(self: ::std::sync::Arc<Self>, channel: &mut ResolvedChannel) -> Fallible<()> {
+        channel.metadata.title = channel
+            .metadata
+            .title
+            .as_deref()
+            .map(|title| Self::clean_channel_title(title).to_owned().into());
+
+        for video in channel.videos.iter_mut().flat_map(|videos| videos.to_mut().iter_mut()) {
+            video.metadata = self.parse(&video.metadata);
+        }
+
+        for playlist in channel.playlists.iter_mut().flat_map(|playlists| playlists.to_mut().iter_mut()) {
+            ::std::sync::Arc::clone(&self).process(playlist).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl TitleParser {
+    const ARTIST_SEPARATORS: [&str; 3] = [" - ", " – ", " — "];
+    const CHANNEL_SUFFIXES: [&str; 3] = [" - Topic", "VEVO", "Vevo"];
+
+    fn parse(&self, metadata: &VideoMetadata) -> VideoMetadata {
+        let Some(title) = metadata.title.as_deref() else {
+            return metadata.clone();
+        };
+
+        let known_artists = metadata.artists.as_deref().unwrap_or_default();
+
+        let (artist, title) = self.split(title, known_artists);
+        let (title, featured) = self.extract_featured(&self.strip_noise(&title));
+        let (artist, featured_with_artist) = match artist {
+            Some(artist) => {
+                let (artist, featured) = self.extract_featured(&artist);
+
+                (Some(artist), featured)
+            },
+            None => (None, Vec::new()),
+        };
+
+        let mut artists = known_artists.to_vec();
+
+        for name in artist.into_iter().chain(featured_with_artist).chain(featured) {
+            if name.is_empty().not() && artists.iter().any(|artist| Self::is_same_name(artist, &name)).not() {
+                artists.push(name.into());
+            }
+        }
+
+        VideoMetadata {
+            title: Some(match title.is_empty() {
+                true => metadata.title.clone().unwrap_or_default(),
+                false => title.into(),
+            }),
+            album: metadata.album.clone(),
+            artists: artists.is_empty().not().then(|| artists.into()),
+            genres: metadata.genres.clone(),
+        }
+    }
+
+    // Topic channels and VEVO accounts carry the artist under a suffix, so that
+    // the suffix is all that needs to go
+    fn clean_channel_title(title: &str) -> &str {
+        Self::CHANNEL_SUFFIXES
+            .iter()
+            .find_map(|suffix| title.strip_suffix(suffix))
+            .map(str::trim)
+            .filter(|title| title.is_empty().not())
+            .unwrap_or(title)
+    }
+
+    pub(crate) fn is_same_name(this: &str, that: &str) -> bool {
+        this.trim().to_lowercase() == that.trim().to_lowercase()
+    }
+
+    // The left side of a dash is only taken for the artist when the video names
+    // none, or names that one, so that titles such as `Song - Live` stay whole
+    fn split(
+        &self, title: &str, known_artists: &[MaybeOwnedString],
+    ) -> (Option<::std::string::String>, ::std::string::String) {
+        let ruled = self.rules.iter().find_map(|rule| {
+            let captures = rule.captures(title)?;
+
+            Some((
+                captures.name("artist").map(|artist| Self::trim(artist.as_str()).to_owned()),
+                captures.name("title")?.as_str().to_owned(),
+            ))
+        });
+
+        if let Some(ruled) = ruled {
+            return ruled;
+        }
+
+        let stripped = self.strip_noise(title);
+
+        let split = Self::ARTIST_SEPARATORS
+            .iter()
+            .filter_map(|separator| stripped.split_once(separator))
+            .min_by_key(|(artist, _)| artist.len());
+
+        match split {
+            Some((artist, title))
+                if known_artists.is_empty()
+                    || known_artists
+                        .iter()
+                        .any(|known| Self::is_same_name(known, &self.extract_featured(artist).0)) =>
+                (Some(Self::trim(artist).to_owned()), title.to_owned()),
+            _ => (None, stripped),
+        }
+    }
+
+    fn strip_noise(&self, title: &str) -> ::std::string::String {
+        Self::trim(&NOISE_PATTERN.replace_all(title, "")).to_owned()
+    }
+
+    fn extract_featured(&self, text: &str) -> (::std::string::String, Vec<::std::string::String>) {
+        let mut featured = Vec::new();
+
+        let text = FEATURING_PATTERN.replace_all(text, |captures: &::regex::Captures| {
+            let names = captures.get(1).or_else(|| captures.get(2)).map_or("", |names| names.as_str());
+
+            featured.extend(
+                SEPARATOR_PATTERN
+                    .split(names)
+                    .map(Self::trim)
+                    .filter(|name| name.is_empty().not())
+                    .map(str::to_owned),
+            );
+
+            ""
+        });
+
+        (Self::trim(&text).to_owned(), featured)
+    }
+
+    fn trim(text: &str) -> &str {
+        text.trim_matches(|character: char| character.is_whitespace() || ['"', '“', '”'].contains(&character))
+    }
+}
+
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct CoverArtWriter {
+    cover_art_policy: CoverArtPolicy,
+    folder_cover_policy: FolderCoverPolicy,
+    workers: u64,
+    executor: ::std::sync::Arc<dyn CommandExecutor>,
+    profile: DeviceProfile,
+}
+
+pub enum CoverArtPolicy {
+    UseVideoThumbnail,
+    UsePlaylistThumbnail,
+}
+
+pub enum FolderCoverPolicy {
+    WriteFolderCovers,
+    SkipFolderCovers,
+}
+
+#[async_trait]
+impl PostProcessor<ResolvedVideo> for CoverArtWriter {
+    async fn process(self: ::std::sync::Arc<Self>, video: &mut ResolvedVideo) -> Fallible<()> {
+        self.write_video_covers(vec![&video.path]).await
+    }
+}
+
+#[async_trait]
+impl PostProcessor<ResolvedPlaylist> for CoverArtWriter {
+    async fn process(self: ::std::sync::Arc<Self>, playlist: &mut ResolvedPlaylist) -> Fallible<()> {
+        self.write_playlist_covers(playlist).await
+    }
+}
+
+#[async_trait]
+impl PostProcessor<ResolvedChannel> for CoverArtWriter {
+    async fn process(self: ::std::sync::Arc<Self>, channel: &mut ResolvedChannel) -> Fallible<()> {
+        self.write_video_covers(
+            channel
+                .videos
+                .as_deref()
+                .into_iter()
+                .flatten()
+                .map(|video| &*video.path)
+                .collect(),
+        )
+        .await?;
+
+        for playlist in channel.playlists.as_deref().into_iter().flatten() {
+            self.write_playlist_covers(playlist).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl CoverArtWriter {
+    const COVER_EXTENSION: &str = "cover.jpg";
+    const FOLDER_COVER_NAME: &str = "folder.jpg";
+    // yt-dlp leaves the thumbnail beside the track under the same name
+    const THUMBNAIL_EXTENSION: &str = "jpg";
+
+    async fn write_playlist_covers(&self, playlist: &ResolvedPlaylist) -> Fallible<()> {
+        let paths: Vec<_> = playlist
+            .videos
+            .as_deref()
+            .into_iter()
+            .flatten()
+            .map(|video| &*video.path)
+            .filter(|path| SplitVideoDetector::is_split(path).not())
+            .collect();
+
+        let thumbnail_url = match (&self.cover_art_policy, self.profile.codec.carries_id3_tags()) {
+            (CoverArtPolicy::UsePlaylistThumbnail, true) => playlist.metadata.thumbnail_url.as_deref(),
+            _ => None,
+        };
+
+        let Some(thumbnail_url) = thumbnail_url else {
+            return self.write_video_covers(paths).await;
+        };
+
+        // Tracks that got the cover before are left alone, so that the thumbnail is
+        // only fetched when some track is still missing it
+        let mut pending = Vec::new();
+
+        for path in &paths {
+            let covered = Id3TagReader::read(path)?
+                .pictures()
+                .any(|picture| picture.picture_type == ::id3::frame::PictureType::CoverFront);
+
+            if covered.not() {
+                pending.push(*path);
+            }
+        }
+
+        if let Some(first) = pending.first() {
+            let cover =
+                first.with_file_name(format!("{}.{}", MediaFileNamer::sanitize(&playlist.id), Self::COVER_EXTENSION));
+
+            let written = async {
+                self.crop(thumbnail_url, &cover).await?;
+
+                let data = ::tokio::fs::read(&cover).await?;
+
+                for path in &pending {
+                    self.embed(path, data.clone())?;
+                }
+
+                self.write_folder_cover(&cover).await
+            };
+
+            let written = written.await;
+
+            ::tokio::fs::remove_file(&cover).await.ok();
+
+            written?;
+        }
+
+        // The playlist cover stands in for the video thumbnails, which are of no
+        // further use
+        for path in paths {
+            ::tokio::fs::remove_file(path.with_extension(Self::THUMBNAIL_EXTENSION))
+                .await
+                .ok();
+        }
+
+        Ok(())
+    }
+
+    // Files without ID3 tags keep their thumbnails beside them, which is where
+    // players look for covers of such files
+    async fn write_video_covers(&self, paths: Vec<&::std::path::Path>) -> Fallible<()> {
+        if self.profile.codec.carries_id3_tags().not() {
+            return Ok(());
+        }
+
+        let mut seen = ::std::collections::HashSet::new();
+        let mut pending = Vec::new();
+
+        for path in paths
+            .into_iter()
+            .filter(|path| SplitVideoDetector::is_split(path).not() && seen.insert(*path))
+        {
+            let thumbnail = path.with_extension(Self::THUMBNAIL_EXTENSION);
+
+            if ::tokio::fs::try_exists(&thumbnail).await? {
+                pending.push((path.to_path_buf(), thumbnail));
+            }
+        }
+
+        ::futures::stream::iter(pending)
+            .map(|(path, thumbnail)| self.write_video_cover(path, thumbnail))
+            .buffer_unordered(self.workers.max(1) as usize)
+            .try_collect()
+            .await
+    }
+
+    async fn write_video_cover(&self, path: ::std::path::PathBuf, thumbnail: ::std::path::PathBuf) -> Fallible<()> {
+        let cover = path.with_extension(Self::COVER_EXTENSION);
+
+        let written = async {
+            self.crop(thumbnail.to_str().ok()?, &cover).await?;
+            self.embed(&path, ::tokio::fs::read(&cover).await?)?;
+            self.write_folder_cover(&cover).await
+        };
+
+        let written = written.await;
+
+        ::tokio::fs::remove_file(&cover).await.ok();
+
+        written?;
+
+        // The thumbnail is only removed once it is embedded, so that a failure leaves
+        // it for the next attempt
+        ::tokio::fs::remove_file(&thumbnail).await?;
+
+        Ok(())
+    }
+
+    // ffmpeg reads local files and URLs alike, so that playlist thumbnails need no
+    // separate download
+    async fn crop(&self, source: &str, cover: &::std::path::Path) -> Fallible<()> {
+        let filter =
+            format!("crop='min(iw,ih)':'min(iw,ih)',scale='min({0},iw)':'min({0},ih)'", self.profile.max_cover_size);
+
+        #[rustfmt::skip]
+        let args = [
+            "-hide_banner", "-nostdin", "-y",
+            "-i", source,
+            "-frames:v", "1",
+            "-vf", &filter,
+            "-c:v", "mjpeg", "-q:v", "2",
+            "-f", "image2", cover.to_str().ok()?,
+        ];
+
+        CommandRunner::run(&*self.executor, "ffmpeg", &args).await?;
+
+        Ok(())
+    }
+
+    fn embed(&self, path: &::std::path::Path, data: Vec<u8>) -> Fallible<()> {
+        use ::id3::TagLike as _;
+
+        let mut tag = Id3TagReader::read(path)?;
+
+        tag.remove_picture_by_type(::id3::frame::PictureType::CoverFront);
+        tag.add_frame(::id3::frame::Picture {
+            mime_type: "image/jpeg".into(),
+            picture_type: ::id3::frame::PictureType::CoverFront,
+            description: ::std::string::String::new(),
+            data,
+        });
+
+        Id3TagWriter::write(&tag, path, &self.profile)
+    }
+
+    // Folders keep the first cover they get, so that playlists sharing a folder do
+    // not keep replacing it
+    async fn write_folder_cover(&self, cover: &::std::path::Path) -> Fallible<()> {
+        let FolderCoverPolicy::WriteFolderCovers = self.folder_cover_policy else {
+            return Ok(());
+        };
+
+        let folder_cover = cover.with_file_name(Self::FOLDER_COVER_NAME);
+
+        if ::tokio::fs::try_exists(&folder_cover).await?.not() {
+            ::tokio::fs::copy(cover, &folder_cover).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct LoudnessNormalizer {
+    normalization_policy: LoudnessNormalizationPolicy,
+    target_loudness: f64,
+    true_peak: f64,
+
+    #[builder(default = 11.0)]
+    loudness_range: f64,
+
+    workers: u64,
+    executor: ::std::sync::Arc<dyn CommandExecutor>,
+    profile: DeviceProfile,
+}
+
+pub enum LoudnessNormalizationPolicy {
+    NormalizeEachTrack,
+    KeepAlbumLevels,
+}
+
+#[async_trait]
+impl PostProcessor<ResolvedVideo> for LoudnessNormalizer {
+    async fn process(self: ::std::sync::Arc<Self>, video: &mut ResolvedVideo) -> Fallible<()> {
+        self.normalize_tracks(vec![&video.path]).await
+    }
+}
+
+#[async_trait]
+impl PostProcessor<ResolvedPlaylist> for LoudnessNormalizer {
+    async fn process(self: ::std::sync::Arc<Self>, playlist: &mut ResolvedPlaylist) -> Fallible<()> {
+        self.normalize_playlist(playlist).await
+    }
+}
+
+#[async_trait]
+impl PostProcessor<ResolvedChannel> for LoudnessNormalizer {
+    async fn process(self: ::std::sync::Arc<Self>, channel: &mut ResolvedChannel) -> Fallible<()> {
+        self.normalize_tracks(
+            channel
+                .videos
+                .as_deref()
+                .into_iter()
+                .flatten()
+                .map(|video| &*video.path)
+                .collect(),
+        )
+        .await?;
+
+        // Playlists go one after another, since each of them already keeps every worker
+        // busy
+        for playlist in channel.playlists.as_deref().into_iter().flatten() {
+            self.normalize_playlist(playlist).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl LoudnessNormalizer {
+    const INPUT_LOUDNESS_DESCRIPTION: &str = "LOUDNORM_INPUT_I";
+    const INPUT_LOUDNESS_RANGE_DESCRIPTION: &str = "LOUDNORM_INPUT_LRA";
+    const INPUT_THRESHOLD_DESCRIPTION: &str = "LOUDNORM_INPUT_THRESH";
+    const INPUT_TRUE_PEAK_DESCRIPTION: &str = "LOUDNORM_INPUT_TP";
+    const MODE_DESCRIPTION: &str = "LOUDNORM_MODE";
+    const SETTINGS_DESCRIPTION: &str = "LOUDNORM_SETTINGS";
+
+    async fn normalize_playlist(&self, playlist: &ResolvedPlaylist) -> Fallible<()> {
+        let paths = playlist
+            .videos
+            .as_deref()
+            .into_iter()
+            .flatten()
+            .map(|video| &*video.path)
+            .collect();
+
+        match self.normalization_policy {
+            LoudnessNormalizationPolicy::NormalizeEachTrack => self.normalize_tracks(paths).await,
+            LoudnessNormalizationPolicy::KeepAlbumLevels => self.normalize_album(paths).await,
+        }
+    }
+
+    // A track that could not be normalized is tried again next time, so that the
+    // other tracks need not wait for it
+    async fn normalize_tracks(&self, paths: Vec<&::std::path::Path>) -> Fallible<()> {
+        let (_, pending) = self.partition(paths)?;
+
+        ::futures::stream::iter(pending)
+            .map(|path| async move {
+                if let Err(error) = self.normalize_track(&path).await {
+                    ::tracing::warn!("Failed to normalize `{}` `{}`", path.display(), error);
+                }
+            })
+            .buffer_unordered(self.workers.max(1) as usize)
+            .collect::<()>()
+            .await;
+
+        Ok(())
+    }
+
+    async fn normalize_track(&self, path: &::std::path::Path) -> Fallible<()> {
+        let measurement = self.measure(path).await?;
+
+        let normalization = LoudnessNormalization {
+            mode: "track",
+            filter: measurement.is_audible().then(|| {
+                format!(
+                    "{}:measured_I={}:measured_TP={}:measured_LRA={}:measured_thresh={}:offset={}:linear=true",
+                    self.loudnorm_filter(),
+                    measurement.input_i,
+                    measurement.input_tp,
+                    measurement.input_lra,
+                    measurement.input_thresh,
+                    measurement.target_offset,
+                )
+            }),
+            measurement: &measurement,
+        };
+
+        self.apply(path, &normalization).await
+    }
+
+    // Tracks that were normalized before keep their levels, but what was recorded
+    // of them still counts towards the album, so that the pending ones are brought
+    // to the gain of the whole album rather than of themselves
+    async fn normalize_album(&self, paths: Vec<&::std::path::Path>) -> Fallible<()> {
+        let (normalized, pending) = self.partition(paths)?;
+
+        let measurements: Vec<_> = ::futures::stream::iter(pending)
+            .map(|path| async move {
+                match self.measure(&path).await {
+                    Ok(measurement) => Some((measurement, path)),
+                    Err(error) => {
+                        ::tracing::warn!("Failed to measure `{}` `{}`", path.display(), error);
+
+                        None
+                    },
+                }
+            })
+            .buffer_unordered(self.workers.max(1) as usize)
+            .filter_map(::futures::future::ready)
+            .collect()
+            .await;
+
+        let audible: Vec<_> = measurements
+            .iter()
+            .map(|(measurement, _)| (measurement.loudness(), measurement.true_peak()))
+            .chain(normalized.iter().map(|recorded| (recorded.loudness, recorded.true_peak)))
+            .filter(|(loudness, true_peak)| loudness.is_finite() && true_peak.is_finite())
+            .collect();
+
+        // The album loudness is the mean energy of its tracks, and the gain is
+        // held back wherever the loudest peak would otherwise break the ceiling
+        let gain = audible.is_empty().not().then(|| {
+            let energy =
+                audible.iter().map(|(loudness, _)| 10f64.powf(loudness / 10.0)).sum::<f64>() / audible.len() as f64;
+            let peak = audible
+                .iter()
+                .map(|(_, true_peak)| *true_peak)
+                .fold(f64::NEG_INFINITY, f64::max);
+
+            (self.target_loudness - 10.0 * energy.log10()).min(self.true_peak - peak)
+        });
+
+        ::futures::stream::iter(measurements)
+            .map(|(measurement, path)| async move {
+                let normalization = LoudnessNormalization {
+                    mode: "album",
+                    filter: gain.map(|gain| format!("volume={:.2}dB", gain)),
+                    measurement: &measurement,
+                };
+
+                if let Err(error) = self.apply(&path, &normalization).await {
+                    ::tracing::warn!("Failed to normalize `{}` `{}`", path.display(), error);
+                }
+            })
+            .buffer_unordered(self.workers.max(1) as usize)
+            .collect::<()>()
+            .await;
 
         Ok(())
     }
+
+    // The measurements are recorded in ID3 tags, so that files without them are
+    // never normalized. Normalized tracks come back as the input loudness and
+    // true peak they were recorded with
+    fn partition(
+        &self, paths: Vec<&::std::path::Path>,
+    ) -> Fallible<(Vec<RecordedLoudness>, Vec<::std::path::PathBuf>)> {
+        if self.profile.codec.carries_id3_tags().not() {
+            return Ok((Vec::new(), Vec::new()));
+        }
+
+        let settings = self.settings();
+        let mut seen = ::std::collections::HashSet::new();
+        let mut normalized = Vec::new();
+        let mut pending = Vec::new();
+
+        for path in paths
+            .into_iter()
+            .filter(|path| SplitVideoDetector::is_split(path).not() && seen.insert(*path))
+        {
+            let tag = Id3TagReader::read(path)?;
+
+            let recorded = |description: &str| {
+                tag.extended_texts()
+                    .find(|text| text.description == description)
+                    .and_then(|text| text.value.parse::<f64>().ok())
+                    .unwrap_or(f64::NEG_INFINITY)
+            };
+
+            let is_normalized = tag
+                .extended_texts()
+                .any(|text| text.description == Self::SETTINGS_DESCRIPTION && text.value == settings);
+
+            match is_normalized {
+                true => normalized.push(RecordedLoudness {
+                    loudness: recorded(Self::INPUT_LOUDNESS_DESCRIPTION),
+                    true_peak: recorded(Self::INPUT_TRUE_PEAK_DESCRIPTION),
+                }),
+                false => pending.push(path.to_path_buf()),
+            }
+        }
+
+        Ok((normalized, pending))
+    }
+
+    async fn measure(&self, path: &::std::path::Path) -> Fallible<LoudnormMeasurement> {
+        let filter = format!("{}:print_format=json", self.loudnorm_filter());
+
+        #[rustfmt::skip]
+        let args = [
+            "-hide_banner", "-nostdin",
+            "-i", path.to_str().ok()?,
+            "-map", "0:a:0",
+            "-af", &filter,
+            "-f", "null", "-",
+        ];
+
+        let lines = CommandRunner::run(&*self.executor, "ffmpeg", &args).await?;
+
+        LoudnormReportParser::parse(&lines.stderr)
+    }
+
+    async fn apply(&self, path: &::std::path::Path, normalization: &LoudnessNormalization<'_>) -> Fallible<()> {
+        use ::id3::TagLike as _;
+
+        let mut tag = Id3TagReader::read(path)?;
+
+        tag.add_frame(::id3::frame::ExtendedText {
+            description: Self::SETTINGS_DESCRIPTION.into(),
+            value: self.settings(),
+        });
+        tag.add_frame(::id3::frame::ExtendedText {
+            description: Self::MODE_DESCRIPTION.into(),
+            value: normalization.mode.into(),
+        });
+        tag.add_frame(::id3::frame::ExtendedText {
+            description: Self::INPUT_LOUDNESS_DESCRIPTION.into(),
+            value: normalization.measurement.input_i.to_string(),
+        });
+        tag.add_frame(::id3::frame::ExtendedText {
+            description: Self::INPUT_TRUE_PEAK_DESCRIPTION.into(),
+            value: normalization.measurement.input_tp.to_string(),
+        });
+        tag.add_frame(::id3::frame::ExtendedText {
+            description: Self::INPUT_LOUDNESS_RANGE_DESCRIPTION.into(),
+            value: normalization.measurement.input_lra.to_string(),
+        });
+        tag.add_frame(::id3::frame::ExtendedText {
+            description: Self::INPUT_THRESHOLD_DESCRIPTION.into(),
+            value: normalization.measurement.input_thresh.to_string(),
+        });
+
+        // Silent tracks have nothing to normalize, so that they are only marked
+        let Some(filter) = normalization.filter.as_deref() else {
+            return Id3TagWriter::write(&tag, path, &self.profile);
+        };
+
+        let partial_path = path.with_file_name(format!("{}.part", path.file_name().ok()?.to_string_lossy()));
+
+        let [bitrate_option, bitrate] = self.profile.bitrate.ffmpeg_args();
+        let sample_rate = self.profile.max_sample_rate.to_string();
+        let channels = self.profile.channels.to_string();
+
+        let encoded = async {
+            #[rustfmt::skip]
+            let args = [
+                "-hide_banner", "-nostdin", "-y",
+                "-i", path.to_str().ok()?,
+                "-map", "0:a:0",
+                "-af", filter,
+                "-map_metadata", "-1",
+                "-c:a", self.profile.codec.ffmpeg_encoder(),
+                &bitrate_option, &bitrate,
+                "-ar", &sample_rate,
+                "-ac", &channels,
+                "-f", self.profile.codec.ffmpeg_muxer(), partial_path.to_str().ok()?,
+            ];
+
+            CommandRunner::run(&*self.executor, "ffmpeg", &args).await?;
+
+            Id3TagWriter::write(&tag, &partial_path, &self.profile)?;
+
+            Fallible::Ok(())
+        };
+
+        match encoded.await {
+            Ok(()) => Ok(::tokio::fs::rename(&partial_path, path).await?),
+            Err(error) => {
+                ::tokio::fs::remove_file(&partial_path).await.ok();
+
+                Err(error)
+            },
+        }
+    }
+
+    fn loudnorm_filter(&self) -> ::std::string::String {
+        format!("loudnorm=I={}:TP={}:LRA={}", self.target_loudness, self.true_peak, self.loudness_range)
+    }
+
+    fn settings(&self) -> ::std::string::String {
+        format!("I={}:TP={}:LRA={}", self.target_loudness, self.true_peak, self.loudness_range)
+    }
+}
+
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct LyricsWriter {
+    languages: MaybeOwnedVec<MaybeOwnedString>,
+    sidecar_policy: LyricsSidecarPolicy,
+    workers: u64,
+
+    #[builder(default)]
+    rate_limiter: ::std::sync::Arc<RateLimiter>,
+
+    executor: ::std::sync::Arc<dyn CommandExecutor>,
+    profile: DeviceProfile,
+}
+
+pub enum LyricsSidecarPolicy {
+    WriteLrcFiles,
+    SkipLrcFiles,
+}
+
+#[async_trait]
+impl PostProcessor<ResolvedVideo> for LyricsWriter {
+    async fn process(self: ::std::sync::Arc<Self>, video: &mut ResolvedVideo) -> Fallible<()> {
+        self.write_all(vec![video]).await
+    }
+}
+
+#[async_trait]
+impl PostProcessor<ResolvedPlaylist> for LyricsWriter {
+    async fn process(self: ::std::sync::Arc<Self>, playlist: &mut ResolvedPlaylist) -> Fallible<()> {
+        self.write_all(playlist.videos.as_deref().into_iter().flatten().collect()).await
+    }
+}
+
+#[async_trait]
+impl PostProcessor<ResolvedChannel> for LyricsWriter {
+    async fn process(self: ::std::sync::Arc<Self>, channel: &mut ResolvedChannel) -> Fallible<()> {
+        self.write_all(
+            channel
+                .videos
+                .as_deref()
+                .into_iter()
+                .flatten()
+                .chain(
+                    channel
+                        .playlists
+                        .as_deref()
+                        .into_iter()
+                        .flatten()
+                        .flat_map(|playlist| playlist.videos.as_deref().into_iter().flatten()),
+                )
+                .collect(),
+        )
+        .await
+    }
+}
+
+#[::bon::bon]
+impl LyricsWriter {
+    const LRC_EXTENSION: &str = "lrc";
+    const MARKER_DESCRIPTION: &str = "WALKMAN_LYRICS";
+    const NO_LYRICS_MARKER: &str = "none";
+    const SUBTITLE_EXTENSION: &str = "vtt";
+    // yt-dlp appends the language and the format to it
+    const SUBTITLE_INFIX: &str = "lyrics";
+    // Subtitles come with two-letter languages, while ID3 wants three
+    const UNKNOWN_LANGUAGE: &str = "XXX";
+
+    async fn write_all(&self, videos: Vec<&ResolvedVideo>) -> Fallible<()> {
+        let tracks: Vec<_> = videos
+            .into_iter()
+            .filter(|video| SplitVideoDetector::is_split(&video.path).not())
+            .map(|video| (video.path.to_path_buf(), video.url.to_string()))
+            .collect();
+
+        ::futures::stream::iter(tracks)
+            .map(|(path, url)| self.write(path, url))
+            .buffer_unordered(self.workers.max(1) as usize)
+            .try_collect()
+            .await
+    }
+
+    async fn write(&self, path: ::std::path::PathBuf, url: ::std::string::String) -> Fallible<()> {
+        use ::id3::TagLike as _;
+
+        let sidecar = path.with_extension(Self::LRC_EXTENSION);
+        let sidecar_exists = ::tokio::fs::try_exists(&sidecar).await?;
+
+        let tag = match self.profile.codec.carries_id3_tags() {
+            true => Some(Id3TagReader::read(&path)?),
+            false => None,
+        };
+
+        let embeds = tag.as_ref().is_some_and(|tag| tag.lyrics().next().is_none());
+        let writes_sidecar = matches!(self.sidecar_policy, LyricsSidecarPolicy::WriteLrcFiles) && sidecar_exists.not();
+        let marked = tag.as_ref().is_some_and(|tag| {
+            tag.extended_texts()
+                .any(|text| text.description == Self::MARKER_DESCRIPTION && text.value == Self::NO_LYRICS_MARKER)
+        });
+
+        // Tracks that have their lyrics already, or were found to have none, are left
+        // alone, so that subtitles are only requested once
+        if (embeds.not() && writes_sidecar.not()) || (marked && sidecar_exists.not()) {
+            return Ok(());
+        }
+
+        // Sidecars put there by hand are taken over, so that they need no subtitles
+        let lines = match sidecar_exists {
+            true => LrcParser::parse(&::tokio::fs::read_to_string(&sidecar).await?),
+            false => match self.fetch(&path, &url).await {
+                Ok(lines) => lines,
+                // A track whose subtitles could not be had is tried again next time, so
+                // that the other tracks need not wait for it
+                Err(error) => {
+                    ::tracing::warn!("Failed to fetch lyrics for `{}` `{}`", path.display(), error);
+
+                    return Ok(());
+                },
+            },
+        };
+
+        if lines.is_empty() {
+            ::tracing::debug!("Found no lyrics for `{}`", path.display());
+
+            if let Some(mut tag) = tag {
+                tag.add_frame(::id3::frame::ExtendedText {
+                    description: Self::MARKER_DESCRIPTION.into(),
+                    value: Self::NO_LYRICS_MARKER.into(),
+                });
+
+                Id3TagWriter::write(&tag, &path, &self.profile)?;
+            }
+
+            return Ok(());
+        }
+
+        if embeds {
+            self.embed(&path, &lines)?;
+        }
+
+        if writes_sidecar {
+            ::tokio::fs::write(&sidecar, LrcFormatter::format(&lines)).await?;
+        }
+
+        Ok(())
+    }
+
+    // Manual captions are asked for first, so that automatic ones only stand in
+    // when none of the languages has any
+    async fn fetch(&self, path: &::std::path::Path, url: &str) -> Fallible<Vec<LyricLine>> {
+        // Imported files have no page to request subtitles from
+        if url.starts_with("http").not() {
+            return Ok(Vec::new());
+        }
+
+        let mut failure = None;
+
+        for captions in ["--write-subs", "--write-auto-subs"] {
+            match self.fetch_captions().path(path).url(url).captions(captions).call().await {
+                Ok(lines) if lines.is_empty().not() => return Ok(lines),
+                Ok(_) => {},
+                Err(error) => {
+                    ::tracing::debug!("Failed to fetch `{}` for `{}` `{}`", captions, url, error);
+
+                    failure = Some(error);
+                },
+            }
+        }
+
+        // Nothing is known to be missing when any of the attempts failed
+        match failure {
+            Some(error) => Err(error),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    #[builder]
+    async fn fetch_captions(&self, path: &::std::path::Path, url: &str, captions: &str) -> Fallible<Vec<LyricLine>> {
+        self.rate_limiter.acquire(1).await;
+
+        let stem = path.with_extension(Self::SUBTITLE_INFIX);
+        // yt-dlp expands percent signs in the template, so that the ones in the path
+        // are escaped
+        let output = format!("{}.%(ext)s", stem.to_str().ok()?.replace('%', "%%"));
+        let languages = self.languages.join(",");
+
+        #[rustfmt::skip]
+        let args = [
+            url,
+            "--quiet",
+            "--no-warnings",
+            "--color", "no_color",
+            "--no-playlist",
+            "--skip-download",
+            captions,
+            "--sub-langs", &languages,
+            "--sub-format", Self::SUBTITLE_EXTENSION,
+            "--output", &output,
+        ];
+
+        CommandRunner::run(&*self.executor, "yt-dlp", &args).await?;
+
+        let prefix = format!("{}.", stem.file_name().ok()?.to_string_lossy());
+        let suffix = format!(".{}", Self::SUBTITLE_EXTENSION);
+
+        let mut subtitles = Vec::new();
+        let mut entries = ::tokio::fs::read_dir(stem.parent().ok()?).await?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().into_owned();
+
+            if let Some(language) = name.strip_prefix(&prefix).and_then(|name| name.strip_suffix(&suffix)) {
+                subtitles.push((language.to_owned(), entry.path()));
+            }
+        }
+
+        subtitles.sort();
+
+        // Languages are tried in the given order, since yt-dlp writes every one it
+        // finds
+        let chosen = self
+            .languages
+            .iter()
+            .find_map(|language| subtitles.iter().find(|(found, _)| found == &**language))
+            .or(subtitles.first())
+            .map(|(_, path)| path.clone());
+
+        let lines = match chosen {
+            Some(path) => ::tokio::fs::read_to_string(path).await.map(|text| WebVttParser::parse(&text)),
+            None => Ok(Vec::new()),
+        };
+
+        for (_, path) in &subtitles {
+            ::tokio::fs::remove_file(path).await.ok();
+        }
+
+        Ok(lines?)
+    }
+
+    fn embed(&self, path: &::std::path::Path, lines: &[LyricLine]) -> Fallible<()> {
+        use ::id3::TagLike as _;
+
+        let mut tag = Id3TagReader::read(path)?;
+
+        tag.remove_all_lyrics();
+        tag.remove_all_synchronised_lyrics();
+        tag.remove_extended_text(Some(Self::MARKER_DESCRIPTION), None);
+        tag.add_frame(::id3::frame::Lyrics {
+            lang: Self::UNKNOWN_LANGUAGE.into(),
+            description: ::std::string::String::new(),
+            text: lines.iter().map(|line| &*line.text).collect::<Vec<_>>().join("\n"),
+        });
+        tag.add_frame(::id3::frame::SynchronisedLyrics {
+            lang: Self::UNKNOWN_LANGUAGE.into(),
+            timestamp_format: ::id3::frame::TimestampFormat::Ms,
+            content_type: ::id3::frame::SynchronisedLyricsType::Lyrics,
+            description: ::std::string::String::new(),
+            content: lines.iter().map(|line| (line.timestamp, line.text.clone())).collect(),
+        });
+
+        Id3TagWriter::write(&tag, path, &self.profile)
+    }
+}
+
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct ChapterSplitter {
+    splitting_policy: ChapterSplittingPolicy,
+
+    #[builder(default)]
+    rate_limiter: ::std::sync::Arc<RateLimiter>,
+
+    executor: ::std::sync::Arc<dyn CommandExecutor>,
+    profile: DeviceProfile,
+}
+
+pub enum ChapterSplittingPolicy {
+    SplitIntoTracks,
+    MarkChapters,
+}
+
+#[async_trait]
+impl PostProcessor<ResolvedVideo> for ChapterSplitter {
+    async fn process(self: ::std::sync::Arc<Self>, video: &mut ResolvedVideo) -> Fallible<()> {
+        self.process_video(video).await
+    }
+}
+
+// Videos go one after another, since every one of them waits on the rate
+// limiter for its chapters anyway
+#[async_trait]
+impl PostProcessor<ResolvedPlaylist> for ChapterSplitter {
+    async fn process(self: ::std::sync::Arc<Self>, playlist: &mut ResolvedPlaylist) -> Fallible<()> {
+        for video in playlist.videos.iter_mut().flat_map(|videos| videos.to_mut().iter_mut()) {
+            self.process_video(video).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PostProcessor<ResolvedChannel> for ChapterSplitter {
+    async fn process(self: ::std::sync::Arc<Self>, channel: &mut ResolvedChannel) -> Fallible<()> {
+        for video in channel.videos.iter_mut().flat_map(|videos| videos.to_mut().iter_mut()) {
+            self.process_video(video).await?;
+        }
+
+        for playlist in channel.playlists.iter_mut().flat_map(|playlists| playlists.to_mut().iter_mut()) {
+            ::std::sync::Arc::clone(&self).process(playlist).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ChapterSplitter {
+    const CUE_SHEET_EXTENSION: &str = "cue";
+    const MARKER_DESCRIPTION: &str = "WALKMAN_CHAPTERS";
+    const NO_CHAPTERS_MARKER: &str = "none";
+    const TABLE_OF_CONTENTS_ID: &str = "toc";
+
+    async fn process_video(&self, video: &mut ResolvedVideo) -> Fallible<()> {
+        use ::id3::TagLike as _;
+
+        // Imported files have no page to request chapters from
+        if video.url.starts_with("http").not() {
+            return Ok(());
+        }
+
+        // Videos that were split before are recorded as the folder of their tracks,
+        // so that they are never split again
+        if SplitVideoDetector::is_split(&video.path) {
+            return Ok(());
+        }
+
+        // Files that were marked before keep their marks, so that chapters are only
+        // requested once
+        let cue_sheet = video.path.with_extension(Self::CUE_SHEET_EXTENSION);
+
+        if matches!(self.splitting_policy, ChapterSplittingPolicy::MarkChapters)
+            && ::tokio::fs::try_exists(&cue_sheet).await?
+        {
+            return Ok(());
+        }
+
+        let tag = match self.profile.codec.carries_id3_tags() {
+            true => Some(Id3TagReader::read(&video.path)?),
+            false => None,
+        };
+
+        // Videos that were found to have no chapters are marked, so that they are not
+        // requested again on every update
+        let marked = tag.as_ref().is_some_and(|tag| {
+            tag.extended_texts()
+                .any(|text| text.description == Self::MARKER_DESCRIPTION && text.value == Self::NO_CHAPTERS_MARKER)
+        });
+
+        if marked {
+            return Ok(());
+        }
+
+        let chapters = match self.fetch(&video.url).await {
+            Ok(chapters) => chapters,
+            // A video whose chapters could not be had is tried again next time, so that
+            // the other videos need not wait for it
+            Err(error) => {
+                ::tracing::warn!("Failed to fetch chapters for `{}` `{}`", video.path.display(), error);
+
+                return Ok(());
+            },
+        };
+
+        if chapters.len() < 2 {
+            ::tracing::debug!("Found no chapters for `{}`", video.path.display());
+
+            if let Some(mut tag) = tag {
+                tag.add_frame(::id3::frame::ExtendedText {
+                    description: Self::MARKER_DESCRIPTION.into(),
+                    value: Self::NO_CHAPTERS_MARKER.into(),
+                });
+
+                Id3TagWriter::write(&tag, &video.path, &self.profile)?;
+            }
+
+            return Ok(());
+        }
+
+        match self.splitting_policy {
+            ChapterSplittingPolicy::SplitIntoTracks => self.split(video, &chapters).await,
+            ChapterSplittingPolicy::MarkChapters => self.mark(video, &chapters).await,
+        }
+    }
+
+    // Chapters set by the uploader come first, and the tracklist in the
+    // description only stands in when there are none
+    async fn fetch(&self, url: &str) -> Fallible<Vec<VideoChapter>> {
+        self.rate_limiter.acquire(1).await;
+
+        #[rustfmt::skip]
+        let args = [
+            url,
+            "--quiet",
+            "--no-warnings",
+            "--color", "no_color",
+            "--no-playlist",
+            "--skip-download",
+            "--print", "%(.{chapters,description,duration})j",
+        ];
+
+        let lines = CommandRunner::run(&*self.executor, "yt-dlp", &args).await?;
+
+        let Some(line) = lines.stdout.iter().rfind(|line| line.trim().is_empty().not()) else {
+            return Ok(Vec::new());
+        };
+
+        let info: YtdlpChapterInfo = ::serde_json::from_str(line)?;
+
+        let chapters: Vec<_> = info
+            .chapters
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .map(|(position, chapter)| VideoChapter {
+                start: (chapter.start_time * 1000.0) as u32,
+                end: (chapter.end_time * 1000.0) as u32,
+                title: chapter
+                    .title
+                    .filter(|title| title.trim().is_empty().not())
+                    .unwrap_or_else(|| format!("Track {}", position + 1)),
+            })
+            .collect();
+
+        if chapters.is_empty().not() {
+            return Ok(chapters);
+        }
+
+        match (info.description.as_deref(), info.duration) {
+            (Some(description), Some(duration)) => Ok(TracklistParser::parse(description, (duration * 1000.0) as u32)),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    // The original is removed once every track is cut, so that the device does
+    // not play the album twice, and the video is recorded as the folder of its
+    // tracks from then on, so that it counts as downloaded and goes with them
+    async fn split(&self, video: &mut ResolvedVideo, chapters: &[VideoChapter]) -> Fallible<()> {
+        use ::id3::TagLike as _;
+
+        let folder = video.path.with_extension("");
+        ::tokio::fs::create_dir_all(&folder).await?;
+
+        let source = match self.profile.codec.carries_id3_tags() {
+            true => Id3TagReader::read(&video.path)?,
+            false => ::id3::Tag::new(),
+        };
+        let album = source.album().or(video.metadata.title.as_deref()).map(str::to_owned);
+        let max_file_stem_length = self.profile.max_file_stem_length(&folder);
+
+        for (position, chapter) in chapters.iter().enumerate() {
+            let stem = TrackFileNamer::name(position + 1, &chapter.title, max_file_stem_length);
+            let path = folder.join(format!("{}.{}", stem, self.profile.codec.extension()));
+            let partial_path = folder.join(format!("{}.{}.part", stem, self.profile.codec.extension()));
+
+            // Only the artists, genres and covers carry over, since the rest of the tag
+            // was about the whole video
+            let mut tag = ::id3::Tag::new();
+
+            source
+                .frames()
+                .filter(|frame| matches!(frame.id(), "TPE1" | "TPE2" | "TCON" | "APIC"))
+                .cloned()
+                .for_each(|frame| {
+                    tag.add_frame(frame);
+                });
+
+            if let Some(album) = album.as_deref() {
+                tag.set_album(album);
+            }
+
+            tag.set_title(&*chapter.title);
+            tag.set_track(position as u32 + 1);
+            tag.set_total_tracks(chapters.len() as u32);
+
+            let start = format!("{:.3}", chapter.start as f64 / 1000.0);
+            let end = format!("{:.3}", chapter.end as f64 / 1000.0);
+
+            let cut = async {
+                // The audio is copied as it is, so that cutting costs no quality
+                #[rustfmt::skip]
+                let args = [
+                    "-hide_banner", "-nostdin", "-y",
+                    "-i", video.path.to_str().ok()?,
+                    "-map", "0:a:0",
+                    "-ss", &start,
+                    "-to", &end,
+                    "-map_metadata", "-1",
+                    "-c:a", "copy",
+                    "-f", self.profile.codec.ffmpeg_muxer(), partial_path.to_str().ok()?,
+                ];
+
+                CommandRunner::run(&*self.executor, "ffmpeg", &args).await?;
+
+                if self.profile.codec.carries_id3_tags() {
+                    Id3TagWriter::write(&tag, &partial_path, &self.profile)?;
+                }
+
+                Fallible::Ok(())
+            };
+
+            match cut.await {
+                Ok(()) => ::tokio::fs::rename(&partial_path, &path).await?,
+                Err(error) => {
+                    ::tokio::fs::remove_file(&partial_path).await.ok();
+
+                    return Err(error);
+                },
+            }
+        }
+
+        ::tokio::fs::remove_file(&video.path).await?;
+
+        video.path = folder.into();
+
+        Ok(())
+    }
+
+    async fn mark(&self, video: &ResolvedVideo, chapters: &[VideoChapter]) -> Fallible<()> {
+        use ::id3::TagLike as _;
+
+        let mut tag = match self.profile.codec.carries_id3_tags() {
+            true => Some(Id3TagReader::read(&video.path)?),
+            false => None,
+        };
+
+        if let Some(tag) = tag.as_mut() {
+            tag.remove_all_chapters();
+            tag.remove_all_tables_of_contents();
+
+            let mut elements = Vec::with_capacity(chapters.len());
+
+            for (position, chapter) in chapters.iter().enumerate() {
+                let element_id = format!("chp{}", position);
+
+                tag.add_frame(::id3::frame::Chapter {
+                    element_id: element_id.clone(),
+                    start_time: chapter.start,
+                    end_time: chapter.end,
+                    // Byte offsets are left out, so that players go by the times
+                    start_offset: u32::MAX,
+                    end_offset: u32::MAX,
+                    frames: vec![::id3::Frame::text("TIT2", &*chapter.title)
+                        .set_encoding(Some(self.profile.text_encoding.into()))],
+                });
+
+                elements.push(element_id);
+            }
+
+            tag.add_frame(::id3::frame::TableOfContents {
+                element_id: Self::TABLE_OF_CONTENTS_ID.into(),
+                top_level: true,
+                ordered: true,
+                elements,
+                frames: Vec::new(),
+            });
+
+            Id3TagWriter::write(tag, &video.path, &self.profile)?;
+        }
+
+        let cue_sheet = CueSheet {
+            file_name: &video.path.file_name().ok()?.to_string_lossy(),
+            file_type: match self.profile.codec {
+                AudioCodec::Mp3 => "MP3",
+                AudioCodec::Aac | AudioCodec::Wma => "WAVE",
+            },
+            title: tag.as_ref().and_then(|tag| tag.album()).or(video.metadata.title.as_deref()),
+            performer: tag.as_ref().and_then(|tag| tag.artist()),
+            chapters,
+        };
+
+        ::tokio::fs::write(video.path.with_extension(Self::CUE_SHEET_EXTENSION), cue_sheet.format()).await?;
+
+        Ok(())
+    }
+}
+
+struct LoudnessNormalization<'a> {
+    mode: &'static str,
+    filter: Option<::std::string::String>,
+    measurement: &'a LoudnormMeasurement,
+}
+
+struct RecordedLoudness {
+    loudness: f64,
+    true_peak: f64,
+}
+
+// The values are kept as ffmpeg printed them, so that they can be handed back
+// to the second pass without losing any precision
+#[derive(::serde::Deserialize)]
+struct LoudnormMeasurement {
+    input_i: MaybeOwnedString,
+    input_tp: MaybeOwnedString,
+    input_lra: MaybeOwnedString,
+    input_thresh: MaybeOwnedString,
+    target_offset: MaybeOwnedString,
+}
+
+impl LoudnormMeasurement {
+    fn loudness(&self) -> f64 {
+        self.input_i.parse().unwrap_or(f64::NEG_INFINITY)
+    }
+
+    fn true_peak(&self) -> f64 {
+        self.input_tp.parse().unwrap_or(f64::NEG_INFINITY)
+    }
+
+    fn is_audible(&self) -> bool {
+        self.loudness().is_finite() && self.true_peak().is_finite()
+    }
+}
+
+struct LoudnormReportParser;
+
+impl LoudnormReportParser {
+    // The report is the last JSON object ffmpeg prints, one line per brace
+    fn parse(lines: &[MaybeOwnedString]) -> Fallible<LoudnormMeasurement> {
+        let Some(start) = lines.iter().rposition(|line| line.trim() == "{") else {
+            ::anyhow::bail!("ffmpeg did not report any loudness measurement");
+        };
+        let Some(end) = lines[start..].iter().position(|line| line.trim() == "}") else {
+            ::anyhow::bail!("ffmpeg reported an incomplete loudness measurement");
+        };
+
+        Ok(::serde_json::from_str(&lines[start..=start + end].join("\n"))?)
+    }
+}
+
+struct LyricLine {
+    // Milliseconds from the start of the track
+    timestamp: u32,
+    text: ::std::string::String,
+}
+
+struct WebVttParser;
+
+impl WebVttParser {
+    // Automatic captions repeat the previous line in every cue as they roll, so
+    // that lines matching the last one are dropped
+    fn parse(text: &str) -> Vec<LyricLine> {
+        let mut lines: Vec<LyricLine> = Vec::new();
+        let mut timestamp = None;
+
+        for line in text.lines() {
+            // Only empty lines end cues, while blank ones may sit inside them
+            if line.is_empty() {
+                timestamp = None;
+
+                continue;
+            }
+
+            if let Some((start, _)) = line.split_once("-->") {
+                timestamp = TimestampParser::parse(start.trim());
+
+                continue;
+            }
+
+            // Headers, notes and cue identifiers precede any timing
+            let Some(timestamp) = timestamp else {
+                continue;
+            };
+
+            let text = Self::clean(line);
+
+            if text.is_empty() || lines.last().is_some_and(|last| last.text == text) {
+                continue;
+            }
+
+            lines.push(LyricLine { timestamp, text });
+        }
+
+        lines
+    }
+
+    // Markup, entities and sound annotations such as `[Music]` are no lyrics
+    fn clean(line: &str) -> ::std::string::String {
+        let mut text = ::std::string::String::new();
+        let mut tagged = false;
+
+        for character in line.chars() {
+            match character {
+                '<' => tagged = true,
+                '>' => tagged = false,
+                _ if tagged.not() => text.push(character),
+                _ => {},
+            }
+        }
+
+        let text = text
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&nbsp;", " ")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&amp;", "&");
+        let text = text
+            .split_whitespace()
+            .filter(|word| *word != "♪")
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        match text.starts_with('[') && text.ends_with(']') {
+            true => ::std::string::String::new(),
+            false => text,
+        }
+    }
+}
+
+struct LrcParser;
+
+impl LrcParser {
+    // Lines may carry several timestamps, so that repeated choruses are written
+    // once
+    fn parse(text: &str) -> Vec<LyricLine> {
+        let mut lines = Vec::new();
+
+        for line in text.lines() {
+            let mut rest = line.trim();
+            let mut timestamps = Vec::new();
+
+            // Tags that are no timestamps, such as `[ar:Artist]`, hold no lyrics
+            while let Some((tag, remainder)) = rest.strip_prefix('[').and_then(|rest| rest.split_once(']')) {
+                let Some(timestamp) = TimestampParser::parse(tag) else {
+                    break;
+                };
+
+                timestamps.push(timestamp);
+                rest = remainder;
+            }
+
+            lines.extend(
+                timestamps
+                    .into_iter()
+                    .map(|timestamp| LyricLine { timestamp, text: rest.trim().to_owned() }),
+            );
+        }
+
+        lines.sort_by_key(|line| line.timestamp);
+
+        lines
+    }
+}
+
+struct LrcFormatter;
+
+impl LrcFormatter {
+    fn format(lines: &[LyricLine]) -> ::std::string::String {
+        lines
+            .iter()
+            .map(|line| {
+                format!(
+                    "[{:02}:{:02}.{:02}]{}\n",
+                    line.timestamp / 60_000,
+                    line.timestamp / 1000 % 60,
+                    line.timestamp % 1000 / 10,
+                    line.text
+                )
+            })
+            .collect()
+    }
+}
+
+struct TimestampParser;
+
+impl TimestampParser {
+    // Fractions are read as milliseconds, whether they come with the three digits
+    // of WebVTT or the two of LRC. Timestamps past what fits are no timestamps,
+    // so that they are skipped rather than wrapped around
+    fn parse(timestamp: &str) -> Option<u32> {
+        let (clock, fraction) = timestamp.split_once('.').unwrap_or((timestamp, "0"));
+
+        let seconds = clock
+            .split(':')
+            .try_fold(0_u32, |seconds, part| seconds.checked_mul(60)?.checked_add(part.parse::<u32>().ok()?))?;
+        let milliseconds = format!("{:0<3}", fraction).get(..3)?.parse::<u32>().ok()?;
+
+        seconds.checked_mul(1000)?.checked_add(milliseconds)
+    }
+}
+
+#[derive(::serde::Deserialize)]
+struct YtdlpChapterInfo {
+    chapters: Option<Vec<YtdlpChapter>>,
+    description: Option<::std::string::String>,
+    duration: Option<f64>,
+}
+
+#[derive(::serde::Deserialize)]
+struct YtdlpChapter {
+    start_time: f64,
+    end_time: f64,
+    title: Option<::std::string::String>,
+}
+
+struct VideoChapter {
+    // Milliseconds from the start of the video
+    start: u32,
+    end: u32,
+    title: ::std::string::String,
+}
+
+struct TracklistParser;
+
+impl TracklistParser {
+    // Any line holding a timestamp is taken for a track, such as `03:15 Title`,
+    // `1. Title - 1:03:15` or `[03:15] Title`
+    fn parse(description: &str, duration: u32) -> Vec<VideoChapter> {
+        let mut chapters: Vec<VideoChapter> = Vec::new();
+
+        for line in description.lines() {
+            let words: Vec<_> = line.split_whitespace().collect();
+
+            let Some((position, start)) = words.iter().enumerate().find_map(|(position, word)| {
+                let word = word.trim_matches(['(', ')', '[', ']']);
+
+                word.contains(':')
+                    .then(|| TimestampParser::parse(word))
+                    .flatten()
+                    .map(|start| (position, start))
+            }) else {
+                continue;
+            };
+
+            // Tracklists count up, so that anything else is some other mention
+            if start >= duration || chapters.last().is_some_and(|last| start <= last.start) {
+                continue;
+            }
+
+            let title = words
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| *index != position)
+                .map(|(_, word)| *word)
+                .skip_while(|word| word.trim_end_matches(['.', ')']).parse::<u64>().is_ok())
+                .collect::<Vec<_>>()
+                .join(" ");
+            let title = title.trim_matches(|character: char| {
+                character.is_whitespace() || ['-', '–', '—', '|', ':'].contains(&character)
+            });
+
+            if let Some(last) = chapters.last_mut() {
+                last.end = start;
+            }
+
+            chapters.push(VideoChapter {
+                start,
+                end: duration,
+                title: match title.is_empty() {
+                    true => format!("Track {}", chapters.len() + 1),
+                    false => title.to_owned(),
+                },
+            });
+        }
+
+        // Whatever comes before the first track belongs to it, so that nothing is
+        // cut away
+        if let Some(first) = chapters.first_mut() {
+            first.start = 0;
+        }
+
+        chapters
+    }
+}
+
+struct SplitVideoDetector;
+
+impl SplitVideoDetector {
+    // Only split videos are recorded as folders, which the other post-processors
+    // leave alone, since their tracks were tagged as they were cut
+    fn is_split(path: &::std::path::Path) -> bool {
+        path.is_dir()
+    }
+}
+
+struct TrackFileNamer;
+
+impl TrackFileNamer {
+    // Characters that no filesystem takes are replaced, and the name is cut down
+    // to what the device takes
+    fn name(number: usize, title: &str, max_length: u64) -> ::std::string::String {
+        let title: ::std::string::String = title
+            .chars()
+            .map(|character| match character {
+                '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+                _ if character.is_control() => '_',
+                _ => character,
+            })
+            .collect();
+
+        let mut name = format!("{:02} - {}", number, title.trim_matches([' ', '.']));
+
+        while name.len() as u64 > max_length {
+            name.pop();
+        }
+
+        name.trim_end_matches([' ', '.']).to_owned()
+    }
+}
+
+struct CueSheet<'a> {
+    file_name: &'a str,
+    // Players read anything but MP3 and AIFF as `WAVE`
+    file_type: &'a str,
+    title: Option<&'a str>,
+    performer: Option<&'a str>,
+    chapters: &'a [VideoChapter],
+}
+
+impl CueSheet<'_> {
+    // Cue sheets have no escapes, so that double quotes become single ones
+    fn format(&self) -> ::std::string::String {
+        let quote = |text: &str| format!("\"{}\"", text.replace('"', "'"));
+
+        let mut sheet = ::std::string::String::new();
+
+        if let Some(performer) = self.performer {
+            sheet.push_str(&format!("PERFORMER {}\n", quote(performer)));
+        }
+
+        if let Some(title) = self.title {
+            sheet.push_str(&format!("TITLE {}\n", quote(title)));
+        }
+
+        sheet.push_str(&format!("FILE {} {}\n", quote(self.file_name), self.file_type));
+
+        for (position, chapter) in self.chapters.iter().enumerate() {
+            // Indices count frames, of which there are 75 every second
+            let frames = chapter.start as u64 * 75 / 1000;
+
+            sheet.push_str(&format!("  TRACK {:02} AUDIO\n", position + 1));
+            sheet.push_str(&format!("    TITLE {}\n", quote(&chapter.title)));
+
+            if let Some(performer) = self.performer {
+                sheet.push_str(&format!("    PERFORMER {}\n", quote(performer)));
+            }
+
+            sheet.push_str(&format!(
+                "    INDEX 01 {:02}:{:02}:{:02}\n",
+                frames / 75 / 60,
+                frames / 75 % 60,
+                frames % 75
+            ));
+        }
+
+        sheet
+    }
+}
+
+pub(crate) struct CommandRunner;
+
+pub(crate) struct CommandLines {
+    stdout: Vec<MaybeOwnedString>,
+    stderr: Vec<MaybeOwnedString>,
+}
+
+impl CommandRunner {
+    // Failures are told by the last line of the standard error, where ffmpeg and
+    // yt-dlp report everything but what they are asked to print
+    pub(crate) async fn run(executor: &dyn CommandExecutor, program: &str, args: &[&str]) -> Fallible<CommandLines> {
+        let (mut stdout, mut stderr, status) = executor.execute(program, args)?;
+
+        let mut stdout_lines = Vec::new();
+        let mut stderr_lines = Vec::new();
+
+        ::tokio::join!(
+            async {
+                while let Some(line) = stdout.next().await {
+                    stdout_lines.push(line);
+                }
+            },
+            async {
+                while let Some(line) = stderr.next().await {
+                    stderr_lines.push(line);
+                }
+            },
+        );
+
+        match (status.await, stderr_lines.last()) {
+            (Some(0), _) => Ok(CommandLines {
+                stdout: stdout_lines,
+                stderr: stderr_lines,
+            }),
+            (Some(_), Some(line)) => ::anyhow::bail!("{} exited unsuccessfully: {}", program, line),
+            (Some(_), None) => ::anyhow::bail!("{} exited unsuccessfully", program),
+            (None, _) => ::anyhow::bail!("{} was terminated", program),
+        }
+    }
+}
+
+struct Id3TagReader;
+
+impl Id3TagReader {
+    fn read(path: &::std::path::Path) -> Fallible<::id3::Tag> {
+        match ::id3::Tag::read_from_path(path) {
+            Ok(tag) => Ok(tag),
+            Err(error) if matches!(error.kind, ::id3::ErrorKind::NoTag) => Ok(::id3::Tag::new()),
+            Err(error) => Err(error.into()),
+        }
+    }
+}
+
+struct Id3TagWriter;
+
+impl Id3TagWriter {
+    // Every frame is encoded the same way, so that the device reads them all
+    fn write(tag: &::id3::Tag, path: &::std::path::Path, profile: &DeviceProfile) -> Fallible<()> {
+        use ::id3::TagLike as _;
+
+        let mut encoded = ::id3::Tag::new();
+
+        tag.frames().for_each(|frame| {
+            encoded.add_frame(frame.clone().set_encoding(Some(profile.text_encoding.into())));
+        });
+
+        encoded.write_to_path(path, profile.id3_version.into())?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(
+        parser: &TitleParser, title: &str, artists: &[&str],
+    ) -> (::std::string::String, Vec<::std::string::String>) {
+        let artists: Vec<MaybeOwnedString> = artists.iter().map(|artist| (*artist).to_owned().into()).collect();
+
+        let metadata = VideoMetadata::builder()
+            .title(Some(title.to_owned().into()))
+            .album(None)
+            .artists(artists.is_empty().not().then(|| artists.into()))
+            .genres(None)
+            .build();
+
+        let metadata = parser.parse(&metadata);
+
+        (
+            metadata.title.unwrap_or_default().into_owned(),
+            metadata
+                .artists
+                .as_deref()
+                .unwrap_or_default()
+                .iter()
+                .map(|artist| artist.to_string())
+                .collect(),
+        )
+    }
+
+    fn lyric_lines(lines: &[LyricLine]) -> Vec<(u32, &str)> {
+        lines.iter().map(|line| (line.timestamp, &*line.text)).collect()
+    }
+
+    #[test]
+    fn titles_are_split_into_artists_and_cleaned() {
+        let parser = TitleParser::builder().build();
+
+        #[rustfmt::skip]
+        let cases: &[(&str, &[&str], &str, &[&str])] = &[
+            ("ARTIST - Song (Official Music Video) [4K]", &[], "Song", &["ARTIST"]),
+            ("Artist - Song feat. Guest", &[], "Song", &["Artist", "Guest"]),
+            ("Artist ft. Guest - Song (Lyrics)", &[], "Song", &["Artist", "Guest"]),
+            ("Artist - Song (feat. One & Two)", &[], "Song", &["Artist", "One", "Two"]),
+            ("Artist - Song | Official Audio", &[], "Song", &["Artist"]),
+            ("artist - Song", &["Artist"], "Song", &["Artist"]),
+            // The left side names someone else than the video's artist
+            ("Song - Live", &["Singer"], "Song - Live", &["Singer"]),
+            ("Song", &[], "Song", &[]),
+        ];
+
+        for (title, artists, expected_title, expected_artists) in cases {
+            let expected_artists: Vec<_> = expected_artists.iter().map(ToString::to_string).collect();
+
+            assert_eq!(parsed(&parser, title, artists), (expected_title.to_string(), expected_artists), "{}", title);
+        }
+    }
+
+    #[test]
+    fn rules_come_before_the_built_in_ones() {
+        let parser = TitleParser::builder()
+            .rules(vec![::regex::Regex::new(r"^(?P<title>.+) / (?P<artist>.+)$").unwrap()])
+            .build();
+
+        assert_eq!(parsed(&parser, "Song / Artist", &[]), ("Song".to_owned(), vec!["Artist".to_owned()]));
+        assert_eq!(parsed(&parser, "Artist - Song", &[]), ("Song".to_owned(), vec!["Artist".to_owned()]));
+    }
+
+    #[test]
+    fn channel_titles_lose_their_suffixes() {
+        #[rustfmt::skip]
+        let cases = [
+            ("Artist - Topic", "Artist"),
+            ("ArtistVEVO", "Artist"),
+            ("Artist Vevo", "Artist"),
+            ("VEVO", "VEVO"),
+            ("Artist", "Artist"),
+        ];
+
+        for (title, expected_title) in cases {
+            assert_eq!(TitleParser::clean_channel_title(title), expected_title, "{}", title);
+        }
+    }
+
+    #[test]
+    fn timestamps_are_read_as_milliseconds() {
+        #[rustfmt::skip]
+        let cases = [
+            ("00:01.500", Some(1_500)),
+            ("00:12.34", Some(12_340)),
+            ("1:02:03", Some(3_723_000)),
+            ("03:15", Some(195_000)),
+            ("ar:Artist", None),
+            ("1:x", None),
+            // Past what fits, rather than wrapped around
+            ("1999:01:01", None),
+            ("4294967:00", None),
+        ];
+
+        for (timestamp, expected) in cases {
+            assert_eq!(TimestampParser::parse(timestamp), expected, "{}", timestamp);
+        }
+    }
+
+    #[test]
+    fn captions_are_read_without_markup_or_repeats() {
+        let captions = "WEBVTT\nKind: captions\nLanguage: en\n\n00:00:01.000 --> 00:00:02.000 \
+                        align:start\n<c>First</c> line\n\n00:00:02.000 --> 00:00:03.000\nFirst line\n\n00:00:03.500 \
+                        --> 00:00:04.000\n[Music]\n\n1\n00:01:02.250 --> 00:01:03.000\n♪ Rock &amp; roll ♪\n";
+
+        assert_eq!(lyric_lines(&WebVttParser::parse(captions)), [(1_000, "First line"), (62_250, "Rock & roll")]);
+    }
+
+    #[test]
+    fn lrc_files_are_read_in_order_with_repeated_lines() {
+        let lrc = "[ar:Artist]\n[ti:Song]\n[00:12.34][01:00.00]Chorus\n[00:05.00] Verse\nno timestamp\n";
+
+        assert_eq!(lyric_lines(&LrcParser::parse(lrc)), [(5_000, "Verse"), (12_340, "Chorus"), (60_000, "Chorus")]);
+    }
+
+    #[test]
+    fn tracklists_are_read_from_descriptions() {
+        let description = "Tracklist:\n0:30 First Song\n2. Second Song - 03:15\n[1:02:03] Third\n0:10 Mentioned \
+                           again\nsee 99:99 later";
+
+        let chapters = TracklistParser::parse(description, 4_000_000)
+            .into_iter()
+            .map(|chapter| (chapter.start, chapter.end, chapter.title))
+            .collect::<Vec<_>>();
+
+        assert_eq!(chapters, [
+            (0, 195_000, "First Song".to_owned()),
+            (195_000, 3_723_000, "Second Song".to_owned()),
+            (3_723_000, 4_000_000, "Third".to_owned()),
+        ]);
+    }
 }
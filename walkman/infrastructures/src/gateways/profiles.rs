@@ -0,0 +1,273 @@
+use crate::utils::aliases::Fallible;
+use crate::utils::aliases::MaybeOwnedString;
+
+#[derive(Debug, Clone)]
+#[derive(::bon::Builder)]
+#[builder(on(_, into))]
+pub struct DeviceProfile {
+    pub(crate) name: MaybeOwnedString,
+
+    pub(crate) codec: AudioCodec,
+    pub(crate) bitrate: AudioBitrate,
+    pub(crate) max_sample_rate: u64,
+    pub(crate) channels: u64,
+
+    pub(crate) id3_version: Id3Version,
+    pub(crate) text_encoding: TextEncoding,
+    // Pixels on each side of the square cover
+    pub(crate) max_cover_size: u64,
+
+    pub(crate) max_file_name_length: u64,
+    pub(crate) max_path_length: u64,
+    pub(crate) max_files_per_folder: Option<u64>,
+
+    // Shared by every clone, so that the videos and the feeds draw on the same
+    // folder capacity
+    #[builder(skip)]
+    folder_slots: ::std::sync::Arc<FolderSlots>,
+}
+
+// Files counted per folder, which are read from disk only the first time
+#[derive(Debug, Default)]
+struct FolderSlots(::std::sync::Mutex<::std::collections::HashMap<::std::path::PathBuf, u64>>);
+
+// Holds a file's place in its folder until the file is kept, and gives it back
+// otherwise
+pub(crate) struct FolderSlot {
+    folder_slots: ::std::sync::Arc<FolderSlots>,
+    directory: ::std::path::PathBuf,
+    kept: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum AudioCodec {
+    Mp3,
+    Aac,
+    Wma,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum AudioBitrate {
+    // Kilobits per second
+    Constant(u64),
+    // From 0 (best) to 9 (worst), as the encoders understand it
+    Variable(u64),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Id3Version {
+    Id3v23,
+    Id3v24,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum TextEncoding {
+    Latin1,
+    Utf16,
+    Utf8,
+}
+
+impl DeviceProfile {
+    pub fn nwz_b183f() -> Self {
+        Self::builder()
+            .name("nwz-b183f")
+            .codec(AudioCodec::Mp3)
+            .bitrate(AudioBitrate::Constant(192))
+            .max_sample_rate(44100_u64)
+            .channels(2_u64)
+            .id3_version(Id3Version::Id3v23)
+            .text_encoding(TextEncoding::Utf16)
+            .max_cover_size(300_u64)
+            .max_file_name_length(128_u64)
+            .max_path_length(255_u64)
+            .max_files_per_folder(4000_u64)
+            .build()
+    }
+
+    pub fn modern_player() -> Self {
+        Self::builder()
+            .name("modern-player")
+            .codec(AudioCodec::Mp3)
+            .bitrate(AudioBitrate::Variable(0))
+            .max_sample_rate(48000_u64)
+            .channels(2_u64)
+            .id3_version(Id3Version::Id3v24)
+            .text_encoding(TextEncoding::Utf8)
+            .max_cover_size(1000_u64)
+            .max_file_name_length(255_u64)
+            .max_path_length(4096_u64)
+            .build()
+    }
+
+    // The extension is left out, since yt-dlp trims the file name without it
+    pub(crate) fn max_file_stem_length(&self, directory: &::std::path::Path) -> u64 {
+        let extension_length = self.codec.extension().len() as u64 + 1;
+        let directory_length = directory.as_os_str().len() as u64 + 1;
+
+        self.max_file_name_length
+            .min(self.max_path_length.saturating_sub(directory_length))
+            .saturating_sub(extension_length)
+            .max(1)
+    }
+
+    // Files that are already there are counted against the limit, and so are the
+    // ones still downloading, so that concurrent downloads never take the folder
+    // past what the device can list
+    pub(crate) async fn reserve_folder_slot(&self, directory: &::std::path::Path) -> Fallible<FolderSlot> {
+        // The folder is read outside of the lock, and whoever counts first wins
+        let files = match self.folder_slots.files(directory) {
+            Some(_) => 0,
+            None => self.count_files(directory).await?,
+        };
+
+        let mut files_by_directories = self.folder_slots.lock();
+        let files = files_by_directories.entry(directory.to_owned()).or_insert(files);
+
+        if let Some(max_files_per_folder) = self.max_files_per_folder {
+            if *files >= max_files_per_folder {
+                ::anyhow::bail!(
+                    "`{}` already holds {} files, while the `{}` profile allows at most {}",
+                    directory.display(),
+                    files,
+                    self.name,
+                    max_files_per_folder
+                );
+            }
+        }
+
+        *files += 1;
+
+        Ok(FolderSlot {
+            folder_slots: ::std::sync::Arc::clone(&self.folder_slots),
+            directory: directory.to_owned(),
+            kept: false,
+        })
+    }
+
+    async fn count_files(&self, directory: &::std::path::Path) -> Fallible<u64> {
+        if self.max_files_per_folder.is_none() {
+            return Ok(0);
+        }
+
+        let mut entries = match ::tokio::fs::read_dir(directory).await {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == ::std::io::ErrorKind::NotFound => return Ok(0),
+            Err(error) => return Err(error.into()),
+        };
+
+        let mut files = 0;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+
+            if path.extension().is_some_and(|extension| extension == self.codec.extension()) {
+                files += 1;
+            }
+        }
+
+        Ok(files)
+    }
+}
+
+impl FolderSlots {
+    fn files(&self, directory: &::std::path::Path) -> Option<u64> {
+        self.lock().get(directory).copied()
+    }
+
+    fn lock(&self) -> ::std::sync::MutexGuard<'_, ::std::collections::HashMap<::std::path::PathBuf, u64>> {
+        self.0.lock().unwrap_or_else(::std::sync::PoisonError::into_inner)
+    }
+}
+
+impl FolderSlot {
+    pub(crate) fn keep(mut self) {
+        self.kept = true;
+    }
+}
+
+impl Drop for FolderSlot {
+    fn drop(&mut self) {
+        if self.kept {
+            return;
+        }
+
+        if let Some(files) = self.folder_slots.lock().get_mut(&self.directory) {
+            *files = files.saturating_sub(1);
+        }
+    }
+}
+
+impl AudioCodec {
+    pub(crate) fn extension(&self) -> &'static str {
+        match self {
+            AudioCodec::Mp3 => "mp3",
+            AudioCodec::Aac => "m4a",
+            AudioCodec::Wma => "wma",
+        }
+    }
+
+    // yt-dlp extracts no WMA audio, so that such profiles are refused upfront
+    pub(crate) fn ytdlp_audio_format(&self) -> Fallible<&'static str> {
+        match self {
+            AudioCodec::Mp3 => Ok("mp3"),
+            AudioCodec::Aac => Ok("m4a"),
+            AudioCodec::Wma => ::anyhow::bail!("yt-dlp cannot extract WMA audio"),
+        }
+    }
+
+    pub(crate) fn ffmpeg_encoder(&self) -> &'static str {
+        match self {
+            AudioCodec::Mp3 => "libmp3lame",
+            AudioCodec::Aac => "aac",
+            AudioCodec::Wma => "wmav2",
+        }
+    }
+
+    pub(crate) fn ffmpeg_muxer(&self) -> &'static str {
+        match self {
+            AudioCodec::Mp3 => "mp3",
+            AudioCodec::Aac => "ipod",
+            AudioCodec::Wma => "asf",
+        }
+    }
+
+    // Only MP3 files carry ID3 tags, the other containers have their own
+    pub(crate) fn carries_id3_tags(&self) -> bool {
+        matches!(self, AudioCodec::Mp3)
+    }
+}
+
+impl AudioBitrate {
+    pub(crate) fn ytdlp_audio_quality(&self) -> ::std::string::String {
+        match self {
+            AudioBitrate::Constant(kilobits) => format!("{}K", kilobits),
+            AudioBitrate::Variable(quality) => quality.to_string(),
+        }
+    }
+
+    pub(crate) fn ffmpeg_args(&self) -> [::std::string::String; 2] {
+        match self {
+            AudioBitrate::Constant(kilobits) => ["-b:a".into(), format!("{}k", kilobits)],
+            AudioBitrate::Variable(quality) => ["-q:a".into(), quality.to_string()],
+        }
+    }
+}
+
+impl From<Id3Version> for ::id3::Version {
+    fn from(version: Id3Version) -> Self {
+        match version {
+            Id3Version::Id3v23 => ::id3::Version::Id3v23,
+            Id3Version::Id3v24 => ::id3::Version::Id3v24,
+        }
+    }
+}
+
+impl From<TextEncoding> for ::id3::Encoding {
+    fn from(encoding: TextEncoding) -> Self {
+        match encoding {
+            TextEncoding::Latin1 => ::id3::Encoding::Latin1,
+            TextEncoding::Utf16 => ::id3::Encoding::UTF16,
+            TextEncoding::Utf8 => ::id3::Encoding::UTF8,
+        }
+    }
+}
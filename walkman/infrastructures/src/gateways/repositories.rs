@@ -1,9 +1,19 @@
 use ::async_trait::async_trait;
+use ::domain::Channel;
+use ::domain::ChannelMetadata;
 use ::domain::ChannelUrl;
+use ::domain::FeedUrl;
+use ::domain::Playlist;
+use ::domain::PlaylistMetadata;
 use ::domain::PlaylistUrl;
+use ::domain::Video;
+use ::domain::VideoMetadata;
 use ::domain::VideoUrl;
 use ::futures::prelude::*;
+use ::std::ops::Not;
 use ::use_cases::gateways::Insert;
+use ::use_cases::gateways::LibraryRepository;
+use ::use_cases::gateways::Remove;
 use ::use_cases::gateways::UrlRepository;
 
 use crate::utils::aliases::BoxedStream;
@@ -17,20 +27,23 @@ pub struct FilesystemResourcesRepository {
     video_urls_path: MaybeOwnedPath,
     playlist_urls_path: MaybeOwnedPath,
     channel_urls_path: MaybeOwnedPath,
+    feed_urls_path: MaybeOwnedPath,
 }
 
 #[async_trait]
 impl UrlRepository for FilesystemResourcesRepository {
     async fn values(
         self: ::std::sync::Arc<Self>,
-    ) -> Fallible<(BoxedStream<VideoUrl>, BoxedStream<PlaylistUrl>, BoxedStream<ChannelUrl>)> {
-        let (video_urls, playlist_urls, channel_urls) = ::tokio::try_join!(
+    ) -> Fallible<(BoxedStream<VideoUrl>, BoxedStream<PlaylistUrl>, BoxedStream<ChannelUrl>, BoxedStream<FeedUrl>)>
+    {
+        let (video_urls, playlist_urls, channel_urls, feed_urls) = ::tokio::try_join!(
+            ::std::sync::Arc::clone(&self).get(),
             ::std::sync::Arc::clone(&self).get(),
             ::std::sync::Arc::clone(&self).get(),
             ::std::sync::Arc::clone(&self).get(),
         )?;
 
-        Ok((video_urls, playlist_urls, channel_urls))
+        Ok((video_urls, playlist_urls, channel_urls, feed_urls))
     }
 }
 
@@ -85,6 +98,76 @@ impl Insert<ChannelUrl> for FilesystemResourcesRepository {
     }
 }
 
+#[async_trait]
+impl Insert<FeedUrl> for FilesystemResourcesRepository {
+    async fn insert(self: ::std::sync::Arc<Self>, url: FeedUrl) -> Fallible<()> {
+        use ::tokio::io::AsyncWriteExt as _;
+
+        let mut file = ::tokio::fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&self.feed_urls_path)
+            .await?;
+
+        file.write_all(format!("{}\n", *url).as_bytes()).await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Remove<VideoUrl> for FilesystemResourcesRepository {
+    async fn remove(self: ::std::sync::Arc<Self>, url: VideoUrl) -> Fallible<bool> {
+        self.remove_line(url, &self.video_urls_path).await
+    }
+}
+
+#[async_trait]
+impl Remove<PlaylistUrl> for FilesystemResourcesRepository {
+    async fn remove(self: ::std::sync::Arc<Self>, url: PlaylistUrl) -> Fallible<bool> {
+        self.remove_line(url, &self.playlist_urls_path).await
+    }
+}
+
+#[async_trait]
+impl Remove<ChannelUrl> for FilesystemResourcesRepository {
+    async fn remove(self: ::std::sync::Arc<Self>, url: ChannelUrl) -> Fallible<bool> {
+        self.remove_line(url, &self.channel_urls_path).await
+    }
+}
+
+#[async_trait]
+impl Remove<FeedUrl> for FilesystemResourcesRepository {
+    async fn remove(self: ::std::sync::Arc<Self>, url: FeedUrl) -> Fallible<bool> {
+        self.remove_line(url, &self.feed_urls_path).await
+    }
+}
+
+impl FilesystemResourcesRepository {
+    async fn remove_line<Url>(self: &::std::sync::Arc<Self>, url: Url, path: &::std::path::Path) -> Fallible<bool>
+    where
+        Url: PartialEq + ::std::ops::Deref<Target = MaybeOwnedString> + ::core::marker::Send,
+        Self: Get<BoxedStream<Url>>,
+    {
+        let urls: BoxedStream<Url> = ::std::sync::Arc::clone(self).get().await?;
+        let urls = urls.collect::<Vec<_>>().await;
+
+        if urls.contains(&url).not() {
+            return Ok(false);
+        }
+
+        let buffer = urls
+            .into_iter()
+            .filter(|other| *other != url)
+            .map(|url| format!("{}\n", *url))
+            .collect::<::std::string::String>();
+
+        ::tokio::fs::write(path, buffer).await?;
+
+        Ok(true)
+    }
+}
+
 #[async_trait]
 trait Get<Item>: ::core::marker::Send + ::core::marker::Sync {
     async fn get(self: ::std::sync::Arc<Self>) -> Fallible<Item>;
@@ -162,6 +245,30 @@ impl Get<BoxedStream<ChannelUrl>> for FilesystemResourcesRepository {
     }
 }
 
+#[async_trait]
+impl Get<BoxedStream<FeedUrl>> for FilesystemResourcesRepository {
+    async fn get(self: ::std::sync::Arc<Self>) -> Fallible<BoxedStream<FeedUrl>> {
+        use ::tokio::io::AsyncBufReadExt as _;
+
+        match ::tokio::fs::File::open(&self.feed_urls_path).await {
+            Ok(file) => {
+                let lines = ::tokio::io::BufReader::new(file).lines();
+
+                let urls = ::tokio_stream::wrappers::LinesStream::new(lines)
+                    .filter_map(|line| async move { line.ok() })
+                    .map(Into::<MaybeOwnedString>::into)
+                    .map(Into::<FeedUrl>::into);
+
+                Ok(::std::boxed::Box::pin(urls))
+            },
+
+            Err(err) if err.kind() == ::std::io::ErrorKind::NotFound =>
+                Ok(::std::boxed::Box::pin(::futures::stream::empty())),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
 #[derive(::bon::Builder)]
 #[builder(on(_, into), finish_fn(name = _build, vis = "pub(self)"))]
 pub struct CompressedSerializedFilesystemResourcesRepository<State = ::std::hash::RandomState> {
@@ -174,6 +281,9 @@ pub struct CompressedSerializedFilesystemResourcesRepository<State = ::std::hash
     #[builder(field = ::std::mem::MaybeUninit::uninit())]
     channel_urls_file: ::std::mem::MaybeUninit<::tokio::sync::Mutex<::tokio::fs::File>>,
 
+    #[builder(field = ::std::mem::MaybeUninit::uninit())]
+    feed_urls_file: ::std::mem::MaybeUninit<::tokio::sync::Mutex<::tokio::fs::File>>,
+
     serializer: ::std::sync::Arc<dyn Serializer<::std::collections::HashSet<MaybeOwnedString, State>>>,
     compressor: ::std::sync::Arc<dyn Compressor>,
 
@@ -188,6 +298,10 @@ pub struct CompressedSerializedFilesystemResourcesRepository<State = ::std::hash
     #[allow(unused)]
     #[builder(getter(vis = "pub(self)"))]
     channel_urls_path: MaybeOwnedPath,
+
+    #[allow(unused)]
+    #[builder(getter(vis = "pub(self)"))]
+    feed_urls_path: MaybeOwnedPath,
 }
 
 impl<State, BuilderState> CompressedSerializedFilesystemResourcesRepositoryBuilder<State, BuilderState>
@@ -201,12 +315,15 @@ where
         BuilderState::VideoUrlsPath: compressed_serialized_filesystem_resources_repository_builder::IsSet,
         BuilderState::PlaylistUrlsPath: compressed_serialized_filesystem_resources_repository_builder::IsSet,
         BuilderState::ChannelUrlsPath: compressed_serialized_filesystem_resources_repository_builder::IsSet,
+        BuilderState::FeedUrlsPath: compressed_serialized_filesystem_resources_repository_builder::IsSet,
     {
+        // The urls are read back from these very files, so that opening them must
+        // not truncate, and every write truncates on its own instead
         let video_urls_file = ::tokio::fs::OpenOptions::new()
             .read(true)
             .write(true)
             .create(true)
-            .truncate(true)
+            .truncate(false)
             .open(self.get_video_urls_path())
             .await?;
 
@@ -214,7 +331,7 @@ where
             .read(true)
             .write(true)
             .create(true)
-            .truncate(true)
+            .truncate(false)
             .open(self.get_playlist_urls_path())
             .await?;
 
@@ -222,15 +339,24 @@ where
             .read(true)
             .write(true)
             .create(true)
-            .truncate(true)
+            .truncate(false)
             .open(self.get_channel_urls_path())
             .await?;
 
+        let feed_urls_file = ::tokio::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(self.get_feed_urls_path())
+            .await?;
+
         let mut output = self._build();
 
         output.video_urls_file.write(::tokio::sync::Mutex::new(video_urls_file));
         output.playlist_urls_file.write(::tokio::sync::Mutex::new(playlist_urls_file));
         output.channel_urls_file.write(::tokio::sync::Mutex::new(channel_urls_file));
+        output.feed_urls_file.write(::tokio::sync::Mutex::new(feed_urls_file));
 
         Ok(output)
     }
@@ -243,14 +369,16 @@ where
 {
     async fn values(
         self: ::std::sync::Arc<Self>,
-    ) -> Fallible<(BoxedStream<VideoUrl>, BoxedStream<PlaylistUrl>, BoxedStream<ChannelUrl>)> {
-        let (video_urls, playlist_urls, channel_urls) = ::tokio::try_join!(
+    ) -> Fallible<(BoxedStream<VideoUrl>, BoxedStream<PlaylistUrl>, BoxedStream<ChannelUrl>, BoxedStream<FeedUrl>)>
+    {
+        let (video_urls, playlist_urls, channel_urls, feed_urls) = ::tokio::try_join!(
+            ::std::sync::Arc::clone(&self).get(),
             ::std::sync::Arc::clone(&self).get(),
             ::std::sync::Arc::clone(&self).get(),
             ::std::sync::Arc::clone(&self).get(),
         )?;
 
-        Ok((video_urls, playlist_urls, channel_urls))
+        Ok((video_urls, playlist_urls, channel_urls, feed_urls))
     }
 }
 
@@ -338,6 +466,120 @@ where
     }
 }
 
+#[async_trait]
+impl<State> Insert<FeedUrl> for CompressedSerializedFilesystemResourcesRepository<State>
+where
+    State: ::std::hash::BuildHasher + Default + ::core::marker::Send,
+{
+    async fn insert(self: ::std::sync::Arc<Self>, url: FeedUrl) -> Fallible<()> {
+        use ::tokio::io::AsyncSeekExt as _;
+        use ::tokio::io::AsyncWriteExt as _;
+
+        let mut urls: ::std::collections::HashSet<FeedUrl, State> = ::std::sync::Arc::clone(&self).get().await?;
+        urls.insert(url);
+
+        let urls = urls.into_iter().map(Into::into).collect();
+
+        let buffer = ::std::sync::Arc::clone(&self.serializer).serialize(urls)?;
+        let buffer = ::std::sync::Arc::clone(&self.compressor).compress(buffer)?;
+
+        let mut file = unsafe { self.feed_urls_file.assume_init_ref() }.lock().await;
+        file.seek(::std::io::SeekFrom::Start(0)).await?;
+        file.set_len(0).await?;
+
+        file.write_all(&buffer).await?;
+        file.flush().await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<State> Remove<VideoUrl> for CompressedSerializedFilesystemResourcesRepository<State>
+where
+    State: ::std::hash::BuildHasher + Default + ::core::marker::Send,
+{
+    async fn remove(self: ::std::sync::Arc<Self>, url: VideoUrl) -> Fallible<bool> {
+        self.remove_url(url, unsafe { self.video_urls_file.assume_init_ref() }).await
+    }
+}
+
+#[async_trait]
+impl<State> Remove<PlaylistUrl> for CompressedSerializedFilesystemResourcesRepository<State>
+where
+    State: ::std::hash::BuildHasher + Default + ::core::marker::Send,
+{
+    async fn remove(self: ::std::sync::Arc<Self>, url: PlaylistUrl) -> Fallible<bool> {
+        self.remove_url(url, unsafe { self.playlist_urls_file.assume_init_ref() }).await
+    }
+}
+
+#[async_trait]
+impl<State> Remove<ChannelUrl> for CompressedSerializedFilesystemResourcesRepository<State>
+where
+    State: ::std::hash::BuildHasher + Default + ::core::marker::Send,
+{
+    async fn remove(self: ::std::sync::Arc<Self>, url: ChannelUrl) -> Fallible<bool> {
+        self.remove_url(url, unsafe { self.channel_urls_file.assume_init_ref() }).await
+    }
+}
+
+#[async_trait]
+impl<State> Remove<FeedUrl> for CompressedSerializedFilesystemResourcesRepository<State>
+where
+    State: ::std::hash::BuildHasher + Default + ::core::marker::Send,
+{
+    async fn remove(self: ::std::sync::Arc<Self>, url: FeedUrl) -> Fallible<bool> {
+        self.remove_url(url, unsafe { self.feed_urls_file.assume_init_ref() }).await
+    }
+}
+
+impl<State> CompressedSerializedFilesystemResourcesRepository<State>
+where
+    State: ::std::hash::BuildHasher + Default + ::core::marker::Send,
+{
+    async fn remove_url<Url>(
+        self: &::std::sync::Arc<Self>, url: Url, file: &::tokio::sync::Mutex<::tokio::fs::File>,
+    ) -> Fallible<bool>
+    where
+        Url: ::std::hash::Hash + Eq + Into<MaybeOwnedString> + ::core::marker::Send,
+        Self: Get<::std::collections::HashSet<Url, State>>,
+    {
+        use ::tokio::io::AsyncSeekExt as _;
+        use ::tokio::io::AsyncWriteExt as _;
+
+        let mut urls: ::std::collections::HashSet<Url, State> = ::std::sync::Arc::clone(self).get().await?;
+
+        if urls.remove(&url).not() {
+            return Ok(false);
+        }
+
+        let urls = urls.into_iter().map(Into::into).collect();
+
+        let buffer = ::std::sync::Arc::clone(&self.serializer).serialize(urls)?;
+        let buffer = ::std::sync::Arc::clone(&self.compressor).compress(buffer)?;
+
+        let mut file = file.lock().await;
+        file.seek(::std::io::SeekFrom::Start(0)).await?;
+        file.set_len(0).await?;
+
+        file.write_all(&buffer).await?;
+        file.flush().await?;
+
+        Ok(true)
+    }
+
+    // Every file is locked in turn, so that a write in flight is waited for rather
+    // than torn down mid-way by a forced exit
+    pub async fn flush(&self) -> Fallible<()> {
+        for file in [&self.video_urls_file, &self.playlist_urls_file, &self.channel_urls_file, &self.feed_urls_file] {
+            unsafe { file.assume_init_ref() }.lock().await.sync_all().await?;
+        }
+
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl<State> Get<BoxedStream<VideoUrl>> for CompressedSerializedFilesystemResourcesRepository<State>
 where
@@ -371,6 +613,17 @@ where
     }
 }
 
+#[async_trait]
+impl<State> Get<BoxedStream<FeedUrl>> for CompressedSerializedFilesystemResourcesRepository<State>
+where
+    State: ::std::hash::BuildHasher + Default,
+{
+    async fn get(self: ::std::sync::Arc<Self>) -> Fallible<BoxedStream<FeedUrl>> {
+        let urls: ::std::collections::HashSet<FeedUrl, State> = self.get().await?;
+        Ok(::std::boxed::Box::pin(::futures::stream::iter(urls)))
+    }
+}
+
 #[async_trait]
 impl<State> Get<::std::collections::HashSet<VideoUrl, State>>
     for CompressedSerializedFilesystemResourcesRepository<State>
@@ -446,6 +699,501 @@ where
     }
 }
 
+#[async_trait]
+impl<State> Get<::std::collections::HashSet<FeedUrl, State>>
+    for CompressedSerializedFilesystemResourcesRepository<State>
+where
+    State: ::std::hash::BuildHasher + Default,
+{
+    async fn get(self: ::std::sync::Arc<Self>) -> Fallible<::std::collections::HashSet<FeedUrl, State>> {
+        use ::tokio::io::AsyncReadExt as _;
+        use ::tokio::io::AsyncSeekExt as _;
+
+        let mut file = unsafe { self.feed_urls_file.assume_init_ref() }.lock().await;
+        file.seek(::std::io::SeekFrom::Start(0)).await?;
+
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).await?;
+
+        let buffer = ::std::sync::Arc::clone(&self.compressor).decompress(buffer)?;
+        let urls = ::std::sync::Arc::clone(&self.serializer).deserialize(buffer)?;
+
+        let urls = urls.into_iter().map(Into::into).collect();
+
+        Ok(urls)
+    }
+}
+
+#[derive(::bon::Builder)]
+#[builder(on(_, into), finish_fn(name = _build, vis = "pub(self)"))]
+pub struct CompressedSerializedFilesystemLibraryRepository {
+    #[builder(field = ::std::mem::MaybeUninit::uninit())]
+    library_file: ::std::mem::MaybeUninit<::tokio::sync::Mutex<::tokio::fs::File>>,
+
+    serializer: ::std::sync::Arc<dyn Serializer<LibraryRecords>>,
+    compressor: ::std::sync::Arc<dyn Compressor>,
+
+    #[allow(unused)]
+    #[builder(getter(vis = "pub(self)"))]
+    library_path: MaybeOwnedPath,
+}
+
+impl<BuilderState> CompressedSerializedFilesystemLibraryRepositoryBuilder<BuilderState>
+where
+    BuilderState: compressed_serialized_filesystem_library_repository_builder::IsComplete,
+{
+    pub async fn build(self) -> Fallible<CompressedSerializedFilesystemLibraryRepository>
+    where
+        BuilderState::Serializer: compressed_serialized_filesystem_library_repository_builder::IsSet,
+        BuilderState::Compressor: compressed_serialized_filesystem_library_repository_builder::IsSet,
+        BuilderState::LibraryPath: compressed_serialized_filesystem_library_repository_builder::IsSet,
+    {
+        let library_file = ::tokio::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(self.get_library_path())
+            .await?;
+
+        let mut output = self._build();
+
+        output.library_file.write(::tokio::sync::Mutex::new(library_file));
+
+        Ok(output)
+    }
+}
+
+#[async_trait]
+impl LibraryRepository for CompressedSerializedFilesystemLibraryRepository {
+    async fn values(
+        self: ::std::sync::Arc<Self>,
+    ) -> Fallible<(BoxedStream<Video>, BoxedStream<Playlist>, BoxedStream<Channel>)> {
+        let mut file = unsafe { self.library_file.assume_init_ref() }.lock().await;
+        let library = self.load(&mut file).await?;
+
+        let videos = library.videos.into_iter().map(Into::into).collect::<Vec<Video>>();
+        let playlists = library.playlists.into_iter().map(Into::into).collect::<Vec<Playlist>>();
+        let channels = library.channels.into_iter().map(Into::into).collect::<Vec<Channel>>();
+
+        Ok((
+            ::std::boxed::Box::pin(::futures::stream::iter(videos)),
+            ::std::boxed::Box::pin(::futures::stream::iter(playlists)),
+            ::std::boxed::Box::pin(::futures::stream::iter(channels)),
+        ))
+    }
+}
+
+#[async_trait]
+impl Insert<Video> for CompressedSerializedFilesystemLibraryRepository {
+    async fn insert(self: ::std::sync::Arc<Self>, video: Video) -> Fallible<()> {
+        let mut file = unsafe { self.library_file.assume_init_ref() }.lock().await;
+        let mut library = self.load(&mut file).await?;
+
+        library.videos.retain(|record| record.id != **video.id);
+        library.videos.push(video.into());
+
+        self.store(&mut file, library).await
+    }
+}
+
+#[async_trait]
+impl Insert<Playlist> for CompressedSerializedFilesystemLibraryRepository {
+    async fn insert(self: ::std::sync::Arc<Self>, playlist: Playlist) -> Fallible<()> {
+        let mut file = unsafe { self.library_file.assume_init_ref() }.lock().await;
+        let mut library = self.load(&mut file).await?;
+
+        library.playlists.retain(|record| record.id != **playlist.id);
+        library.playlists.push(playlist.into());
+
+        self.store(&mut file, library).await
+    }
+}
+
+#[async_trait]
+impl Insert<Channel> for CompressedSerializedFilesystemLibraryRepository {
+    async fn insert(self: ::std::sync::Arc<Self>, channel: Channel) -> Fallible<()> {
+        let mut file = unsafe { self.library_file.assume_init_ref() }.lock().await;
+        let mut library = self.load(&mut file).await?;
+
+        library.channels.retain(|record| record.id != **channel.id);
+        library.channels.push(channel.into());
+
+        self.store(&mut file, library).await
+    }
+}
+
+#[async_trait]
+impl Remove<Video> for CompressedSerializedFilesystemLibraryRepository {
+    async fn remove(self: ::std::sync::Arc<Self>, video: Video) -> Fallible<bool> {
+        self.remove_record(|library| &mut library.videos, &video.id).await
+    }
+}
+
+#[async_trait]
+impl Remove<Playlist> for CompressedSerializedFilesystemLibraryRepository {
+    async fn remove(self: ::std::sync::Arc<Self>, playlist: Playlist) -> Fallible<bool> {
+        self.remove_record(|library| &mut library.playlists, &playlist.id).await
+    }
+}
+
+#[async_trait]
+impl Remove<Channel> for CompressedSerializedFilesystemLibraryRepository {
+    async fn remove(self: ::std::sync::Arc<Self>, channel: Channel) -> Fallible<bool> {
+        self.remove_record(|library| &mut library.channels, &channel.id).await
+    }
+}
+
+impl CompressedSerializedFilesystemLibraryRepository {
+    // The file is locked, so that a write in flight is waited for rather than torn
+    // down mid-way by a forced exit
+    pub async fn flush(&self) -> Fallible<()> {
+        unsafe { self.library_file.assume_init_ref() }.lock().await.sync_all().await?;
+
+        Ok(())
+    }
+
+    async fn remove_record<Record, Records>(&self, records: Records, id: &str) -> Fallible<bool>
+    where
+        Record: LibraryRecord,
+        Records: FnOnce(&mut LibraryRecords) -> &mut Vec<Record> + ::core::marker::Send,
+    {
+        let mut file = unsafe { self.library_file.assume_init_ref() }.lock().await;
+        let mut library = self.load(&mut file).await?;
+
+        let removed = {
+            let records = records(&mut library);
+
+            let count = records.len();
+            records.retain(|record| record.id() != id);
+
+            records.len() != count
+        };
+
+        if removed.not() {
+            return Ok(false);
+        }
+
+        self.store(&mut file, library).await?;
+
+        Ok(true)
+    }
+
+    async fn load(&self, file: &mut ::tokio::fs::File) -> Fallible<LibraryRecords> {
+        use ::tokio::io::AsyncReadExt as _;
+        use ::tokio::io::AsyncSeekExt as _;
+
+        file.seek(::std::io::SeekFrom::Start(0)).await?;
+
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).await?;
+
+        if buffer.is_empty() {
+            return Ok(Default::default());
+        }
+
+        let buffer = ::std::sync::Arc::clone(&self.compressor).decompress(buffer)?;
+        let library = ::std::sync::Arc::clone(&self.serializer).deserialize(buffer)?;
+
+        Ok(library)
+    }
+
+    async fn store(&self, file: &mut ::tokio::fs::File, library: LibraryRecords) -> Fallible<()> {
+        use ::tokio::io::AsyncSeekExt as _;
+        use ::tokio::io::AsyncWriteExt as _;
+
+        let buffer = ::std::sync::Arc::clone(&self.serializer).serialize(library)?;
+        let buffer = ::std::sync::Arc::clone(&self.compressor).compress(buffer)?;
+
+        file.seek(::std::io::SeekFrom::Start(0)).await?;
+        file.set_len(0).await?;
+
+        file.write_all(&buffer).await?;
+        file.flush().await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[derive(::bincode::Encode, ::bincode::Decode)]
+pub struct LibraryRecords {
+    videos: Vec<VideoRecord>,
+    playlists: Vec<PlaylistRecord>,
+    channels: Vec<ChannelRecord>,
+}
+
+impl LibraryRecords {
+    // Written ahead of the records and bumped whenever they change shape, so that
+    // older libraries are read through the layout they were written in
+    const MAGIC: &'static [u8] = b"walkman-library";
+    const VERSION: u8 = 2;
+}
+
+#[derive(Debug, Clone)]
+#[derive(::bincode::Encode, ::bincode::Decode)]
+struct VideoRecord {
+    id: ::std::string::String,
+    url: ::std::string::String,
+
+    title: Option<::std::string::String>,
+    album: Option<::std::string::String>,
+    artists: Option<Vec<::std::string::String>>,
+    genres: Option<Vec<::std::string::String>>,
+
+    path: ::std::path::PathBuf,
+
+    updated_at: Option<::std::time::SystemTime>,
+}
+
+impl From<Video> for VideoRecord {
+    fn from(video: Video) -> Self {
+        Self {
+            id: MaybeOwnedString::from(video.id).into_owned(),
+            url: MaybeOwnedString::from(video.url).into_owned(),
+            title: video.metadata.title.map(MaybeOwnedString::into_owned),
+            album: video.metadata.album.map(MaybeOwnedString::into_owned),
+            artists: video
+                .metadata
+                .artists
+                .map(|artists| artists.iter().cloned().map(MaybeOwnedString::into_owned).collect()),
+            genres: video
+                .metadata
+                .genres
+                .map(|genres| genres.iter().cloned().map(MaybeOwnedString::into_owned).collect()),
+            path: MaybeOwnedPath::from(video.path).into_owned(),
+            updated_at: Some(video.updated_at.unwrap_or_else(::std::time::SystemTime::now)),
+        }
+    }
+}
+
+impl From<VideoRecord> for Video {
+    fn from(record: VideoRecord) -> Self {
+        Self {
+            id: MaybeOwnedString::from(record.id).into(),
+            url: MaybeOwnedString::from(record.url).into(),
+            metadata: VideoMetadata {
+                title: record.title.map(Into::into),
+                album: record.album.map(Into::into),
+                artists: record
+                    .artists
+                    .map(|artists| artists.into_iter().map(Into::into).collect::<Vec<_>>().into()),
+                genres: record
+                    .genres
+                    .map(|genres| genres.into_iter().map(Into::into).collect::<Vec<_>>().into()),
+            },
+            path: MaybeOwnedPath::from(record.path).into(),
+            updated_at: record.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[derive(::bincode::Encode, ::bincode::Decode)]
+struct PlaylistRecord {
+    id: ::std::string::String,
+    url: ::std::string::String,
+
+    title: Option<::std::string::String>,
+
+    videos: Option<Vec<VideoRecord>>,
+
+    updated_at: Option<::std::time::SystemTime>,
+}
+
+impl From<Playlist> for PlaylistRecord {
+    fn from(playlist: Playlist) -> Self {
+        Self {
+            id: MaybeOwnedString::from(playlist.id).into_owned(),
+            url: MaybeOwnedString::from(playlist.url).into_owned(),
+            title: playlist.metadata.title.map(MaybeOwnedString::into_owned),
+            videos: playlist.videos.map(|videos| videos.iter().cloned().map(Into::into).collect()),
+            updated_at: Some(playlist.updated_at.unwrap_or_else(::std::time::SystemTime::now)),
+        }
+    }
+}
+
+impl From<PlaylistRecord> for Playlist {
+    fn from(record: PlaylistRecord) -> Self {
+        Self {
+            id: MaybeOwnedString::from(record.id).into(),
+            url: MaybeOwnedString::from(record.url).into(),
+            metadata: PlaylistMetadata { title: record.title.map(Into::into) },
+            videos: record
+                .videos
+                .map(|videos| videos.into_iter().map(Into::into).collect::<Vec<_>>().into()),
+            updated_at: record.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[derive(::bincode::Encode, ::bincode::Decode)]
+struct ChannelRecord {
+    id: ::std::string::String,
+    url: ::std::string::String,
+
+    title: Option<::std::string::String>,
+
+    videos: Option<Vec<VideoRecord>>,
+    playlists: Option<Vec<PlaylistRecord>>,
+
+    updated_at: Option<::std::time::SystemTime>,
+}
+
+impl From<Channel> for ChannelRecord {
+    fn from(channel: Channel) -> Self {
+        Self {
+            id: MaybeOwnedString::from(channel.id).into_owned(),
+            url: MaybeOwnedString::from(channel.url).into_owned(),
+            title: channel.metadata.title.map(MaybeOwnedString::into_owned),
+            videos: channel.videos.map(|videos| videos.iter().cloned().map(Into::into).collect()),
+            playlists: channel
+                .playlists
+                .map(|playlists| playlists.iter().cloned().map(Into::into).collect()),
+            updated_at: Some(channel.updated_at.unwrap_or_else(::std::time::SystemTime::now)),
+        }
+    }
+}
+
+impl From<ChannelRecord> for Channel {
+    fn from(record: ChannelRecord) -> Self {
+        Self {
+            id: MaybeOwnedString::from(record.id).into(),
+            url: MaybeOwnedString::from(record.url).into(),
+            metadata: ChannelMetadata { title: record.title.map(Into::into) },
+            videos: record
+                .videos
+                .map(|videos| videos.into_iter().map(Into::into).collect::<Vec<_>>().into()),
+            playlists: record
+                .playlists
+                .map(|playlists| playlists.into_iter().map(Into::into).collect::<Vec<_>>().into()),
+            updated_at: record.updated_at,
+        }
+    }
+}
+
+// The layout libraries were written in before they carried a header, so that
+// they are still read and rewritten in the current one
+#[derive(Debug, Clone)]
+#[derive(::bincode::Encode, ::bincode::Decode)]
+struct LegacyLibraryRecords {
+    videos: Vec<LegacyVideoRecord>,
+    playlists: Vec<LegacyPlaylistRecord>,
+    channels: Vec<LegacyChannelRecord>,
+}
+
+impl From<LegacyLibraryRecords> for LibraryRecords {
+    fn from(records: LegacyLibraryRecords) -> Self {
+        Self {
+            videos: records.videos.into_iter().map(Into::into).collect(),
+            playlists: records.playlists.into_iter().map(Into::into).collect(),
+            channels: records.channels.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[derive(::bincode::Encode, ::bincode::Decode)]
+struct LegacyVideoRecord {
+    id: ::std::string::String,
+    url: ::std::string::String,
+
+    title: Option<::std::string::String>,
+    album: Option<::std::string::String>,
+    artists: Option<Vec<::std::string::String>>,
+    genres: Option<Vec<::std::string::String>>,
+
+    path: ::std::path::PathBuf,
+}
+
+impl From<LegacyVideoRecord> for VideoRecord {
+    fn from(record: LegacyVideoRecord) -> Self {
+        Self {
+            id: record.id,
+            url: record.url,
+            title: record.title,
+            album: record.album,
+            artists: record.artists,
+            genres: record.genres,
+            path: record.path,
+            updated_at: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[derive(::bincode::Encode, ::bincode::Decode)]
+struct LegacyPlaylistRecord {
+    id: ::std::string::String,
+    url: ::std::string::String,
+
+    title: Option<::std::string::String>,
+
+    videos: Option<Vec<LegacyVideoRecord>>,
+}
+
+impl From<LegacyPlaylistRecord> for PlaylistRecord {
+    fn from(record: LegacyPlaylistRecord) -> Self {
+        Self {
+            id: record.id,
+            url: record.url,
+            title: record.title,
+            videos: record.videos.map(|videos| videos.into_iter().map(Into::into).collect()),
+            updated_at: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[derive(::bincode::Encode, ::bincode::Decode)]
+struct LegacyChannelRecord {
+    id: ::std::string::String,
+    url: ::std::string::String,
+
+    title: Option<::std::string::String>,
+
+    videos: Option<Vec<LegacyVideoRecord>>,
+    playlists: Option<Vec<LegacyPlaylistRecord>>,
+}
+
+impl From<LegacyChannelRecord> for ChannelRecord {
+    fn from(record: LegacyChannelRecord) -> Self {
+        Self {
+            id: record.id,
+            url: record.url,
+            title: record.title,
+            videos: record.videos.map(|videos| videos.into_iter().map(Into::into).collect()),
+            playlists: record
+                .playlists
+                .map(|playlists| playlists.into_iter().map(Into::into).collect()),
+            updated_at: None,
+        }
+    }
+}
+
+trait LibraryRecord: ::core::marker::Send {
+    fn id(&self) -> &str;
+}
+
+impl LibraryRecord for VideoRecord {
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl LibraryRecord for PlaylistRecord {
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl LibraryRecord for ChannelRecord {
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
 type Buffer = Vec<u8>;
 
 pub trait Serializer<Payload>: ::core::marker::Send + ::core::marker::Sync {
@@ -484,6 +1232,41 @@ where
     }
 }
 
+impl Serializer<LibraryRecords> for BincodeSerializer {
+    fn serialize(self: ::std::sync::Arc<Self>, payload: LibraryRecords) -> Fallible<Buffer> {
+        let mut buffer = LibraryRecords::MAGIC.to_vec();
+        buffer.push(LibraryRecords::VERSION);
+
+        ::bincode::encode_into_std_write(payload, &mut buffer, self.configurations)?;
+
+        Ok(buffer)
+    }
+
+    fn deserialize(self: ::std::sync::Arc<Self>, buffer: Buffer) -> Fallible<LibraryRecords> {
+        if buffer.is_empty() {
+            return Ok(Default::default());
+        }
+
+        let Some(buffer) = buffer.strip_prefix(LibraryRecords::MAGIC) else {
+            let (payload, length) =
+                ::bincode::decode_from_slice::<LegacyLibraryRecords, _>(&buffer, self.configurations)?;
+            ::anyhow::ensure!(length == buffer.len(), "library has an unrecognized layout");
+
+            return Ok(payload.into());
+        };
+
+        match buffer.split_first() {
+            Some((&LibraryRecords::VERSION, buffer)) => {
+                let (payload, _) = ::bincode::decode_from_slice(buffer, self.configurations)?;
+
+                Ok(payload)
+            },
+            Some((version, _)) => ::anyhow::bail!("unsupported library version `{}`", version),
+            None => ::anyhow::bail!("library is missing its version"),
+        }
+    }
+}
+
 pub trait Compressor: ::core::marker::Send + ::core::marker::Sync {
     fn compress(self: ::std::sync::Arc<Self>, buffer: Buffer) -> Fallible<Buffer>;
     fn decompress(self: ::std::sync::Arc<Self>, buffer: Buffer) -> Fallible<Buffer>;
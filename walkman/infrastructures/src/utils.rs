@@ -7,6 +7,8 @@ pub mod aliases {
 
     pub type BoxedStream<T> =
         ::std::pin::Pin<::std::boxed::Box<dyn ::futures::Stream<Item = T> + ::core::marker::Send>>;
+    pub type BoxedFuture<T> =
+        ::std::pin::Pin<::std::boxed::Box<dyn ::futures::Future<Output = T> + ::core::marker::Send>>;
 }
 
 pub mod extensions {
@@ -0,0 +1,16 @@
+pub struct TemporaryDirectory(pub ::std::path::PathBuf);
+
+impl TemporaryDirectory {
+    pub fn new(name: &str) -> Self {
+        let path = ::std::env::temp_dir().join(format!("walkman-{}-{}", name, ::std::process::id()));
+        ::std::fs::create_dir_all(&path).unwrap();
+
+        Self(path)
+    }
+}
+
+impl Drop for TemporaryDirectory {
+    fn drop(&mut self) {
+        ::std::fs::remove_dir_all(&self.0).ok();
+    }
+}
@@ -0,0 +1,327 @@
+use ::domain::ChannelUrl;
+use ::domain::FeedUrl;
+use ::domain::PlaylistUrl;
+use ::domain::VideoUrl;
+use ::futures::prelude::*;
+use ::infrastructures::gateways::downloaders::HttpFeedDownloader;
+use ::infrastructures::gateways::downloaders::YtdlpDownloader;
+use ::infrastructures::gateways::executors::ReplayCommandExecutor;
+use ::infrastructures::gateways::executors::TokioCommandExecutor;
+use ::infrastructures::gateways::profiles::DeviceProfile;
+use ::std::ops::Not;
+use ::use_cases::gateways::ChannelDownloader;
+use ::use_cases::gateways::FeedDownloader;
+use ::use_cases::gateways::PlaylistDownloader;
+use ::use_cases::gateways::VideoDownloader;
+use ::use_cases::models::descriptors::ResolvedVideo;
+use ::use_cases::models::descriptors::VideoMetadata;
+use ::use_cases::models::events::ChannelDownloadEvent;
+use ::use_cases::models::events::PlaylistDownloadEvent;
+use ::use_cases::models::events::VideoDownloadEvent;
+
+mod common;
+
+use common::TemporaryDirectory;
+
+type MaybeOwnedString = ::std::borrow::Cow<'static, str>;
+
+// Answers every request with the body registered for its path, one connection
+// per request
+struct LocalHttpServer(::tokio::net::TcpListener);
+
+impl LocalHttpServer {
+    async fn bind() -> Self {
+        Self(::tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap())
+    }
+
+    fn url(&self, path: &str) -> ::std::string::String {
+        format!("http://{}{}", self.0.local_addr().unwrap(), path)
+    }
+
+    fn serve(self, bodies: ::std::collections::HashMap<&'static str, Vec<u8>>) {
+        use ::tokio::io::AsyncReadExt as _;
+        use ::tokio::io::AsyncWriteExt as _;
+
+        let bodies = ::std::sync::Arc::new(bodies);
+
+        ::tokio::spawn(async move {
+            while let Ok((mut stream, _)) = self.0.accept().await {
+                let bodies = ::std::sync::Arc::clone(&bodies);
+
+                ::tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buffer = [0; 1024];
+
+                    while request.windows(4).any(|window| window == b"\r\n\r\n").not() {
+                        let read = stream.read(&mut buffer).await.unwrap();
+                        request.extend_from_slice(&buffer[..read]);
+                    }
+
+                    let request = ::std::string::String::from_utf8_lossy(&request);
+                    let path = request.split_whitespace().nth(1).unwrap_or_default();
+
+                    let response = match bodies.get(path) {
+                        Some(body) => [
+                            format!("HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n", body.len())
+                                .into_bytes(),
+                            body.clone(),
+                        ]
+                        .concat(),
+                        None => b"HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n".to_vec(),
+                    };
+
+                    stream.write_all(&response).await.unwrap();
+                });
+            }
+        });
+    }
+}
+
+// Fixtures were recorded into `/music`, which replaying never touches
+async fn replay(directory: &TemporaryDirectory, fixture: &str) -> ::std::sync::Arc<YtdlpDownloader> {
+    let executor = ReplayCommandExecutor::builder()
+        .path(
+            ::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/fixtures")
+                .join(fixture),
+        )
+        .build()
+        .await
+        .unwrap();
+
+    let downloader = YtdlpDownloader::builder()
+        .directory(directory.0.clone())
+        .profile(DeviceProfile::nwz_b183f())
+        .workers(1_u64)
+        .resolvers(1_u64)
+        .executor(::std::sync::Arc::new(executor) as ::std::sync::Arc<_>)
+        .build();
+
+    ::std::sync::Arc::new(downloader)
+}
+
+fn completed_video_ids(video_download_events: &[VideoDownloadEvent]) -> Vec<&str> {
+    let mut video_ids = video_download_events
+        .iter()
+        .filter_map(|event| match event {
+            VideoDownloadEvent::Completed(event) => Some(&*event.video.id),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    video_ids.sort();
+
+    video_ids
+}
+
+#[::tokio::test]
+async fn recorded_downloads_are_replayed() {
+    let directory = TemporaryDirectory::new("recorded-downloads-are-replayed");
+
+    let (video_download_events, diagnostic_events) = VideoDownloader::download(
+        replay(&directory, "video-download.jsonl").await,
+        VideoUrl::from(MaybeOwnedString::from("https://youtu.be/R1")),
+    )
+    .await
+    .unwrap();
+
+    let (video_download_events, diagnostic_events) =
+        ::tokio::join!(video_download_events.collect::<Vec<_>>(), diagnostic_events.collect::<Vec<_>>());
+
+    assert!(diagnostic_events.is_empty());
+
+    let completed_videos = video_download_events
+        .iter()
+        .filter_map(|event| match event {
+            VideoDownloadEvent::Completed(event) => Some(&event.video),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(completed_videos.len(), 1);
+    assert_eq!(&*completed_videos[0].id, "R1");
+    assert_eq!(completed_videos[0].metadata.title.as_deref(), Some("A; B R1"));
+
+    assert!(video_download_events
+        .iter()
+        .any(|event| matches!(event, VideoDownloadEvent::Failed(_)))
+        .not());
+}
+
+#[::tokio::test]
+async fn recorded_playlists_are_replayed_around_downloaded_videos() {
+    let directory = TemporaryDirectory::new("recorded-playlists-are-replayed-around-downloaded-videos");
+
+    let downloaded_video = ResolvedVideo::builder()
+        .id(MaybeOwnedString::from("R2"))
+        .url(MaybeOwnedString::from("https://youtu.be/R2"))
+        .metadata(
+            VideoMetadata::builder()
+                .title(Some("A; B R2".into()))
+                .album(None)
+                .artists(None)
+                .genres(None)
+                .build(),
+        )
+        .path(::std::borrow::Cow::from(::std::path::Path::new("/music/TR2.mp3")))
+        .build();
+
+    let (video_download_events, playlist_download_events, diagnostic_events) = PlaylistDownloader::download(
+        replay(&directory, "playlist-download.jsonl").await,
+        PlaylistUrl::from(MaybeOwnedString::from("https://youtube.com/playlist?list=R")),
+        vec![downloaded_video].into(),
+    )
+    .await
+    .unwrap();
+
+    let (video_download_events, playlist_download_events, diagnostic_events) = ::tokio::join!(
+        video_download_events.collect::<Vec<_>>(),
+        playlist_download_events.collect::<Vec<_>>(),
+        diagnostic_events.collect::<Vec<_>>()
+    );
+
+    assert!(diagnostic_events.is_empty());
+    assert_eq!(completed_video_ids(&video_download_events), ["R1", "R3"]);
+
+    let playlist = playlist_download_events
+        .iter()
+        .find_map(|event| match event {
+            PlaylistDownloadEvent::Completed(event) => Some(&event.playlist),
+            _ => None,
+        })
+        .unwrap();
+
+    // The downloaded video keeps its place and its index among the new ones
+    let videos = playlist
+        .videos
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(|video| (&*video.id, video.index))
+        .collect::<Vec<_>>();
+
+    assert_eq!(&*playlist.id, "R");
+    assert_eq!(videos, [("R1", Some(1)), ("R2", Some(2)), ("R3", Some(3))]);
+    assert_eq!(playlist.listed_videos, Some(3));
+}
+
+#[::tokio::test]
+async fn recorded_channels_are_replayed_past_failed_playlists() {
+    let directory = TemporaryDirectory::new("recorded-channels-are-replayed-past-failed-playlists");
+
+    let (video_download_events, playlist_download_events, channel_download_events, diagnostic_events) =
+        ChannelDownloader::download(
+            replay(&directory, "channel-download.jsonl").await,
+            ChannelUrl::from(MaybeOwnedString::from("https://youtube.com/@R")),
+            Vec::new().into(),
+        )
+        .await
+        .unwrap();
+
+    let (video_download_events, playlist_download_events, channel_download_events, ..) = ::tokio::join!(
+        video_download_events.collect::<Vec<_>>(),
+        playlist_download_events.collect::<Vec<_>>(),
+        channel_download_events.collect::<Vec<_>>(),
+        diagnostic_events.collect::<Vec<_>>()
+    );
+
+    assert_eq!(completed_video_ids(&video_download_events), ["P11", "P12", "P13", "c1", "c2", "c3"]);
+
+    let failed_playlist_urls = playlist_download_events
+        .iter()
+        .filter_map(|event| match event {
+            PlaylistDownloadEvent::Failed(event) => Some(&*event.url),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(failed_playlist_urls, ["https://youtube.com/playlist?list=BROKEN"]);
+
+    let channel = channel_download_events
+        .iter()
+        .find_map(|event| match event {
+            ChannelDownloadEvent::Completed(event) => Some(&event.channel),
+            _ => None,
+        })
+        .unwrap();
+
+    let video_ids = channel
+        .videos
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(|video| &*video.id)
+        .collect::<Vec<_>>();
+    let playlist_ids = channel
+        .playlists
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(|playlist| &*playlist.id)
+        .collect::<Vec<_>>();
+
+    assert_eq!(video_ids, ["c1", "c2", "c3"]);
+    assert_eq!(playlist_ids, ["P1"]);
+}
+
+#[::tokio::test]
+async fn feeds_keep_audio_episodes_under_unique_names() {
+    let directory = TemporaryDirectory::new("feeds-keep-audio-episodes-under-unique-names");
+
+    let server = LocalHttpServer::bind().await;
+    let feed_url = server.url("/feed.xml");
+    let feed = format!(
+        r#"<rss><channel><title>Show</title>
+            <item><title>Same Title</title><guid>ep-1</guid><enclosure url="{}" type="audio/mpeg"/></item>
+            <item><title>Same Title</title><guid>ep-2</guid><enclosure url="{}" type="audio/mpeg"/></item>
+            <item><title>Trailer</title><guid>ep-3</guid><enclosure url="{}" type="video/mp4"/></item>
+        </channel></rss>"#,
+        server.url("/ep-1.mp3"),
+        server.url("/ep-2.mp3"),
+        server.url("/ep-3.mp4"),
+    );
+
+    server.serve(::std::collections::HashMap::from([
+        ("/feed.xml", feed.into_bytes()),
+        ("/ep-1.mp3", b"first".to_vec()),
+        ("/ep-2.mp3", b"second".to_vec()),
+        ("/ep-3.mp4", b"trailer".to_vec()),
+    ]));
+
+    let downloader = HttpFeedDownloader::builder()
+        .directory(directory.0.clone())
+        .profile(DeviceProfile::nwz_b183f())
+        .workers(2_u64)
+        .executor(::std::sync::Arc::new(TokioCommandExecutor::builder().build()) as ::std::sync::Arc<_>)
+        .build();
+
+    let (video_download_events, playlist_download_events, diagnostic_events) = ::std::sync::Arc::new(downloader)
+        .download(FeedUrl::from(MaybeOwnedString::from(feed_url)), Vec::new().into())
+        .await
+        .unwrap();
+
+    let (video_download_events, ..) = ::tokio::join!(
+        video_download_events.collect::<Vec<_>>(),
+        playlist_download_events.collect::<Vec<_>>(),
+        diagnostic_events.collect::<Vec<_>>()
+    );
+
+    let mut completed_videos = video_download_events
+        .iter()
+        .filter_map(|event| match event {
+            VideoDownloadEvent::Completed(event) => Some(&event.video),
+            _ => None,
+        })
+        .map(|video| (&*video.id, ::std::fs::read(&*video.path).unwrap()))
+        .collect::<Vec<_>>();
+    completed_videos.sort();
+
+    assert_eq!(completed_videos, [("ep-1", b"first".to_vec()), ("ep-2", b"second".to_vec())]);
+
+    let mut file_names = ::std::fs::read_dir(&directory.0)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    file_names.sort();
+
+    assert_eq!(file_names, ["Same_Title.mp3", "Same_Title_2.mp3"]);
+}
@@ -0,0 +1,78 @@
+use ::infrastructures::gateways::formats::OpmlFormat;
+use ::infrastructures::gateways::formats::PlainTextFormat;
+use ::use_cases::gateways::Exporter;
+use ::use_cases::gateways::Importer;
+use ::use_cases::models::descriptors::SubscriptionUrls;
+
+mod common;
+
+use common::TemporaryDirectory;
+
+type MaybeOwnedString = ::std::borrow::Cow<'static, str>;
+
+fn subscription_urls() -> SubscriptionUrls {
+    SubscriptionUrls::builder()
+        .video_urls(vec![
+            MaybeOwnedString::from("https://youtu.be/a"),
+            MaybeOwnedString::from("https://youtu.be/b?t=1&si=c"),
+        ])
+        .playlist_urls(vec![MaybeOwnedString::from("https://youtube.com/playlist?list=d&si=e")])
+        .channel_urls(vec![MaybeOwnedString::from("https://youtube.com/@f")])
+        .feed_urls(vec![MaybeOwnedString::from("https://example.com/g.xml")])
+        .build()
+}
+
+fn sections(subscription_urls: &SubscriptionUrls) -> [Vec<&str>; 4] {
+    [
+        &subscription_urls.video_urls,
+        &subscription_urls.playlist_urls,
+        &subscription_urls.channel_urls,
+        &subscription_urls.feed_urls,
+    ]
+    .map(|urls| urls.iter().map(|url| &**url).collect())
+}
+
+#[::tokio::test]
+async fn subscriptions_survive_a_round_trip_through_every_format() {
+    let directory = TemporaryDirectory::new("subscriptions-survive-a-round-trip-through-every-format");
+
+    let opml_format = ::std::sync::Arc::new(OpmlFormat::builder().build());
+    let plain_text_format = ::std::sync::Arc::new(PlainTextFormat);
+
+    let formats = [
+        (
+            "subscriptions.opml",
+            ::std::sync::Arc::clone(&opml_format) as ::std::sync::Arc<dyn Exporter<_>>,
+            opml_format as ::std::sync::Arc<dyn Importer<_>>,
+        ),
+        ("subscriptions.txt", ::std::sync::Arc::clone(&plain_text_format) as _, plain_text_format as _),
+    ];
+
+    let subscription_urls = subscription_urls();
+
+    for (file_name, exporter, importer) in formats {
+        let path = directory.0.join(file_name);
+
+        exporter.export(&subscription_urls, &path).await.unwrap();
+        let imported_urls = importer.import(&path).await.unwrap();
+
+        assert_eq!(sections(&imported_urls), sections(&subscription_urls), "{}", file_name);
+    }
+}
+
+#[::tokio::test]
+async fn urls_outside_of_any_section_are_refused() {
+    let directory = TemporaryDirectory::new("urls-outside-of-any-section-are-refused");
+
+    let opml_path = directory.0.join("subscriptions.opml");
+    let plain_text_path = directory.0.join("subscriptions.txt");
+
+    ::std::fs::write(&opml_path, r#"<opml version="2.0"><body><outline url="https://youtu.be/a"/></body></opml>"#)
+        .unwrap();
+    ::std::fs::write(&plain_text_path, "https://youtu.be/a\n").unwrap();
+
+    let opml_format = ::std::sync::Arc::new(OpmlFormat::builder().build());
+
+    assert!(opml_format.import(&opml_path).await.is_err());
+    assert!(::std::sync::Arc::new(PlainTextFormat).import(&plain_text_path).await.is_err());
+}
@@ -0,0 +1,277 @@
+use ::domain::ChannelUrl;
+use ::domain::FeedUrl;
+use ::domain::PlaylistUrl;
+use ::domain::Video;
+use ::domain::VideoMetadata;
+use ::domain::VideoUrl;
+use ::futures::prelude::*;
+use ::infrastructures::gateways::formats::PlainTextFormat;
+use ::infrastructures::gateways::repositories::BincodeSerializer;
+use ::infrastructures::gateways::repositories::CompressedSerializedFilesystemLibraryRepository;
+use ::infrastructures::gateways::repositories::CompressedSerializedFilesystemResourcesRepository;
+use ::infrastructures::gateways::repositories::Flate2Compressor;
+use ::use_cases::boundaries::Accept;
+use ::use_cases::boundaries::Activate;
+use ::use_cases::boundaries::ImportSubscriptionsOutputBoundary;
+use ::use_cases::boundaries::ImportSubscriptionsRequestModel;
+use ::use_cases::boundaries::Update;
+use ::use_cases::gateways::Importer;
+use ::use_cases::gateways::Insert;
+use ::use_cases::gateways::LibraryRepository;
+use ::use_cases::gateways::Remove;
+use ::use_cases::gateways::UrlRepository;
+use ::use_cases::interactors::ImportSubscriptionsInteractor;
+use ::use_cases::models::events::DiagnosticEvent;
+use ::use_cases::models::events::SubscriptionImportEvent;
+
+mod common;
+
+use common::TemporaryDirectory;
+
+type MaybeOwnedString = ::std::borrow::Cow<'static, str>;
+
+async fn open(
+    directory: &TemporaryDirectory,
+) -> ::std::sync::Arc<CompressedSerializedFilesystemResourcesRepository<::std::hash::RandomState>> {
+    let repository = CompressedSerializedFilesystemResourcesRepository::builder()
+        .serializer(::std::sync::Arc::new(
+            BincodeSerializer::builder()
+                .configurations(::bincode::config::standard())
+                .build(),
+        ) as ::std::sync::Arc<_>)
+        .compressor(::std::sync::Arc::new(Flate2Compressor::builder().level(::flate2::Compression::default()).build())
+            as ::std::sync::Arc<_>)
+        .video_urls_path(directory.0.join("video-urls"))
+        .playlist_urls_path(directory.0.join("playlist-urls"))
+        .channel_urls_path(directory.0.join("channel-urls"))
+        .feed_urls_path(directory.0.join("feed-urls"))
+        .build()
+        .await
+        .unwrap();
+
+    ::std::sync::Arc::new(repository)
+}
+
+async fn video_urls(
+    repository: &::std::sync::Arc<CompressedSerializedFilesystemResourcesRepository<::std::hash::RandomState>>,
+) -> Vec<MaybeOwnedString> {
+    let (video_urls, ..) = ::std::sync::Arc::clone(repository).values().await.unwrap();
+
+    let mut video_urls = video_urls.map(MaybeOwnedString::from).collect::<Vec<_>>().await;
+    video_urls.sort();
+
+    video_urls
+}
+
+#[::tokio::test]
+async fn subscriptions_survive_reopening() {
+    let directory = TemporaryDirectory::new("subscriptions-survive-reopening");
+
+    let repository = open(&directory).await;
+    ::std::sync::Arc::clone(&repository)
+        .insert(VideoUrl::from(MaybeOwnedString::from("https://youtu.be/a")))
+        .await
+        .unwrap();
+    ::std::sync::Arc::clone(&repository)
+        .insert(PlaylistUrl::from(MaybeOwnedString::from("https://youtube.com/playlist?list=b")))
+        .await
+        .unwrap();
+    ::std::sync::Arc::clone(&repository)
+        .insert(ChannelUrl::from(MaybeOwnedString::from("https://youtube.com/@c")))
+        .await
+        .unwrap();
+    ::std::sync::Arc::clone(&repository)
+        .insert(FeedUrl::from(MaybeOwnedString::from("https://example.com/d.xml")))
+        .await
+        .unwrap();
+    ::core::mem::drop(repository);
+
+    let repository = open(&directory).await;
+    let (video_urls, playlist_urls, channel_urls, feed_urls) =
+        ::std::sync::Arc::clone(&repository).values().await.unwrap();
+
+    assert_eq!(video_urls.count().await, 1);
+    assert_eq!(playlist_urls.count().await, 1);
+    assert_eq!(channel_urls.count().await, 1);
+    assert_eq!(feed_urls.count().await, 1);
+}
+
+#[::tokio::test]
+async fn shorter_rewrites_leave_nothing_behind() {
+    let directory = TemporaryDirectory::new("shorter-rewrites-leave-nothing-behind");
+
+    let repository = open(&directory).await;
+
+    for index in 0..64 {
+        let url = format!("https://www.youtube.com/watch?v={:0>32}", index);
+
+        ::std::sync::Arc::clone(&repository)
+            .insert(VideoUrl::from(MaybeOwnedString::from(url)))
+            .await
+            .unwrap();
+    }
+
+    let length = ::std::fs::metadata(directory.0.join("video-urls")).unwrap().len();
+
+    for index in 1..64 {
+        let url = format!("https://www.youtube.com/watch?v={:0>32}", index);
+
+        let removed = ::std::sync::Arc::clone(&repository)
+            .remove(VideoUrl::from(MaybeOwnedString::from(url)))
+            .await
+            .unwrap();
+
+        assert!(removed);
+    }
+
+    assert!(::std::fs::metadata(directory.0.join("video-urls")).unwrap().len() < length);
+    assert_eq!(video_urls(&repository).await, [format!("https://www.youtube.com/watch?v={:0>32}", 0)]);
+    ::core::mem::drop(repository);
+
+    let repository = open(&directory).await;
+    assert_eq!(video_urls(&repository).await, [format!("https://www.youtube.com/watch?v={:0>32}", 0)]);
+}
+
+// Keeps the imported and skipped counts of every import
+#[derive(Default)]
+struct SubscriptionImportsView(::std::sync::Mutex<Vec<(u64, u64)>>);
+
+#[::async_trait::async_trait]
+impl Activate for SubscriptionImportsView {
+    async fn activate(self: ::std::sync::Arc<Self>) -> ::anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn deactivate(self: ::std::sync::Arc<Self>) -> ::anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+#[::async_trait::async_trait]
+impl Update<SubscriptionImportEvent> for SubscriptionImportsView {
+    async fn update(self: ::std::sync::Arc<Self>, event: &SubscriptionImportEvent) -> ::anyhow::Result<()> {
+        let SubscriptionImportEvent::Completed(event) = event;
+        self.0.lock().unwrap().push((event.imported_urls, event.skipped_urls));
+
+        Ok(())
+    }
+}
+
+#[::async_trait::async_trait]
+impl Update<DiagnosticEvent> for SubscriptionImportsView {
+    async fn update(self: ::std::sync::Arc<Self>, _: &DiagnosticEvent) -> ::anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+#[::tokio::test]
+async fn importing_subscriptions_twice_adds_them_once() {
+    let directory = TemporaryDirectory::new("importing-subscriptions-twice-adds-them-once");
+
+    let repository = open(&directory).await;
+    ::std::sync::Arc::clone(&repository)
+        .insert(VideoUrl::from(MaybeOwnedString::from("https://youtu.be/a")))
+        .await
+        .unwrap();
+
+    let path = directory.0.join("subscriptions.txt");
+    ::std::fs::write(&path, "# videos\nhttps://youtu.be/a\nhttps://youtu.be/b\n\n# feeds\nhttps://example.com/c.xml\n")
+        .unwrap();
+
+    let view = ::std::sync::Arc::new(SubscriptionImportsView::default());
+    let interactor = ::std::sync::Arc::new(
+        ImportSubscriptionsInteractor::builder()
+            .view(::std::sync::Arc::clone(&view) as ::std::sync::Arc<dyn ImportSubscriptionsOutputBoundary>)
+            .urls(::std::sync::Arc::clone(&repository) as ::std::sync::Arc<dyn UrlRepository>)
+            .importer(::std::sync::Arc::new(PlainTextFormat) as ::std::sync::Arc<dyn Importer<_>>)
+            .build(),
+    );
+
+    for _ in 0..2 {
+        let request = ImportSubscriptionsRequestModel::builder().path(path.clone()).build();
+        ::std::sync::Arc::clone(&interactor).accept(request).await.unwrap();
+    }
+
+    // The subscription that was already there is merged rather than duplicated
+    assert_eq!(*view.0.lock().unwrap(), [(2, 1), (0, 3)]);
+    assert_eq!(video_urls(&repository).await, ["https://youtu.be/a", "https://youtu.be/b"]);
+
+    let (.., feed_urls) = ::std::sync::Arc::clone(&repository).values().await.unwrap();
+    assert_eq!(feed_urls.count().await, 1);
+}
+
+async fn open_library(
+    directory: &TemporaryDirectory,
+) -> ::std::sync::Arc<CompressedSerializedFilesystemLibraryRepository> {
+    let library = CompressedSerializedFilesystemLibraryRepository::builder()
+        .serializer(::std::sync::Arc::new(
+            BincodeSerializer::builder()
+                .configurations(::bincode::config::standard())
+                .build(),
+        ) as ::std::sync::Arc<_>)
+        .compressor(::std::sync::Arc::new(Flate2Compressor::builder().level(::flate2::Compression::default()).build())
+            as ::std::sync::Arc<_>)
+        .library_path(directory.0.join("library"))
+        .build()
+        .await
+        .unwrap();
+
+    ::std::sync::Arc::new(library)
+}
+
+#[::tokio::test]
+async fn libraries_without_a_version_are_migrated() {
+    use ::std::io::Write as _;
+
+    let directory = TemporaryDirectory::new("libraries-without-a-version-are-migrated");
+
+    // Records are encoded field by field, the same way tuples are
+    let legacy_video = (
+        "a".to_owned(),
+        "https://youtu.be/a".to_owned(),
+        Some("Song".to_owned()),
+        None::<::std::string::String>,
+        Some(vec!["Artist".to_owned()]),
+        None::<Vec<::std::string::String>>,
+        ::std::path::PathBuf::from("/music/Song.mp3"),
+    );
+    let legacy_library = (vec![legacy_video], Vec::<u8>::new(), Vec::<u8>::new());
+
+    let buffer = ::bincode::encode_to_vec(legacy_library, ::bincode::config::standard()).unwrap();
+    let mut compressor = ::flate2::write::ZlibEncoder::new(Vec::new(), ::flate2::Compression::default());
+    compressor.write_all(&buffer).unwrap();
+    ::std::fs::write(directory.0.join("library"), compressor.finish().unwrap()).unwrap();
+
+    let library = open_library(&directory).await;
+    let (videos, ..) = ::std::sync::Arc::clone(&library).values().await.unwrap();
+    let videos = videos.collect::<Vec<_>>().await;
+
+    assert_eq!(videos.len(), 1);
+    assert_eq!(&**videos[0].id, "a");
+    assert_eq!(videos[0].metadata.title.as_deref(), Some("Song"));
+    assert_eq!(videos[0].updated_at, None);
+
+    let video = Video {
+        id: MaybeOwnedString::from("b").into(),
+        url: MaybeOwnedString::from("https://youtu.be/b").into(),
+        metadata: VideoMetadata {
+            title: None,
+            album: None,
+            artists: None,
+            genres: None,
+        },
+        path: ::std::borrow::Cow::Borrowed(::std::path::Path::new("/music/b.mp3")).into(),
+        updated_at: None,
+    };
+    ::std::sync::Arc::clone(&library).insert(video).await.unwrap();
+    ::core::mem::drop(library);
+
+    let library = open_library(&directory).await;
+    let (videos, ..) = ::std::sync::Arc::clone(&library).values().await.unwrap();
+    let mut videos = videos.collect::<Vec<_>>().await;
+    videos.sort_by(|this, other| this.id.cmp(&other.id));
+
+    assert_eq!(videos.len(), 2);
+    assert_eq!(videos[0].updated_at, None);
+    assert!(videos[1].updated_at.is_some());
+}